@@ -9,6 +9,7 @@ pub type NamedArgList<'ast> = Box<'ast, [NamedArg<'ast>]>;
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.expression>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Expr<'ast> {
     pub span: Span,
     pub kind: ExprKind<'ast>,
@@ -34,6 +35,7 @@ impl<'ast> Expr<'ast> {
 
 /// A kind of expression.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ExprKind<'ast> {
     /// An array literal expression: `[a, b, c, d]`.
     Array(Box<'ast, [Box<'ast, Expr<'ast>>]>),
@@ -89,6 +91,7 @@ pub enum ExprKind<'ast> {
 
 /// A binary operation: `a + b`, `a += b`.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BinOp {
     pub span: Span,
     pub kind: BinOpKind,
@@ -96,6 +99,7 @@ pub struct BinOp {
 
 /// A kind of binary operation.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum BinOpKind {
     /// `<`
     Lt,
@@ -205,6 +209,7 @@ impl BinOpKind {
 
 /// A unary operation: `!x`, `-x`, `x++`.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct UnOp {
     pub span: Span,
     pub kind: UnOpKind,
@@ -212,6 +217,7 @@ pub struct UnOp {
 
 /// A kind of unary operation.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum UnOpKind {
     /// `++x`
     PreInc,
@@ -266,6 +272,7 @@ impl UnOpKind {
 
 /// A list of function call arguments.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CallArgs<'ast> {
     /// A list of unnamed arguments: `(1, 2, 3)`.
     Unnamed(Box<'ast, [Box<'ast, Expr<'ast>>]>),
@@ -289,6 +296,7 @@ impl CallArgs<'_> {
 
 /// A named argument: `name: value`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NamedArg<'ast> {
     pub name: Ident,
     pub value: Box<'ast, Expr<'ast>>,
@@ -296,6 +304,7 @@ pub struct NamedArg<'ast> {
 
 /// A kind of square bracketed indexing expression: `vector[index]`, `slice[l:r]`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum IndexKind<'ast> {
     /// A single index: `vector[index]`.
     Index(Option<Box<'ast, Expr<'ast>>>),
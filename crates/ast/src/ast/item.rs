@@ -12,6 +12,7 @@ pub type ParameterList<'ast> = Box<'ast, [VariableDefinition<'ast>]>;
 
 /// A top-level item in a Solidity source file.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Item<'ast> {
     pub docs: DocComments<'ast>,
     pub span: Span,
@@ -39,6 +40,7 @@ impl Item<'_> {
 /// An AST item. A more expanded version of a [Solidity source unit][ref].
 ///
 /// [ref]: https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.sourceUnit
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ItemKind<'ast> {
     /// A pragma directive: `pragma solidity ^0.8.0;`
     Pragma(PragmaDirective<'ast>),
@@ -149,6 +151,7 @@ impl ItemKind<'_> {
 
 /// A pragma directive: `pragma solidity ^0.8.0;`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PragmaDirective<'ast> {
     /// The parsed or unparsed tokens of the pragma directive.
     pub tokens: PragmaTokens<'ast>,
@@ -156,6 +159,7 @@ pub struct PragmaDirective<'ast> {
 
 /// The parsed or unparsed tokens of a pragma directive.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PragmaTokens<'ast> {
     /// A Semantic Versioning requirement: `pragma solidity <req>;`.
     ///
@@ -199,6 +203,7 @@ impl PragmaTokens<'_> {
 ///
 /// Syntax-checked in: <https://github.com/ethereum/solidity/blob/194b114664c7daebc2ff68af3c573272f5d28913/libsolidity/analysis/SyntaxChecker.cpp#L77>
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum IdentOrStrLit {
     /// An identifier.
     Ident(Ident),
@@ -228,6 +233,7 @@ impl IdentOrStrLit {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.importDirective>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ImportDirective<'ast> {
     /// The path string literal value.
     pub path: StrLit,
@@ -243,6 +249,7 @@ impl ImportDirective<'_> {
 
 /// The path of an import directive.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ImportItems<'ast> {
     /// A plain import directive: `import "foo.sol" as Foo;`.
     Plain(Option<Ident>),
@@ -256,6 +263,7 @@ pub enum ImportItems<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.usingDirective>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct UsingDirective<'ast> {
     /// The list of paths.
     pub list: UsingList<'ast>,
@@ -266,6 +274,7 @@ pub struct UsingDirective<'ast> {
 
 /// The path list of a `using` directive.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum UsingList<'ast> {
     /// `A.B`
     Single(AstPath<'ast>),
@@ -277,6 +286,7 @@ pub enum UsingList<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.userDefinableOperator>
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum UserDefinableOperator {
     /// `&`
     BitAnd,
@@ -338,15 +348,28 @@ impl UserDefinableOperator {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.contractDefinition>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ItemContract<'ast> {
     pub kind: ContractKind,
     pub name: Ident,
+    pub layout: Option<StorageLayoutSpecifier<'ast>>,
     pub bases: Box<'ast, [Modifier<'ast>]>,
     pub body: Box<'ast, [Item<'ast>]>,
 }
 
+/// The storage layout specifier of a contract: `layout at <slot>`.
+///
+/// Reference: <https://docs.soliditylang.org/en/latest/contracts/custom-storage-layout.html>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StorageLayoutSpecifier<'ast> {
+    pub span: Span,
+    pub slot: Box<'ast, Expr<'ast>>,
+}
+
 /// The kind of contract.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ContractKind {
     /// `contract`
     Contract,
@@ -381,6 +404,7 @@ impl ContractKind {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.functionDefinition>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ItemFunction<'ast> {
     /// What kind of function this is.
     pub kind: FunctionKind,
@@ -392,6 +416,7 @@ pub struct ItemFunction<'ast> {
 
 /// A function header: `function helloWorld() external pure returns(string memory)`.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct FunctionHeader<'ast> {
     /// The name of the function.
     /// Only `None` if this is a constructor, fallback, or receive function.
@@ -411,6 +436,7 @@ pub struct FunctionHeader<'ast> {
 
 /// A kind of function.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum FunctionKind {
     /// `constructor`
     Constructor,
@@ -458,6 +484,7 @@ impl FunctionKind {
 /// [m]: https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.modifierInvocation
 /// [i]: https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.inheritanceSpecifier
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Modifier<'ast> {
     pub name: AstPath<'ast>,
     pub arguments: CallArgs<'ast>,
@@ -465,6 +492,7 @@ pub struct Modifier<'ast> {
 
 /// An override specifier: `override`, `override(a, b.c)`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Override<'ast> {
     pub span: Span,
     pub paths: Box<'ast, [AstPath<'ast>]>,
@@ -472,6 +500,7 @@ pub struct Override<'ast> {
 
 /// A storage location.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum DataLocation {
     /// `storage`
     Storage,
@@ -503,6 +532,7 @@ impl DataLocation {
 
 // How a function can mutate the EVM state.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, EnumIs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StateMutability {
     /// `pure`
     Pure,
@@ -535,6 +565,7 @@ impl StateMutability {
 
 /// Visibility ordered from restricted to unrestricted.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Visibility {
     /// `private`: visible only in the current contract.
     Private,
@@ -568,6 +599,7 @@ impl Visibility {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.stateVariableDeclaration>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VariableDefinition<'ast> {
     pub span: Span,
     pub ty: Type<'ast>,
@@ -582,6 +614,7 @@ pub struct VariableDefinition<'ast> {
 
 /// The mutability of a variable.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum VarMut {
     /// `immutable`
     Immutable,
@@ -619,6 +652,7 @@ impl VarMut {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.structDefinition>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ItemStruct<'ast> {
     pub name: Ident,
     pub fields: Box<'ast, [VariableDefinition<'ast>]>,
@@ -628,6 +662,7 @@ pub struct ItemStruct<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.enumDefinition>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ItemEnum<'ast> {
     pub name: Ident,
     pub variants: Box<'ast, [Ident]>,
@@ -637,6 +672,7 @@ pub struct ItemEnum<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.userDefinedValueTypeDefinition>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ItemUdvt<'ast> {
     pub name: Ident,
     pub ty: Type<'ast>,
@@ -646,6 +682,7 @@ pub struct ItemUdvt<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.errorDefinition>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ItemError<'ast> {
     pub name: Ident,
     pub parameters: ParameterList<'ast>,
@@ -656,6 +693,7 @@ pub struct ItemError<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.eventDefinition>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ItemEvent<'ast> {
     pub name: Ident,
     pub parameters: ParameterList<'ast>,
@@ -6,6 +6,7 @@ use std::{fmt, sync::Arc};
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.literal>
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Lit {
     /// The span of the literal.
     pub span: Span,
@@ -18,22 +19,34 @@ pub struct Lit {
 
 /// A kind of literal.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum LitKind {
     /// A string, unicode string, or hex string literal. Contains the kind and the unescaped
     /// contents of the string.
     ///
     /// Note that even if this is a string or unicode string literal, invalid UTF-8 sequences
     /// are allowed, and as such this cannot be a `str` or `Symbol`.
-    Str(StrKind, Arc<[u8]>),
+    ///
+    /// Serialized as a string if the contents are valid UTF-8, otherwise as an array of bytes.
+    Str(
+        StrKind,
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_str_value"))] Arc<[u8]>,
+    ),
     /// A decimal or hexadecimal number literal.
-    Number(num_bigint::BigInt),
+    Number(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+        num_bigint::BigInt,
+    ),
     /// A rational number literal.
     ///
     /// Note that rational literals that evaluate to integers are represented as
     /// [`Number`](Self::Number) (e.g. `1.2e3` is represented as `Number(1200)`).
-    Rational(num_rational::BigRational),
+    Rational(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+        num_rational::BigRational,
+    ),
     /// An address literal. This is a special case of a 40 digit hexadecimal number literal.
-    Address(Address),
+    Address(#[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))] Address),
     /// A boolean literal.
     Bool(bool),
     /// An error occurred while parsing the literal, which has been emitted.
@@ -54,8 +67,28 @@ impl LitKind {
     }
 }
 
+#[cfg(feature = "serde")]
+fn serialize_str_value<S: serde::Serializer>(
+    value: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match std::str::from_utf8(value) {
+        Ok(s) => serializer.serialize_str(s),
+        Err(_) => serializer.serialize_bytes(value),
+    }
+}
+
+#[cfg(feature = "serde")]
+fn serialize_display<S: serde::Serializer>(
+    value: &impl fmt::Display,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
 /// A single UTF-8 string literal. Only used in import paths and statements, not expressions.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StrLit {
     /// The span of the literal.
     pub span: Span,
@@ -65,6 +98,7 @@ pub struct StrLit {
 
 /// A string literal kind.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StrKind {
     /// A regular string literal.
     Str,
@@ -87,6 +121,7 @@ impl StrKind {
 
 /// A number sub-denomination.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SubDenomination {
     /// An ether sub-denomination.
     Ether(EtherSubDenomination),
@@ -131,6 +166,7 @@ impl SubDenomination {
 
 /// An ether [`SubDenomination`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum EtherSubDenomination {
     /// `wei`
     Wei,
@@ -178,6 +214,7 @@ impl EtherSubDenomination {
 
 /// A time [`SubDenomination`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TimeSubDenomination {
     /// `seconds`
     Seconds,
@@ -240,6 +277,7 @@ impl TimeSubDenomination {
 
 /// Base of numeric literal encoding according to its prefix.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Base {
     /// Literal starts with "0b".
     Binary = 2,
@@ -74,6 +74,7 @@ pub type DocComments<'ast> = Box<'ast, [DocComment]>;
 
 /// A single doc-comment: `/// foo`, `/** bar */`.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DocComment {
     /// The comment kind.
     pub kind: CommentKind,
@@ -97,6 +98,17 @@ impl fmt::Debug for SourceUnit<'_> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for SourceUnit<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("SourceUnit", 1)?;
+        s.serialize_field("items", &self.items.raw)?;
+        s.end()
+    }
+}
+
 impl<'ast> SourceUnit<'ast> {
     /// Creates a new source unit from the given items.
     pub fn new(items: Box<'ast, [Item<'ast>]>) -> Self {
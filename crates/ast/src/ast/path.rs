@@ -11,6 +11,7 @@ pub type AstPath<'ast> = Box<'ast, PathSlice>;
 /// This is a list of identifiers, and is never empty.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PathSlice([Ident]);
 
 impl ToOwned for PathSlice {
@@ -17,6 +17,7 @@ pub use semver::Op as SemverOp;
 
 /// A SemVer version number.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SemverVersionNumber {
     /// A number.
     Number(u32),
@@ -85,6 +86,7 @@ impl Ord for SemverVersionNumber {
 
 /// A SemVer version.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SemverVersion {
     pub span: Span,
     /// Major version.
@@ -180,6 +182,7 @@ impl SemverVersion {
 
 /// A SemVer version requirement. This is a list of components, and is never empty.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SemverReq<'ast> {
     /// The components of this requirement.
     ///
@@ -230,6 +233,7 @@ impl SemverVersionReqCompat {
 
 /// A list of conjoint SemVer version requirement components.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SemverReqCon<'ast> {
     pub span: Span,
     /// The list of components. See [`SemverReq::dis`] for more details.
@@ -264,6 +268,7 @@ impl SemverReqCon<'_> {
 
 /// A single SemVer version requirement component.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SemverReqComponent {
     pub span: Span,
     pub kind: SemverReqComponentKind,
@@ -289,9 +294,13 @@ impl SemverReqComponent {
 
 /// A SemVer version requirement component.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SemverReqComponentKind {
     /// `v`, `=v`
-    Op(Option<Op>, SemverVersion),
+    Op(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_op"))] Option<Op>,
+        SemverVersion,
+    ),
     /// `l - r`
     Range(SemverVersion, SemverVersion),
 }
@@ -301,18 +310,7 @@ impl fmt::Display for SemverReqComponentKind {
         match self {
             Self::Op(op, version) => {
                 if let Some(op) = op {
-                    let op = match op {
-                        Op::Exact => "=",
-                        Op::Greater => ">",
-                        Op::GreaterEq => ">=",
-                        Op::Less => "<",
-                        Op::LessEq => "<=",
-                        Op::Tilde => "~",
-                        Op::Caret => "^",
-                        Op::Wildcard => "*",
-                        _ => "",
-                    };
-                    f.write_str(op)?;
+                    f.write_str(op_to_str(*op))?;
                 }
                 write!(f, "{version}")
             }
@@ -365,6 +363,25 @@ fn matches_op(op: Op, a: &SemverVersion, b: &SemverVersion) -> bool {
     }
 }
 
+fn op_to_str(op: Op) -> &'static str {
+    match op {
+        Op::Exact => "=",
+        Op::Greater => ">",
+        Op::GreaterEq => ">=",
+        Op::Less => "<",
+        Op::LessEq => "<=",
+        Op::Tilde => "~",
+        Op::Caret => "^",
+        Op::Wildcard => "*",
+        _ => "",
+    }
+}
+
+#[cfg(feature = "serde")]
+fn serialize_op<S: serde::Serializer>(op: &Option<Op>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(op.map_or("", op_to_str))
+}
+
 fn matches_tilde(a: &SemverVersion, b: &SemverVersion) -> bool {
     // https://github.com/ethereum/solidity/blob/e81f2bdbd66e9c8780f74b8a8d67b4dc2c87945e/liblangutil/SemVerHandler.cpp#L80
     if !matches_op(Op::GreaterEq, a, b) {
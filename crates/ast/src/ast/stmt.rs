@@ -11,6 +11,7 @@ pub type Block<'ast> = Box<'ast, [Stmt<'ast>]>;
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.statement>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Stmt<'ast> {
     pub docs: DocComments<'ast>,
     pub span: Span,
@@ -19,6 +20,7 @@ pub struct Stmt<'ast> {
 
 /// A kind of statement.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StmtKind<'ast> {
     /// An assembly block, with optional flags: `assembly "evmasm" (...) { ... }`.
     Assembly(StmtAssembly<'ast>),
@@ -81,6 +83,7 @@ pub enum StmtKind<'ast> {
 
 /// An assembly block, with optional flags: `assembly "evmasm" (...) { ... }`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StmtAssembly<'ast> {
     /// The assembly block dialect.
     pub dialect: Option<StrLit>,
@@ -94,6 +97,7 @@ pub struct StmtAssembly<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.tryStatement>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StmtTry<'ast> {
     pub expr: Box<'ast, Expr<'ast>>,
     pub returns: ParameterList<'ast>,
@@ -107,6 +111,7 @@ pub struct StmtTry<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.catchClause>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CatchClause<'ast> {
     pub name: Option<Ident>,
     pub args: ParameterList<'ast>,
@@ -6,6 +6,7 @@ use std::{borrow::Cow, fmt};
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.typeName>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Type<'ast> {
     pub span: Span,
     pub kind: TypeKind<'ast>,
@@ -26,6 +27,7 @@ impl Type<'_> {
 }
 
 /// The kind of a type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TypeKind<'ast> {
     /// An elementary/primitive type.
     Elementary(ElementaryType),
@@ -71,6 +73,7 @@ impl TypeKind<'_> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.elementaryTypeName>
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ElementaryType {
     /// Ethereum address, 20-byte fixed-size byte array.
     /// `address $(payable)?`
@@ -176,6 +179,7 @@ impl ElementaryType {
 
 /// Byte size of a fixed-bytes, integer, or fixed-point number (M) type. Valid values: 0..=32.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TypeSize(u8);
 
 impl fmt::Debug for TypeSize {
@@ -296,6 +300,7 @@ impl TypeSize {
 
 /// Size of a fixed-point number (N) type. Valid values: 0..=80.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TypeFixedSize(u8);
 
 impl fmt::Debug for TypeFixedSize {
@@ -330,6 +335,7 @@ impl TypeFixedSize {
 
 /// An array type.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TypeArray<'ast> {
     pub element: Type<'ast>,
     pub size: Option<Box<'ast, Expr<'ast>>>,
@@ -339,6 +345,7 @@ pub struct TypeArray<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.functionTypeName>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TypeFunction<'ast> {
     pub parameters: ParameterList<'ast>,
     pub visibility: Option<Visibility>,
@@ -350,6 +357,7 @@ pub struct TypeFunction<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.mappingType>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TypeMapping<'ast> {
     pub key: Type<'ast>,
     pub key_name: Option<Ident>,
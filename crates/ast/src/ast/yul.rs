@@ -12,6 +12,7 @@ pub type Block<'ast> = Box<'ast, [Stmt<'ast>]>;
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/yul.html#specification-of-yul-object>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Object<'ast> {
     /// The doc-comments of the object.
     pub docs: DocComments<'ast>,
@@ -29,6 +30,7 @@ pub struct Object<'ast> {
 
 /// A Yul `code` block. See [`Object`].
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CodeBlock<'ast> {
     /// The span of the code block, including the `code` keyword.
     ///
@@ -41,6 +43,7 @@ pub struct CodeBlock<'ast> {
 
 /// A Yul `data` segment. See [`Object`].
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Data<'ast> {
     /// The span of the code block, including the `data` keyword.
     pub span: Span,
@@ -54,6 +57,7 @@ pub struct Data<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.yulStatement>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Stmt<'ast> {
     /// The doc-comments of the statement.
     pub docs: DocComments<'ast>,
@@ -65,6 +69,7 @@ pub struct Stmt<'ast> {
 
 /// A kind of Yul statement.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StmtKind<'ast> {
     /// A blocked scope: `{ ... }`.
     ///
@@ -135,6 +140,7 @@ pub enum StmtKind<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.yulSwitchStatement>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StmtSwitch<'ast> {
     pub selector: Expr<'ast>,
     pub branches: Box<'ast, [StmtSwitchCase<'ast>]>,
@@ -145,6 +151,7 @@ pub struct StmtSwitch<'ast> {
 ///
 /// See [`StmtSwitch`] for more information.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StmtSwitchCase<'ast> {
     pub constant: &'ast mut Lit,
     pub body: Block<'ast>,
@@ -154,6 +161,7 @@ pub struct StmtSwitchCase<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.yulFunctionDefinition>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Function<'ast> {
     pub name: Ident,
     pub parameters: Box<'ast, [Ident]>,
@@ -163,6 +171,7 @@ pub struct Function<'ast> {
 
 /// A Yul expression.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Expr<'ast> {
     /// The span of the expression.
     pub span: Span,
@@ -172,6 +181,7 @@ pub struct Expr<'ast> {
 
 /// A kind of Yul expression.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ExprKind<'ast> {
     /// A single path.
     Path(AstPath<'ast>),
@@ -185,6 +195,7 @@ pub enum ExprKind<'ast> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.yulFunctionCall>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ExprCall<'ast> {
     pub name: Ident,
     pub arguments: Box<'ast, [Expr<'ast>]>,
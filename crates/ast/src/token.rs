@@ -6,6 +6,7 @@ use std::{borrow::Cow, fmt};
 
 /// The type of a comment.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CommentKind {
     /// `// ...`, `/// ...`
     Line,
@@ -17,6 +18,7 @@ pub enum CommentKind {
 ///
 /// Note that this enum contains only binary operators that can also be used in assignments.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum BinOpToken {
     /// `+`
     Plus,
@@ -93,7 +95,7 @@ impl BinOpToken {
             Self::Star => BinOpKind::Mul,
             Self::Slash => BinOpKind::Div,
             Self::Percent => BinOpKind::Rem,
-            Self::Caret => BinOpKind::Pow,
+            Self::Caret => BinOpKind::BitXor,
             Self::And => BinOpKind::BitAnd,
             Self::Or => BinOpKind::BitOr,
             Self::Shl => BinOpKind::Shl,
@@ -105,6 +107,7 @@ impl BinOpToken {
 
 /// Describes how a sequence of token trees is delimited.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Delimiter {
     /// `( ... )`
     Parenthesis,
@@ -117,6 +120,7 @@ pub enum Delimiter {
 /// A literal token. Different from an AST literal as this is unparsed and only contains the raw
 /// contents.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TokenLit {
     /// The symbol of the literal token, excluding any quotes.
     pub symbol: Symbol,
@@ -153,6 +157,7 @@ impl TokenLit {
 
 /// A kind of literal token.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TokenLitKind {
     /// An integer literal token.
     Integer,
@@ -185,6 +190,7 @@ impl TokenLitKind {
 /// A kind of token.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[allow(missing_copy_implementations)] // Future-proofing.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TokenKind {
     // Expression-operator symbols.
     /// `=`
@@ -448,6 +454,7 @@ impl TokenKind {
 /// A single token.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[allow(missing_copy_implementations)] // Future-proofing.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Token {
     /// The kind of the token.
     pub kind: TokenKind,
@@ -96,8 +96,11 @@ declare_visitors! {
         }
 
         fn visit_item_contract(&mut self, contract: &'ast #mut ItemContract<'ast>) -> ControlFlow<Self::BreakValue> {
-            let ItemContract { kind: _, name, bases, body } = contract;
+            let ItemContract { kind: _, name, layout, bases, body } = contract;
             self.visit_ident #_mut(name)?;
+            if let Some(layout) = layout {
+                self.visit_storage_layout_specifier #_mut(layout)?;
+            }
             for base in bases.iter #_mut() {
                 self.visit_modifier #_mut(base)?;
             }
@@ -107,6 +110,13 @@ declare_visitors! {
             ControlFlow::Continue(())
         }
 
+        fn visit_storage_layout_specifier(&mut self, layout: &'ast #mut StorageLayoutSpecifier<'ast>) -> ControlFlow<Self::BreakValue> {
+            let StorageLayoutSpecifier { span, slot } = layout;
+            self.visit_span #_mut(span)?;
+            self.visit_expr #_mut(slot)?;
+            ControlFlow::Continue(())
+        }
+
         fn visit_item_function(&mut self, function: &'ast #mut ItemFunction<'ast>) -> ControlFlow<Self::BreakValue> {
             let ItemFunction { kind: _, header, body } = function;
             self.visit_function_header #_mut(header)?;
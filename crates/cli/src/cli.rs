@@ -1,7 +1,9 @@
 //! Solar CLI arguments.
 
 use clap::{ColorChoice, Parser, ValueHint};
-use solar_config::{CompilerOutput, CompilerStage, Dump, EvmVersion, Language};
+use solar_config::{
+    ArtifactLayout, CompilerOutput, CompilerStage, Dump, EvmVersion, Language, Library,
+};
 use std::path::PathBuf;
 
 /// Blazingly fast Solidity compiler.
@@ -14,14 +16,24 @@ use std::path::PathBuf;
 )]
 #[non_exhaustive]
 pub struct Args {
+    /// Subcommand to run instead of compiling the input files.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Files to compile or import remappings.
     #[arg(value_hint = ValueHint::FilePath)]
     pub input: Vec<PathBuf>,
     /// Directory to search for files.
-    #[arg(help_heading = "Input options", long, short = 'I', visible_alias = "base-path", value_hint = ValueHint::FilePath)]
+    #[arg(help_heading = "Input options", global = true, long, short = 'I', visible_alias = "base-path", value_hint = ValueHint::FilePath)]
     pub import_path: Vec<PathBuf>,
     /// Map to search for files. Can also be provided as a positional argument.
-    #[arg(help_heading = "Input options", long, short = 'm', value_name = "MAP=PATH")]
+    #[arg(
+        help_heading = "Input options",
+        global = true,
+        long,
+        short = 'm',
+        value_name = "MAP=PATH"
+    )]
     pub import_map: Vec<ImportMap>,
     /// Source code language. Only Solidity is currently implemented.
     #[arg(help_heading = "Input options", long, value_enum, default_value_t, hide = true)]
@@ -37,12 +49,83 @@ pub struct Args {
     #[arg(long, value_enum)]
     pub stop_after: Option<CompilerStage>,
 
+    /// Library addresses to link against: `path:Name=0x...`.
+    ///
+    /// Can be specified multiple times or as a comma separated list.
+    #[arg(long, value_delimiter = ',', value_name = "PATH:NAME=ADDRESS")]
+    pub libraries: Vec<Library>,
+    /// Check the storage layouts of the contracts for upgrade compatibility against a previous
+    /// `--emit storage-layout,transient-storage-layout` JSON output, or against the sources of
+    /// the previous version in a directory.
+    #[arg(long, value_hint = ValueHint::AnyPath, value_name = "PATH")]
+    pub storage_layout_baseline: Option<PathBuf>,
+
     /// Directory to write output files.
-    #[arg(long, value_hint = ValueHint::DirPath)]
+    #[arg(long, short = 'o', visible_alias = "output-dir", value_hint = ValueHint::DirPath)]
     pub out_dir: Option<PathBuf>,
+    /// Layout of the artifacts written to `--out-dir`.
+    #[arg(long, value_enum, default_value_t)]
+    pub artifact_layout: ArtifactLayout,
     /// Comma separated list of types of output for the compiler to emit.
     #[arg(long, value_delimiter = ',')]
     pub emit: Vec<CompilerOutput>,
+    /// Comma separated list of solc output names to emit as combined JSON.
+    ///
+    /// Same as `--emit`, for compatibility with solc. Outputs that require bytecode generation
+    /// are not supported yet, and are ignored with a warning.
+    #[arg(
+        help_heading = "solc compatibility",
+        long,
+        value_delimiter = ',',
+        value_name = "OUTPUTS",
+        conflicts_with = "artifact_layout"
+    )]
+    pub combined_json: Vec<String>,
+    /// Emit the ABI of each contract.
+    ///
+    /// Same as `--emit abi --artifact-layout solc`.
+    #[arg(
+        help_heading = "solc compatibility",
+        long,
+        conflicts_with_all = ["artifact_layout", "combined_json"]
+    )]
+    pub abi: bool,
+    /// Emit the function, error and event signature hashes of each contract.
+    ///
+    /// Same as `--emit hashes --artifact-layout solc`.
+    #[arg(
+        help_heading = "solc compatibility",
+        long,
+        conflicts_with_all = ["artifact_layout", "combined_json"]
+    )]
+    pub hashes: bool,
+    /// Emit the bytecode of each contract. Not supported yet.
+    #[arg(
+        help_heading = "solc compatibility",
+        long,
+        conflicts_with_all = ["artifact_layout", "combined_json"]
+    )]
+    pub bin: bool,
+    /// Emit the gas estimates of each function. Not supported yet.
+    #[arg(
+        help_heading = "solc compatibility",
+        long,
+        conflicts_with_all = ["artifact_layout", "combined_json"]
+    )]
+    pub gas: bool,
+    /// Read a solc Standard JSON input and print the Standard JSON output.
+    ///
+    /// The input is read from the given path, the first input file, or stdin.
+    ///
+    /// Unlike solc, all errors in the output have the type `Exception`.
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        require_equals = true,
+        value_hint = ValueHint::FilePath
+    )]
+    pub standard_json: Option<Option<PathBuf>>,
 
     /// Coloring.
     #[arg(help_heading = "Display options", long, value_enum, default_value = "auto")]
@@ -89,6 +172,44 @@ impl Args {
     }
 }
 
+/// Solar subcommands.
+#[derive(Clone, Debug, clap::Subcommand)]
+#[non_exhaustive]
+pub enum Command {
+    /// Flatten the input files and their imports into a single source file.
+    Flatten(FlattenArgs),
+    /// Generate a Solidity interface from a JSON ABI.
+    Abi2sol(Abi2solArgs),
+}
+
+/// Arguments of `solar flatten`.
+#[derive(Clone, Debug, clap::Args)]
+#[non_exhaustive]
+pub struct FlattenArgs {
+    /// Files to flatten or import remappings.
+    #[arg(required = true, value_hint = ValueHint::FilePath)]
+    pub input: Vec<PathBuf>,
+    /// File to write the flattened source to. Defaults to stdout.
+    #[arg(long, short, value_hint = ValueHint::FilePath)]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments of `solar abi2sol`.
+#[derive(Clone, Debug, clap::Args)]
+#[non_exhaustive]
+pub struct Abi2solArgs {
+    /// JSON ABI, or compiler artifact with an `abi` field, to generate the interface from. Use
+    /// `-` to read from stdin.
+    #[arg(value_hint = ValueHint::FilePath)]
+    pub input: PathBuf,
+    /// Name of the interface. Defaults to the file name of the input without its extension.
+    #[arg(long, short)]
+    pub name: Option<String>,
+    /// File to write the interface to. Defaults to stdout.
+    #[arg(long, short, value_hint = ValueHint::FilePath)]
+    pub output: Option<PathBuf>,
+}
+
 /// Internal options.
 #[derive(Clone, Debug, Default, Parser)]
 #[clap(
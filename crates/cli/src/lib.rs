@@ -7,11 +7,18 @@
 
 use clap::Parser as _;
 use cli::Args;
+use solar_config::{ArtifactLayout, CompilerOutput};
 use solar_interface::{
     diagnostics::{DiagCtxt, DynEmitter, HumanEmitter, JsonEmitter},
     Result, Session, SourceMap,
 };
-use std::{collections::BTreeSet, num::NonZeroUsize, path::Path, sync::Arc};
+use std::{
+    collections::BTreeSet,
+    io::Read,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 pub mod cli;
 pub mod utils;
@@ -45,9 +52,56 @@ where
 }
 
 pub fn run_compiler_args(args: Args) -> Result<()> {
+    if args.standard_json.is_some() {
+        return run_standard_json(args);
+    }
+    match &args.command {
+        Some(cli::Command::Flatten(_)) => return run_compiler_with(args, Compiler::run_flatten),
+        Some(cli::Command::Abi2sol(_)) => return run_compiler_with(args, Compiler::run_abi2sol),
+        None => {}
+    }
     run_compiler_with(args, Compiler::run_default)
 }
 
+/// Compiles a solc Standard JSON input and prints the Standard JSON output to stdout.
+///
+/// Diagnostics are reported in the output, so this only fails if the input cannot be read.
+pub fn run_standard_json(args: Args) -> Result<()> {
+    let path = match &args.standard_json {
+        Some(Some(path)) => Some(path.clone()),
+        _ => args.input.first().cloned(),
+    };
+    let input = match path.filter(|path| *path != Path::new("-")) {
+        Some(path) => std::fs::read_to_string(&path).map_err(|e| (e, path)),
+        None => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map(|_| input)
+                .map_err(|e| (e, "stdin".into()))
+        }
+    };
+    let input = input.map_err(|(e, path): (_, PathBuf)| {
+        let msg = format!("couldn't read {}: {e}", path.display());
+        DiagCtxt::new_early().err(msg).emit()
+    })?;
+
+    let ui_testing = args.unstable.ui_testing;
+    let source_map = Arc::new(SourceMap::empty());
+    let emitter = JsonEmitter::buffered(source_map.clone()).ui_testing(ui_testing);
+    let dcx = DiagCtxt::new(Box::new(emitter)).set_flags(|flags| {
+        flags.deduplicate_diagnostics &= !ui_testing;
+        flags.track_diagnostics &= !ui_testing;
+        flags.track_diagnostics |= args.unstable.track_diagnostics;
+    });
+    let mut sess = Session::new(dcx, source_map);
+    sess.jobs = jobs(&args);
+
+    let output = solar_sema::standard_json::compile(&mut sess, &input);
+    println!("{}", output.to_json(args.pretty_json));
+    Ok(())
+}
+
 pub struct Compiler {
     pub sess: Session,
     pub args: Args,
@@ -61,12 +115,84 @@ impl Compiler {
             return Err(sess.dcx.err("Yul is not supported yet").emit());
         }
 
+        let pcx = self.load_input(&args.input)?;
+        pcx.parse_and_resolve()?;
+
+        Ok(())
+    }
+
+    /// Runs `solar flatten`.
+    pub fn run_flatten(&self) -> Result<()> {
+        let Self { sess, args } = self;
+        let Some(cli::Command::Flatten(flatten)) = &args.command else {
+            unreachable!("not a flatten command")
+        };
+
+        let pcx = self.load_input(&flatten.input)?;
+        let flattened = pcx.flatten()?;
+        match &flatten.output {
+            Some(path) => std::fs::write(path, flattened).map_err(|e| {
+                let msg = format!("failed to write to {}: {e}", path.display());
+                sess.dcx.err(msg).emit()
+            }),
+            None => {
+                print!("{flattened}");
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs `solar abi2sol`.
+    pub fn run_abi2sol(&self) -> Result<()> {
+        let Self { sess, args } = self;
+        let Some(cli::Command::Abi2sol(abi2sol)) = &args.command else {
+            unreachable!("not an abi2sol command")
+        };
+
+        let stdin = abi2sol.input == Path::new("-");
+        let json = if stdin {
+            let mut json = String::new();
+            std::io::stdin().read_to_string(&mut json).map(|_| json)
+        } else {
+            std::fs::read_to_string(&abi2sol.input)
+        };
+        let json = json.map_err(|e| {
+            let msg = format!("couldn't read {}: {e}", abi2sol.input.display());
+            sess.dcx.err(msg).emit()
+        })?;
+        let name = match &abi2sol.name {
+            Some(name) => name.clone(),
+            None if stdin => "Interface".to_string(),
+            None => {
+                let stem = abi2sol.input.file_stem().unwrap_or_default().to_string_lossy();
+                // Compiler artifacts are named `<name>.json` or `<name>.abi.json`.
+                stem.split('.').next().unwrap_or_default().to_string()
+            }
+        };
+
+        let sol = solar_sema::abi2sol::abi_json_to_sol(sess, &json, &name)?;
+        match &abi2sol.output {
+            Some(path) => std::fs::write(path, sol).map_err(|e| {
+                let msg = format!("failed to write to {}: {e}", path.display());
+                sess.dcx.err(msg).emit()
+            }),
+            None => {
+                print!("{sol}");
+                Ok(())
+            }
+        }
+    }
+
+    /// Creates a parsing context and loads the input files into it.
+    fn load_input(&self, input: &[PathBuf]) -> Result<solar_sema::ParsingContext<'_>> {
+        let Self { sess, args } = self;
+
         // Partition arguments into three categories:
         // - `stdin`: `-`, occurrences after the first are ignored
         // - remappings: `path=mapped`
         // - paths: everything else
-        let stdin = args.input.iter().any(|arg| *arg == Path::new("-"));
-        let non_stdin_args = args.input.iter().filter(|arg| *arg != Path::new("-"));
+        let stdin = input.iter().any(|arg| *arg == Path::new("-"));
+        let non_stdin_args = input.iter().filter(|arg| *arg != Path::new("-"));
         let arg_remappings = non_stdin_args
             .clone()
             .filter_map(|arg| arg.to_str().unwrap_or("").parse::<cli::ImportMap>().ok());
@@ -91,9 +217,7 @@ impl Compiler {
         }
         pcx.load_files(paths)?;
 
-        pcx.parse_and_resolve()?;
-
-        Ok(())
+        Ok(pcx)
     }
 
     fn finish_diagnostics(&self) -> Result {
@@ -136,8 +260,7 @@ fn run_compiler_with(args: Args, f: impl FnOnce(&Compiler) -> Result + Send) ->
     sess.language = args.language;
     sess.stop_after = args.stop_after;
     sess.dump = args.unstable.dump.clone();
-    sess.jobs = NonZeroUsize::new(args.threads)
-        .unwrap_or_else(|| std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN));
+    sess.jobs = jobs(&args);
     if !args.input.is_empty()
         && args.input.iter().all(|arg| arg.extension() == Some("yul".as_ref()))
     {
@@ -151,9 +274,57 @@ fn run_compiler_with(args: Args, f: impl FnOnce(&Compiler) -> Result + Send) ->
                 return Err(sess.dcx.err(msg).emit());
             }
         }
+        // solc-compatible spellings.
+        for name in &args.combined_json {
+            let emit = match name.as_str() {
+                "abi" => CompilerOutput::Abi,
+                "ast" => CompilerOutput::AstJson,
+                "hashes" => CompilerOutput::Hashes,
+                "metadata" => CompilerOutput::Metadata,
+                "userdoc" => CompilerOutput::Userdoc,
+                "devdoc" => CompilerOutput::Devdoc,
+                "storage-layout" => CompilerOutput::StorageLayout,
+                "transient-storage-layout" => CompilerOutput::TransientStorageLayout,
+                name if SOLC_BYTECODE_OUTPUTS.contains(&name) => {
+                    let msg = format!("`--combined-json {name}` is not supported yet");
+                    sess.dcx.warn(msg).note("bytecode generation is not implemented").emit();
+                    continue;
+                }
+                name => {
+                    let msg = format!("invalid `--combined-json` output `{name}`");
+                    let note = "use `--emit` for outputs that solc does not support";
+                    return Err(sess.dcx.err(msg).note(note).emit());
+                }
+            };
+            set.insert(emit);
+        }
+        if args.abi {
+            set.insert(CompilerOutput::Abi);
+        }
+        if args.hashes {
+            set.insert(CompilerOutput::Hashes);
+        }
+        if args.bin {
+            let msg = "`--bin` is not supported yet";
+            sess.dcx.warn(msg).note("bytecode generation is not implemented").emit();
+        }
+        if args.gas {
+            let msg = "`--gas` is not supported yet";
+            sess.dcx.warn(msg).note("bytecode generation is not implemented").emit();
+        }
         set
     };
+    sess.libraries = args.libraries.clone();
+    sess.storage_layout_baseline = args.storage_layout_baseline.clone();
     sess.out_dir = args.out_dir.clone();
+    sess.artifact_layout = args.artifact_layout;
+    if args.abi || args.hashes || args.bin || args.gas {
+        sess.artifact_layout = ArtifactLayout::Solc;
+    }
+    if sess.artifact_layout == ArtifactLayout::PerContract && sess.out_dir.is_none() {
+        let msg = "`--artifact-layout per-contract` requires `--out-dir`";
+        return Err(sess.dcx.err(msg).emit());
+    }
     sess.pretty_json = args.pretty_json;
 
     let compiler = Compiler { sess, args };
@@ -163,3 +334,23 @@ fn run_compiler_with(args: Args, f: impl FnOnce(&Compiler) -> Result + Send) ->
         r
     })
 }
+
+/// solc `--combined-json` outputs that require bytecode generation.
+const SOLC_BYTECODE_OUTPUTS: &[&str] = &[
+    "asm",
+    "bin",
+    "bin-runtime",
+    "function-debug",
+    "function-debug-runtime",
+    "generated-sources",
+    "generated-sources-runtime",
+    "opcodes",
+    "srcmap",
+    "srcmap-runtime",
+];
+
+/// Returns the number of threads to use, resolving zero to the number of logical cores.
+fn jobs(args: &Args) -> NonZeroUsize {
+    NonZeroUsize::new(args.threads)
+        .unwrap_or_else(|| std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN))
+}
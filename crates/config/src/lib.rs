@@ -103,16 +103,63 @@ str_enum! {
     pub enum CompilerOutput {
         /// JSON ABI.
         Abi,
+        /// Solc-compatible compact AST JSON.
+        AstJson,
         // /// Creation bytecode.
         // Bin,
         // /// Runtime bytecode.
         // BinRuntime,
+        /// Error selectors, keyed by signature.
+        ErrorHashes,
+        /// Event topics of the non-anonymous events, keyed by signature.
+        EventHashes,
         /// Function signature hashes.
         Hashes,
+        /// Solidity `interface I<Name>` source of the contract's external interface.
+        Interface,
+        /// ERC-165 interface IDs of the contract and the interfaces it inherits.
+        InterfaceIds,
+        /// Contract metadata.
+        ///
+        /// Sources only list their IPFS URL and not solc's Swarm URL, so the metadata is not
+        /// byte-identical to solc's.
+        Metadata,
+        /// NatSpec user documentation.
+        Userdoc,
+        /// NatSpec developer documentation.
+        Devdoc,
+        /// Markdown documentation, one page per contract.
+        Docs,
+        /// Rust bindings using `alloy-sol-types`, one module per contract.
+        RustBindings,
+        /// Storage layout of state variables.
+        StorageLayout,
+        /// Storage layout of transient state variables.
+        TransientStorageLayout,
+    }
+}
+
+str_enum! {
+    /// Layout of the artifacts written to the output directory.
+    #[derive(Default)]
+    #[strum(serialize_all = "kebab-case")]
+    pub enum ArtifactLayout {
+        /// A single `combined.json` file with the outputs of all contracts.
+        #[default]
+        Combined,
+        /// One `<file name>/<contract name>.json` file per contract, along with a
+        /// `build-info.json` file with the content hashes of the sources.
+        PerContract,
+        /// solc's layout: one `<contract name>.abi` and `<contract name>.signatures` file per
+        /// contract, or one human-readable section per contract on stdout if no output directory
+        /// is specified.
+        Solc,
     }
 }
 
 /// `-Zdump=kind[=paths...]`.
+///
+/// HIR dumps also accept item selectors in paths: `File.sol:Contract.function`.
 #[derive(Clone, Debug)]
 pub struct Dump {
     pub kind: DumpKind,
@@ -142,8 +189,64 @@ str_enum! {
     pub enum DumpKind {
         /// Print the AST.
         Ast,
-        /// Print the HIR.
+        /// Print the AST as JSON. Requires the `serde` feature.
+        AstJson,
+        /// Pretty-print the HIR, with resolved names and types.
         Hir,
+        /// Print the HIR's `Debug` representation.
+        HirDebug,
+        /// Print the HIR and the types of its items as JSON. Requires the `serde` feature.
+        HirJson,
+    }
+}
+
+/// A library address to link against: `path:Name=0x...`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Library {
+    /// The path of the source file that defines the library.
+    pub path: String,
+    /// The name of the library.
+    pub name: String,
+    /// The address of the deployed library.
+    pub address: [u8; 20],
+}
+
+impl Library {
+    /// Returns the fully qualified name of the library: `path:Name`.
+    pub fn fully_qualified_name(&self) -> String {
+        format!("{}:{}", self.path, self.name)
+    }
+}
+
+impl std::fmt::Display for Library {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}=0x", self.path, self.name)?;
+        self.address.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+impl std::str::FromStr for Library {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (fqn, address) = s.split_once('=').ok_or("missing '='")?;
+        let (path, name) = fqn.rsplit_once(':').ok_or("missing ':' in library name")?;
+        if path.is_empty() || name.is_empty() {
+            return Err("expected `path:Name`");
+        }
+        let address = address.strip_prefix("0x").ok_or("address must start with '0x'")?;
+        if address.len() != 40 {
+            return Err("address must be 20 bytes long");
+        }
+        if !address.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err("invalid hex address");
+        }
+        let mut bytes = [0u8; 20];
+        for (byte, chunk) in bytes.iter_mut().zip(address.as_bytes().chunks(2)) {
+            let chunk = std::str::from_utf8(chunk).unwrap();
+            *byte = u8::from_str_radix(chunk, 16).unwrap();
+        }
+        Ok(Self { path: path.to_string(), name: name.to_string(), address: bytes })
     }
 }
 
@@ -169,4 +272,21 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn library() {
+        let s = "src/A.sol:Lib=0x00000000000000000000000000000000deadbeef";
+        let lib = s.parse::<Library>().unwrap();
+        assert_eq!(lib.path, "src/A.sol");
+        assert_eq!(lib.name, "Lib");
+        assert_eq!(lib.address[16..], [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(lib.fully_qualified_name(), "src/A.sol:Lib");
+        assert_eq!(lib.to_string(), s);
+
+        assert!("Lib=0x00000000000000000000000000000000deadbeef".parse::<Library>().is_err());
+        assert!("a.sol:Lib=00000000000000000000000000000000deadbeef".parse::<Library>().is_err());
+        assert!("a.sol:Lib=0xdeadbeef".parse::<Library>().is_err());
+        assert!("a.sol:Lib=0x+0000000000000000000000000000000deadbeef".parse::<Library>().is_err());
+        assert!("a.sol:Lib".parse::<Library>().is_err());
+    }
 }
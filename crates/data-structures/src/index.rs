@@ -51,10 +51,36 @@ macro_rules! newtype_index {
             }
         }
 
+        $crate::__impl_index_serialize!($name);
+
         $crate::newtype_index!($($rest)*);
     };
 }
 
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "serde")]
+macro_rules! __impl_index_serialize {
+    ($name:ident) => {
+        impl $crate::__private::serde::Serialize for $name {
+            #[inline]
+            fn serialize<S: $crate::__private::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u32(self.get())
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "serde"))]
+macro_rules! __impl_index_serialize {
+    ($name:ident) => {};
+}
+
 // NOTE: The max MUST be less than the maximum value of the underlying integer.
 macro_rules! base_index {
     ($(#[$attr:meta])* $name:ident($primitive:ident <= $max:literal)) => {
@@ -35,6 +35,12 @@ pub use interned::Interned;
 
 pub use smallvec;
 
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
+
 /// This calls the passed function while ensuring it won't be inlined into the caller.
 #[inline(never)]
 #[cold]
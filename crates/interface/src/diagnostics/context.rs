@@ -173,8 +173,8 @@ impl DiagCtxt {
 
     /// Returns the emitted diagnostics. Can be empty.
     ///
-    /// Returns `None` if the underlying emitter is not a human buffer emitter created with
-    /// [`with_buffer_emitter`](Self::with_buffer_emitter).
+    /// Returns `None` if the underlying emitter does not write to a local buffer, like the ones
+    /// created with [`with_buffer_emitter`](Self::with_buffer_emitter) and `JsonEmitter::buffered`.
     pub fn emitted_diagnostics(&self) -> Option<EmittedDiagnostics> {
         let inner = self.inner.lock();
         Some(EmittedDiagnostics(inner.emitter.local_buffer()?.to_string()))
@@ -182,8 +182,8 @@ impl DiagCtxt {
 
     /// Returns `Err` with the printed diagnostics if any errors have been emitted.
     ///
-    /// Returns `None` if the underlying emitter is not a human buffer emitter created with
-    /// [`with_buffer_emitter`](Self::with_buffer_emitter).
+    /// Returns `None` if the underlying emitter does not write to a local buffer, like the ones
+    /// created with [`with_buffer_emitter`](Self::with_buffer_emitter) and `JsonEmitter::buffered`.
     pub fn emitted_errors(&self) -> Option<Result<(), EmittedDiagnostics>> {
         let inner = self.inner.lock();
         let buffer = inner.emitter.local_buffer()?;
@@ -11,6 +11,8 @@ use std::{io, sync::Arc};
 /// Diagnostic emitter that emits diagnostics as JSON.
 pub struct JsonEmitter {
     writer: Box<dyn io::Write + Send>,
+    /// The local buffer that diagnostics are written to instead of `writer`, if any.
+    buffer: Option<String>,
     pretty: bool,
     rustc_like: bool,
 
@@ -39,12 +41,29 @@ impl JsonEmitter {
     pub fn new(writer: Box<dyn io::Write + Send>, source_map: Arc<SourceMap>) -> Self {
         Self {
             writer,
+            buffer: None,
             pretty: false,
             rustc_like: false,
             human_emitter: HumanBufferEmitter::new(ColorChoice::Never).source_map(Some(source_map)),
         }
     }
 
+    /// Creates a new `JsonEmitter` that writes to a local buffer, one diagnostic per line.
+    ///
+    /// The buffer can be retrieved with
+    /// [`DiagCtxt::emitted_diagnostics`](crate::diagnostics::DiagCtxt::emitted_diagnostics).
+    pub fn buffered(source_map: Arc<SourceMap>) -> Self {
+        let mut emitter = Self::new(Box::new(io::sink()), source_map);
+        emitter.buffer = Some(String::new());
+        emitter
+    }
+
+    /// Returns a reference to the local buffer, if the emitter was created with
+    /// [`buffered`](Self::buffered).
+    pub fn buffer(&self) -> Option<&str> {
+        self.buffer.as_deref()
+    }
+
     /// Sets whether to pretty print the JSON.
     pub fn pretty(mut self, pretty: bool) -> Self {
         self.pretty = pretty;
@@ -149,6 +168,8 @@ impl JsonEmitter {
                 .iter()
                 .map(|sub| self.solc_span(&sub.span, &file, Some(sub.label().into_owned())))
                 .collect(),
+            // Solar's diagnostics are not categorized like solc's, so all errors are reported as
+            // `Exception` instead of `ParserError`, `DeclarationError`, `TypeError`, etc.
             r#type: match severity {
                 Severity::Error => match diagnostic.level {
                     Level::Bug => "InternalCompilerError",
@@ -200,6 +221,11 @@ impl JsonEmitter {
     }
 
     fn emit<T: ?Sized + Serialize>(&mut self, value: &T) -> io::Result<()> {
+        if let Some(buffer) = &mut self.buffer {
+            buffer.push_str(&serde_json::to_string(value)?);
+            buffer.push('\n');
+            return Ok(());
+        }
         if self.pretty {
             serde_json::to_writer_pretty(&mut *self.writer, value)
         } else {
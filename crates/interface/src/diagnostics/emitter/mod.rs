@@ -35,7 +35,14 @@ pub trait Emitter: Any {
 
 impl DynEmitter {
     pub(crate) fn local_buffer(&self) -> Option<&str> {
-        self.downcast_ref::<HumanBufferEmitter>().map(HumanBufferEmitter::buffer)
+        if let Some(emitter) = self.downcast_ref::<HumanBufferEmitter>() {
+            return Some(emitter.buffer());
+        }
+        #[cfg(feature = "json")]
+        if let Some(emitter) = self.downcast_ref::<JsonEmitter>() {
+            return emitter.buffer();
+        }
+        None
     }
 
     // TODO: Remove when dyn trait upcasting is stable.
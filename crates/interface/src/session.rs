@@ -2,7 +2,9 @@ use crate::{
     diagnostics::{DiagCtxt, EmittedDiagnostics},
     ColorChoice, SessionGlobals, SourceMap,
 };
-use solar_config::{CompilerOutput, CompilerStage, Dump, EvmVersion, Language};
+use solar_config::{
+    ArtifactLayout, CompilerOutput, CompilerStage, Dump, EvmVersion, Language, Library,
+};
 use std::{collections::BTreeSet, num::NonZeroUsize, path::PathBuf, sync::Arc};
 
 /// Information about the current compiler session.
@@ -31,9 +33,19 @@ pub struct Session {
     /// Types of output to emit.
     #[builder(default)]
     pub emit: BTreeSet<CompilerOutput>,
+    /// Library addresses to link against.
+    #[builder(default)]
+    pub libraries: Vec<Library>,
+    /// Path to a `storage-layout` JSON, or to a directory of sources, to check the storage layouts
+    /// of the contracts against.
+    #[builder(default)]
+    pub storage_layout_baseline: Option<PathBuf>,
     /// Output directory.
     #[builder(default)]
     pub out_dir: Option<PathBuf>,
+    /// Layout of the artifacts written to the output directory.
+    #[builder(default)]
+    pub artifact_layout: ArtifactLayout,
     /// Internal state to dump to stdout.
     #[builder(default)]
     pub dump: Option<Dump>,
@@ -136,8 +148,9 @@ impl Session {
 
     /// Returns the emitted diagnostics. Can be empty.
     ///
-    /// Returns `None` if the underlying emitter is not a human buffer emitter created with
-    /// [`with_buffer_emitter`](SessionBuilder::with_buffer_emitter).
+    /// Returns `None` if the underlying emitter does not write to a local buffer, like the ones
+    /// created with [`with_buffer_emitter`](SessionBuilder::with_buffer_emitter) and
+    /// `JsonEmitter::buffered`.
     #[inline]
     pub fn emitted_diagnostics(&self) -> Option<EmittedDiagnostics> {
         self.dcx.emitted_diagnostics()
@@ -145,8 +158,9 @@ impl Session {
 
     /// Returns `Err` with the printed diagnostics if any errors have been emitted.
     ///
-    /// Returns `None` if the underlying emitter is not a human buffer emitter created with
-    /// [`with_buffer_emitter`](SessionBuilder::with_buffer_emitter).
+    /// Returns `None` if the underlying emitter does not write to a local buffer, like the ones
+    /// created with [`with_buffer_emitter`](SessionBuilder::with_buffer_emitter) and
+    /// `JsonEmitter::buffered`.
     #[inline]
     pub fn emitted_errors(&self) -> Option<Result<(), EmittedDiagnostics>> {
         self.dcx.emitted_errors()
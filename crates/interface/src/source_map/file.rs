@@ -428,20 +428,20 @@ pub enum SourceFileHashAlgorithm {
     // Md5,
     // Sha1,
     // Sha256,
+    Keccak256,
 }
 
 impl std::str::FromStr for SourceFileHashAlgorithm {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // match s {
-        //     "md5" => Ok(Self::Md5),
-        //     "sha1" => Ok(Self::Sha1),
-        //     "sha256" => Ok(Self::Sha256),
-        //     _ => Err(()),
-        // }
-        let _ = s;
-        Err(())
+        match s {
+            // "md5" => Ok(Self::Md5),
+            // "sha1" => Ok(Self::Sha1),
+            // "sha256" => Ok(Self::Sha256),
+            "keccak256" => Ok(Self::Keccak256),
+            _ => Err(()),
+        }
     }
 }
 
@@ -454,6 +454,7 @@ impl SourceFileHashAlgorithm {
             // Self::Md5 => 16,
             // Self::Sha1 => 20,
             // Self::Sha256 => 32,
+            Self::Keccak256 => 32,
         }
     }
 }
@@ -498,8 +499,14 @@ impl SourceFileHash {
         //     SourceFileHashAlgorithm::Sha256 => digest_into::<sha256::Sha256>(data, value),
         // }
         // hash
-        let _ = src;
-        Self { kind, value: Default::default() }
+        let mut hash = Self { kind, value: Default::default() };
+        match kind {
+            SourceFileHashAlgorithm::None => (),
+            SourceFileHashAlgorithm::Keccak256 => {
+                hash.value = alloy_primitives::keccak256(src.as_bytes()).0;
+            }
+        }
+        hash
     }
 
     /// Check if the stored hash matches the hash of the string.
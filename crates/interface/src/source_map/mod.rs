@@ -19,6 +19,10 @@ pub use file::*;
 mod file_resolver;
 pub use file_resolver::{FileResolver, ResolveError};
 
+// Not used until bytecode generation is implemented.
+#[allow(dead_code)]
+mod srcmap;
+
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,140 @@
+//! Solc-style source mappings.
+//!
+//! These are the building blocks for the `srcmap` and `srcmap-runtime` outputs, which will be
+//! emitted once bytecode generation is implemented. Until then they are private to this crate.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/internals/source_mappings.html>
+
+use super::{SourceFile, SourceMap};
+use crate::{Pos, Span};
+use std::fmt;
+
+/// A source location in the solc format: `start:length:index`.
+///
+/// `start` and `length` are byte offsets into the source file, and `index` is the index of the
+/// source file in the `sourceList` of the compiler output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct SourceLocation {
+    /// The byte offset of the start of the range in the source file.
+    pub(crate) start: u32,
+    /// The length of the range in bytes.
+    pub(crate) length: u32,
+    /// The index of the source file in the source list, if any.
+    pub(crate) index: Option<u32>,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:", self.start, self.length)?;
+        match self.index {
+            Some(index) => write!(f, "{index}"),
+            None => f.write_str("-1"),
+        }
+    }
+}
+
+impl SourceMap {
+    /// Converts the given span to a solc-style source location.
+    ///
+    /// `source_index` returns the index of the given file in the source list. Returns `None` if
+    /// the span is a dummy span or if it spans multiple files.
+    pub(crate) fn span_to_source_location(
+        &self,
+        span: Span,
+        source_index: impl FnOnce(&SourceFile) -> Option<u32>,
+    ) -> Option<SourceLocation> {
+        if span.is_dummy() || self.files().is_empty() {
+            return None;
+        }
+        let lo = self.lookup_byte_offset(span.lo());
+        let hi = self.lookup_byte_offset(span.hi());
+        if lo.sf.start_pos != hi.sf.start_pos {
+            return None;
+        }
+        let start = lo.pos.to_u32();
+        Some(SourceLocation { start, length: hi.pos.to_u32() - start, index: source_index(&lo.sf) })
+    }
+}
+
+/// The kind of jump of an instruction in a source map.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) enum JumpType {
+    /// A jump into a function: `i`.
+    In,
+    /// A return from a function: `o`.
+    Out,
+    /// A regular jump, or any other instruction: `-`.
+    #[default]
+    Regular,
+}
+
+impl JumpType {
+    /// Returns the character representation of the jump type.
+    pub(crate) const fn to_char(self) -> char {
+        match self {
+            Self::In => 'i',
+            Self::Out => 'o',
+            Self::Regular => '-',
+        }
+    }
+}
+
+/// A single entry of a source map, corresponding to one instruction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct SourceMapEntry {
+    /// The source location of the instruction. `None` if it has no associated source.
+    pub(crate) location: Option<SourceLocation>,
+    /// The jump type of the instruction.
+    pub(crate) jump: JumpType,
+    /// The modifier depth of the instruction.
+    pub(crate) modifier_depth: u32,
+}
+
+/// Encodes the given entries as a compressed source map: `s:l:f:j:m;...`.
+///
+/// Fields that are equal to the ones of the previous entry are left empty, and trailing empty
+/// fields are omitted, matching the output of solc.
+pub(crate) fn compress_source_map<'a>(
+    entries: impl IntoIterator<Item = &'a SourceMapEntry>,
+) -> String {
+    let mut out = String::new();
+    let mut prev = [-1i64; 3];
+    let mut prev_jump = None;
+    let mut prev_modifier_depth = None;
+    for (i, entry) in entries.into_iter().enumerate() {
+        if i > 0 {
+            out.push(';');
+        }
+
+        let fields = match entry.location {
+            Some(loc) => [loc.start.into(), loc.length.into(), loc.index.map_or(-1, i64::from)],
+            None => [-1; 3],
+        };
+        let changed = [
+            fields[0] != prev[0],
+            fields[1] != prev[1],
+            fields[2] != prev[2],
+            prev_jump != Some(entry.jump),
+            prev_modifier_depth != Some(entry.modifier_depth),
+        ];
+        let n_components = changed.iter().rposition(|&c| c).map_or(0, |i| i + 1);
+        for (j, &changed) in changed[..n_components].iter().enumerate() {
+            if j > 0 {
+                out.push(':');
+            }
+            if !changed {
+                continue;
+            }
+            match j {
+                0..=2 => out.push_str(itoa::Buffer::new().format(fields[j])),
+                3 => out.push(entry.jump.to_char()),
+                _ => out.push_str(itoa::Buffer::new().format(entry.modifier_depth)),
+            }
+        }
+
+        prev = fields;
+        prev_jump = Some(entry.jump);
+        prev_modifier_depth = Some(entry.modifier_depth);
+    }
+    out
+}
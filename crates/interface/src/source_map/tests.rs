@@ -1,4 +1,4 @@
-use super::*;
+use super::{srcmap::*, *};
 use std::path::PathBuf;
 
 fn init_source_map() -> SourceMap {
@@ -102,6 +102,61 @@ fn t5() {
     assert_eq!(loc2.col, CharPos(0));
 }
 
+/// Tests `span_to_source_location`.
+#[test]
+fn source_location() {
+    let sm = init_source_map();
+    let index = |sf: &SourceFile| sm.files().iter().position(|f| f.name == sf.name);
+    let index = |sf: &SourceFile| index(sf).map(|i| i as u32);
+
+    let span = Span::new(BytePos(6), BytePos(10));
+    let loc = sm.span_to_source_location(span, index).unwrap();
+    assert_eq!(loc, SourceLocation { start: 6, length: 4, index: Some(0) });
+    assert_eq!(loc.to_string(), "6:4:0");
+
+    let span = Span::new(BytePos(25), BytePos(30));
+    let loc = sm.span_to_source_location(span, index).unwrap();
+    assert_eq!(loc.to_string(), "0:5:2");
+
+    let loc = sm.span_to_source_location(span, |_| None).unwrap();
+    assert_eq!(loc.to_string(), "0:5:-1");
+
+    assert_eq!(sm.span_to_source_location(Span::DUMMY, index), None);
+    assert_eq!(sm.span_to_source_location(Span::new(BytePos(6), BytePos(26)), index), None);
+}
+
+/// Tests `compress_source_map`.
+#[test]
+fn compressed_source_map() {
+    let entry = |start, length, index, jump, modifier_depth| SourceMapEntry {
+        location: Some(SourceLocation { start, length, index }),
+        jump,
+        modifier_depth,
+    };
+    let regular = JumpType::Regular;
+
+    assert_eq!(compress_source_map(&[]), "");
+
+    // Example from the Solidity documentation.
+    let entries = [
+        entry(1, 2, Some(1), regular, 0),
+        entry(1, 9, Some(1), regular, 0),
+        entry(2, 1, Some(2), regular, 0),
+        entry(2, 1, Some(2), regular, 0),
+        entry(2, 1, Some(2), regular, 0),
+    ];
+    assert_eq!(compress_source_map(&entries), "1:2:1:-:0;:9;2:1:2;;");
+
+    let entries = [
+        SourceMapEntry::default(),
+        entry(0, 10, Some(0), JumpType::In, 0),
+        entry(0, 10, Some(0), JumpType::Out, 1),
+        entry(3, 10, None, JumpType::Out, 1),
+        SourceMapEntry { location: None, jump: regular, modifier_depth: 1 },
+    ];
+    assert_eq!(compress_source_map(&entries), ":::-:0;0:10:0:i;:::o:1;3::-1;-1:-1::-");
+}
+
 fn init_source_map_mbc() -> SourceMap {
     let sm = SourceMap::empty();
     // "€" is a three-byte UTF8 char.
@@ -216,6 +271,19 @@ fn span_merging_fail() {
     assert!(sm.merge_spans(span1, span2).is_none());
 }
 
+#[test]
+fn keccak256_src_hash() {
+    let sm = SourceMap::new(SourceFileHashAlgorithm::Keccak256);
+    let file = sm.new_dummy_source_file(PathBuf::from("empty.sol"), String::new()).unwrap();
+    assert_eq!(file.src_hash.kind(), SourceFileHashAlgorithm::Keccak256);
+    assert_eq!(
+        hex::encode(file.src_hash.hash_bytes()),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert!(file.src_hash.matches(""));
+    assert!(!file.src_hash.matches("contract C {}"));
+}
+
 /// Tests loading an external source file that requires normalization.
 #[cfg(any())]
 #[test]
@@ -51,6 +51,42 @@ impl fmt::Debug for Span {
     }
 }
 
+/// Serializes the span as its file name and 1-based start and end line/column pairs, using the
+/// global `SourceMap`.
+///
+/// Dummy spans, and spans that cannot be resolved because no `SourceMap` is set, are serialized
+/// as `null`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Span {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        #[derive(serde::Serialize)]
+        struct LineColumn {
+            line: usize,
+            column: usize,
+        }
+
+        let info = SessionGlobals::is_set()
+            .then(|| {
+                SessionGlobals::with(|g| {
+                    let sm = g.source_map.lock();
+                    sm.as_ref().map(|sm| sm.span_to_location_info(*self))
+                })
+            })
+            .flatten();
+        let Some((Some(file), lo_line, lo_col, hi_line, hi_col)) = info else {
+            return serializer.serialize_none();
+        };
+
+        let mut s = serializer.serialize_struct("Span", 3)?;
+        s.serialize_field("file", &file.name.display().to_string())?;
+        s.serialize_field("start", &LineColumn { line: lo_line, column: lo_col })?;
+        s.serialize_field("end", &LineColumn { line: hi_line, column: hi_col })?;
+        s.end()
+    }
+}
+
 impl Span {
     /// A dummy span.
     pub const DUMMY: Self = Self { lo: BytePos(0), hi: BytePos(0) };
@@ -5,6 +5,7 @@ use std::{cmp, fmt, hash, str};
 
 /// An identifier.
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Ident {
     /// The identifier's name.
     pub name: Symbol,
@@ -353,6 +354,14 @@ impl fmt::Display for Symbol {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Symbol {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 type InternerInner = LassoInterner;
 
 /// Symbol interner.
@@ -850,6 +859,7 @@ symbols! {
         abi,
         abicoder,
         assert,
+        at,
         block,
         code,
         codehash,
@@ -869,6 +879,7 @@ symbols! {
         gasleft,
         global,
         interfaceId,
+        layout,
         length,
         max,
         min,
@@ -319,12 +319,39 @@ impl<'sess, 'ast> Parser<'sess, 'ast> {
             _ => unreachable!("parse_contract called without contract-like keyword"),
         };
         let name = self.parse_ident()?;
-        let bases =
-            if self.eat_keyword(kw::Is) { self.parse_inheritance()? } else { Default::default() };
+
+        let mut bases = None;
+        let mut layout = None;
+        loop {
+            let lo = self.token.span;
+            if self.eat_keyword(kw::Is) {
+                let new_bases = self.parse_inheritance()?;
+                if bases.is_some() {
+                    let msg = "base contracts were already specified";
+                    self.dcx().err(msg).span(lo.to(self.prev_token.span)).emit();
+                } else {
+                    bases = Some(new_bases);
+                }
+            } else if self.eat_keyword(sym::layout) {
+                self.expect_keyword(sym::at)?;
+                let slot = self.parse_expr()?;
+                let span = lo.to(self.prev_token.span);
+                if layout.is_some() {
+                    let msg = "storage layout was already specified";
+                    self.dcx().err(msg).span(span).emit();
+                } else {
+                    layout = Some(StorageLayoutSpecifier { span, slot });
+                }
+            } else {
+                break;
+            }
+        }
+        let bases = bases.unwrap_or_default();
+
         self.expect(&TokenKind::OpenDelim(Delimiter::Brace))?;
         let body =
             self.in_contract(|this| this.parse_items(&TokenKind::CloseDelim(Delimiter::Brace)))?;
-        Ok(ItemContract { kind, name, bases, body })
+        Ok(ItemContract { kind, name, layout, bases, body })
     }
 
     /// Parses an enum definition.
@@ -850,13 +877,20 @@ impl<'sess, 'ast> Parser<'sess, 'ast> {
 
     /// Parses a list of inheritance specifiers.
     fn parse_inheritance(&mut self) -> PResult<'sess, Box<'ast, [Modifier<'ast>]>> {
-        self.parse_seq_to_before_end(
-            &TokenKind::OpenDelim(Delimiter::Brace),
-            SeqSep::trailing_disallowed(TokenKind::Comma),
-            false,
-            Self::parse_modifier,
-        )
-        .map(|(x, _)| x)
+        // Not `parse_seq_to_before_end` since the list can be followed by other specifiers.
+        let mut bases = Vec::new();
+        loop {
+            bases.push(self.parse_modifier()?);
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+            if self.check(&TokenKind::OpenDelim(Delimiter::Brace)) {
+                let msg = "trailing `,` separator is not allowed";
+                self.dcx().err(msg).span(self.prev_token.span).emit();
+                break;
+            }
+        }
+        Ok(self.alloc_vec(bases))
     }
 
     /// Parses a single modifier invocation.
@@ -0,0 +1,357 @@
+//! Solidity interface generation from JSON ABIs.
+//!
+//! The reverse of `--emit abi`: reconstructs a Solidity `interface`, along with the structs and
+//! user-defined value types of its signatures, from a JSON ABI.
+
+use crate::ParsingContext;
+use alloy_json_abi::{InternalType, JsonAbi, Param, StateMutability};
+use solar_data_structures::map::{FxHashSet, FxIndexMap};
+use solar_interface::{source_map::FileName, Result, Session};
+use std::fmt::Write;
+
+/// Generates the source of a Solidity `interface` named `name` from the given JSON ABI.
+///
+/// Structs are reconstructed from the `internalType` and `components` of the parameters. Enums,
+/// whose variants are not part of the ABI, and user-defined value types are declared as
+/// user-defined value types of their ABI type. All of them are declared in the interface,
+/// prefixed with the name of their contract if their names collide. Contract types are declared as
+/// `address`.
+///
+/// Reference-type parameters are declared in `calldata` and return values in `memory`.
+///
+/// The generated source is parsed and analyzed in the given session before it is returned, so
+/// that an invalid or inconsistent ABI is reported as an error instead of producing a source that
+/// does not compile.
+pub fn abi_to_sol(sess: &Session, abi: &JsonAbi, name: &str) -> Result<String> {
+    let src = Generator::default().generate(abi, name);
+
+    let mut pcx = ParsingContext::new(sess);
+    let file = sess
+        .source_map()
+        .new_source_file(FileName::Custom(format!("{name}.sol")), || Ok(src.clone()))
+        .map_err(|e| sess.dcx.err(format!("failed to load the generated source: {e}")).emit())?;
+    pcx.add_file(file);
+    crate::parse_and_resolve_with(pcx, |_, _, _| Ok(())).map_err(|_| {
+        let msg = format!("the interface generated from the ABI of `{name}` is invalid");
+        sess.dcx.err(msg).emit()
+    })?;
+
+    Ok(src)
+}
+
+/// Generates the source of a Solidity `interface` named `name` from the given JSON.
+///
+/// Accepts either a JSON ABI or an object with an `abi` field, such as a compiler artifact.
+///
+/// See [`abi_to_sol`] for more details.
+pub fn abi_json_to_sol(sess: &Session, json: &str, name: &str) -> Result<String> {
+    let abi = serde_json::from_str::<serde_json::Value>(json).and_then(|mut value| {
+        let abi = value.get_mut("abi").map(serde_json::Value::take).unwrap_or(value);
+        serde_json::from_value::<JsonAbi>(abi)
+    });
+    let abi = abi.map_err(|e| sess.dcx.err(format!("invalid JSON ABI: {e}")).emit())?;
+    abi_to_sol(sess, &abi, name)
+}
+
+/// A user-defined type reconstructed from the ABI.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum TypeKey {
+    /// A struct, identified by its contract and name, or by its fields if the ABI has no
+    /// `internalType`.
+    Struct { contract: Option<String>, name: String },
+    /// A user-defined value type or enum, identified by its contract and name.
+    Udvt { contract: Option<String>, name: String },
+}
+
+/// A user-defined type declaration.
+struct TypeDecl {
+    /// The name of the type in the interface.
+    name: String,
+    /// The fields of a struct, or the underlying type of a user-defined value type.
+    body: Vec<String>,
+}
+
+#[derive(Default)]
+struct Generator {
+    types: FxIndexMap<TypeKey, TypeDecl>,
+    names: FxHashSet<String>,
+}
+
+impl Generator {
+    fn generate(mut self, abi: &JsonAbi, name: &str) -> String {
+        let mut items = Vec::<String>::new();
+        for event in abi.events() {
+            let params = event.inputs.iter().map(|p| {
+                let mut s = self.ty(&p.ty, p.internal_type.as_ref(), &p.components);
+                if p.indexed {
+                    s.push_str(" indexed");
+                }
+                push_name(&mut s, &p.name);
+                s
+            });
+            let params = params.collect::<Vec<_>>().join(", ");
+            let anonymous = if event.anonymous { " anonymous" } else { "" };
+            items.push(format!("event {}({params}){anonymous};", event.name));
+        }
+        for error in abi.errors() {
+            let params = self.parameter_list(&error.inputs, None);
+            items.push(format!("error {}({params});", error.name));
+        }
+        if let Some(fallback) = &abi.fallback {
+            items.push(format!("fallback() external{};", mutability(fallback.state_mutability)));
+        }
+        if abi.receive.is_some() {
+            items.push("receive() external payable;".to_string());
+        }
+        for f in abi.functions() {
+            let params = self.parameter_list(&f.inputs, Some("calldata"));
+            let mut s =
+                format!("function {}({params}) external{}", f.name, mutability(f.state_mutability));
+            if !f.outputs.is_empty() {
+                write!(s, " returns ({})", self.parameter_list(&f.outputs, Some("memory")))
+                    .unwrap();
+            }
+            s.push(';');
+            items.push(s);
+        }
+
+        let mut out = String::from("// SPDX-License-Identifier: UNLICENSED\n");
+        // Custom errors require 0.8.4 and user-defined value types 0.8.8.
+        let minor = if self.types.keys().any(|key| matches!(key, TypeKey::Udvt { .. })) {
+            8
+        } else if abi.errors().next().is_some() {
+            4
+        } else {
+            0
+        };
+        writeln!(out, "pragma solidity ^0.8.{minor};\n").unwrap();
+        writeln!(out, "interface {name} {{").unwrap();
+        let udvts = self.types.iter().filter(|(key, _)| matches!(key, TypeKey::Udvt { .. }));
+        let udvts = udvts.map(|(_, decl)| format!("type {} is {};", decl.name, decl.body[0]));
+        let structs = self.types.iter().filter(|(key, _)| matches!(key, TypeKey::Struct { .. }));
+        let structs = structs.map(|(_, decl)| {
+            let mut s = format!("struct {} {{\n", decl.name);
+            for field in &decl.body {
+                writeln!(s, "        {field};").unwrap();
+            }
+            s.push_str("    }");
+            s
+        });
+        for (i, item) in udvts.chain(structs).chain(items).enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            writeln!(out, "    {item}").unwrap();
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Formats the given parameters, declaring reference types in the given data location.
+    fn parameter_list(&mut self, params: &[Param], loc: Option<&str>) -> String {
+        let params = params.iter().map(|p| {
+            let mut s = self.ty(&p.ty, p.internal_type.as_ref(), &p.components);
+            if let Some(loc) = loc.filter(|_| is_reference_type(&p.ty)) {
+                write!(s, " {loc}").unwrap();
+            }
+            push_name(&mut s, &p.name);
+            s
+        });
+        params.collect::<Vec<_>>().join(", ")
+    }
+
+    /// Returns the Solidity type of a parameter, declaring the user-defined types it depends on.
+    fn ty(
+        &mut self,
+        abi_ty: &str,
+        internal_type: Option<&InternalType>,
+        components: &[Param],
+    ) -> String {
+        let (base, suffix) = split_array_suffix(abi_ty);
+        let base = match internal_type {
+            _ if base == "tuple" => {
+                let (contract, name) = match internal_type {
+                    Some(InternalType::Struct { contract, ty }) => {
+                        (contract.clone(), split_array_suffix(ty).0.to_string())
+                    }
+                    _ => (None, String::new()),
+                };
+                self.declare_struct(contract, name, components)
+            }
+            Some(InternalType::Enum { contract, ty }) => {
+                let name = split_array_suffix(ty).0.to_string();
+                self.declare_udvt(contract.clone(), name, base)
+            }
+            Some(InternalType::Other { contract, ty }) if split_array_suffix(ty).0 != base => {
+                let name = split_array_suffix(ty).0.to_string();
+                self.declare_udvt(contract.clone(), name, base)
+            }
+            Some(InternalType::AddressPayable(_)) => "address payable".to_string(),
+            // The parameters of function types are not part of the ABI, but all external function
+            // types have the same ABI type.
+            _ if base == "function" => match internal_type {
+                Some(InternalType::Other { contract: None, ty }) if ty.starts_with("function ") => {
+                    ty.clone()
+                }
+                _ => "function() external".to_string(),
+            },
+            _ => base.to_string(),
+        };
+        base + suffix
+    }
+
+    fn declare_struct(
+        &mut self,
+        contract: Option<String>,
+        name: String,
+        components: &[Param],
+    ) -> String {
+        let fields = components
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let ty = self.ty(&p.ty, p.internal_type.as_ref(), &p.components);
+                let name = if p.name.is_empty() { format!("field{i}") } else { p.name.clone() };
+                format!("{ty} {name}")
+            })
+            .collect::<Vec<_>>();
+        // Structs without an `internalType` are deduplicated by their fields.
+        if name.is_empty() {
+            let key = TypeKey::Struct { contract: None, name: fields.join(", ") };
+            let name = format!("Struct{}", self.types.len());
+            return self.declare(key, None, name, fields);
+        }
+        let key = TypeKey::Struct { contract: contract.clone(), name: name.clone() };
+        self.declare(key, contract, name, fields)
+    }
+
+    fn declare_udvt(&mut self, contract: Option<String>, name: String, ty: &str) -> String {
+        let key = TypeKey::Udvt { contract: contract.clone(), name: name.clone() };
+        self.declare(key, contract, name, vec![ty.to_string()])
+    }
+
+    /// Declares a type if it is not already declared, prefixing its name with the name of its
+    /// contract, and then with a numeric suffix, if it collides with another type.
+    fn declare(
+        &mut self,
+        key: TypeKey,
+        contract: Option<String>,
+        mut name: String,
+        body: Vec<String>,
+    ) -> String {
+        if let Some(decl) = self.types.get(&key) {
+            return decl.name.clone();
+        }
+        if !self.names.insert(name.clone()) {
+            if let Some(contract) = contract {
+                name = format!("{contract}_{name}");
+            }
+            let base = name.clone();
+            let mut i = 1;
+            while !self.names.insert(name.clone()) {
+                name = format!("{base}_{i}");
+                i += 1;
+            }
+        }
+        self.types.insert(key, TypeDecl { name: name.clone(), body });
+        name
+    }
+}
+
+/// Splits a type into its base type and its array dimensions: `uint256[2][]` into `uint256` and
+/// `[2][]`.
+fn split_array_suffix(ty: &str) -> (&str, &str) {
+    ty.split_at(ty.find('[').unwrap_or(ty.len()))
+}
+
+/// Returns `true` if the given ABI type must be declared with a data location.
+fn is_reference_type(ty: &str) -> bool {
+    matches!(ty, "string" | "bytes") || ty.ends_with(']') || ty.starts_with("tuple")
+}
+
+/// Appends the given parameter name, if any.
+fn push_name(s: &mut String, name: &str) {
+    if !name.is_empty() {
+        s.push(' ');
+        s.push_str(name);
+    }
+}
+
+/// Returns the state mutability of a function, prefixed with a space, or an empty string if it is
+/// non-payable.
+fn mutability(state_mutability: StateMutability) -> &'static str {
+    match state_mutability {
+        StateMutability::NonPayable => "",
+        StateMutability::Pure => " pure",
+        StateMutability::View => " view",
+        StateMutability::Payable => " payable",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_json_to_sol_test(json: &str, name: &str) -> Result<String> {
+        let sess = Session::builder().with_test_emitter().build();
+        sess.enter(|| abi_json_to_sol(&sess, json, name))
+    }
+
+    #[test]
+    fn reconstructs_types() {
+        let json = r#"{"abi": [
+            {"type": "function", "name": "f", "stateMutability": "view", "inputs": [
+                {"name": "xs", "type": "tuple[]", "internalType": "struct A.S[]", "components": [
+                    {"name": "a", "type": "uint128", "internalType": "Price"}
+                ]},
+                {"name": "y", "type": "tuple", "internalType": "struct B.S", "components": [
+                    {"name": "b", "type": "string", "internalType": "string"},
+                    {"name": "e", "type": "uint8", "internalType": "enum A.E"}
+                ]},
+                {"name": "to", "type": "address", "internalType": "address payable"}
+            ], "outputs": [
+                {"name": "", "type": "address", "internalType": "contract IERC20"}
+            ]},
+            {"type": "event", "name": "Ev", "anonymous": false, "inputs": [
+                {"name": "s", "type": "tuple", "internalType": "struct A.S", "indexed": true,
+                 "components": [{"name": "a", "type": "uint128", "internalType": "Price"}]}
+            ]},
+            {"type": "error", "name": "Err", "inputs": [
+                {"name": "", "type": "bytes32[2]", "internalType": "bytes32[2]"}
+            ]}
+        ]}"#;
+        let expected = r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.8;
+
+interface I {
+    type Price is uint128;
+
+    type E is uint8;
+
+    struct S {
+        Price a;
+    }
+
+    struct B_S {
+        string b;
+        E e;
+    }
+
+    event Ev(S indexed s);
+
+    error Err(bytes32[2]);
+
+    function f(S[] calldata xs, B_S calldata y, address payable to) external view returns (address);
+}
+"#;
+        assert_eq!(abi_json_to_sol_test(json, "I").unwrap(), expected);
+    }
+
+    #[test]
+    fn invalid_interface() {
+        let json = r#"[{"type": "function", "name": "not valid", "inputs": [], "outputs": [],
+            "stateMutability": "nonpayable"}]"#;
+        assert!(abi_json_to_sol_test(json, "I").is_err());
+        assert!(abi_json_to_sol_test("{}", "I").is_err());
+    }
+}
@@ -56,6 +56,7 @@ impl<'ast> super::LoweringContext<'_, 'ast, '_> {
             // Set later.
             bases: &[],
             linearized_bases: &[],
+            layout: None,
 
             ctor: None,
             fallback: None,
@@ -110,6 +111,7 @@ impl<'ast> super::LoweringContext<'_, 'ast, '_> {
             ast::ItemKind::Error(i) => hir::ItemId::Error(self.lower_error(item, i)),
             ast::ItemKind::Event(i) => hir::ItemId::Event(self.lower_event(item, i)),
         };
+        self.lower_natspec(item_id, item.docs);
         self.hir_to_ast.insert(item_id, item);
         item_id
     }
@@ -12,6 +12,8 @@ use solar_interface::{diagnostics::DiagCtxt, Session};
 
 mod lower;
 
+mod natspec;
+
 mod linearize;
 
 pub(crate) mod resolve;
@@ -0,0 +1,191 @@
+use crate::hir::{self, NatSpecKind};
+use solar_ast::{ast, token::CommentKind};
+use solar_interface::{BytePos, Span, Symbol};
+
+impl<'hir> super::LoweringContext<'_, '_, 'hir> {
+    /// Parses the NatSpec tags of the given doc-comments and attaches them to `item`.
+    ///
+    /// Unknown tags and tags that are not valid for the kind of `item` are reported and skipped.
+    pub(super) fn lower_natspec(&mut self, item: hir::ItemId, docs: &[ast::DocComment]) {
+        let mut items = Vec::<(NatSpecKind, Span, String)>::new();
+        // Whether the content of the last tag is being skipped because the tag is invalid.
+        let mut skipping = false;
+        for doc in docs {
+            // The opening delimiter, `///` or `/**`, is not part of the symbol.
+            let content_start = doc.span.lo() + BytePos(3);
+            for (offset, line) in doc_lines(doc) {
+                let lo = content_start + BytePos(offset as u32);
+                let span = Span::new(lo, lo + BytePos(line.len() as u32));
+                if let Some(tagged) = line.strip_prefix('@') {
+                    let (tag, rest) = split_word(tagged);
+                    let tag = match parse_tag(tag, rest) {
+                        Ok((kind, content)) => {
+                            self.check_natspec_tag(item, kind, span).then_some((kind, content))
+                        }
+                        Err(err) => {
+                            err.emit(self, tag, span);
+                            None
+                        }
+                    };
+                    skipping = tag.is_none();
+                    if let Some((kind, content)) = tag {
+                        items.push((kind, span, content.to_string()));
+                    }
+                } else if line.is_empty() || skipping {
+                    continue;
+                } else if let Some((_, _, content)) = items.last_mut() {
+                    if !content.is_empty() {
+                        content.push(' ');
+                    }
+                    content.push_str(line);
+                } else if self.check_natspec_tag(item, NatSpecKind::Notice, span) {
+                    items.push((NatSpecKind::Notice, span, line.to_string()));
+                } else {
+                    skipping = true;
+                }
+            }
+        }
+        if items.is_empty() {
+            return;
+        }
+
+        let items = items.into_iter().map(|(kind, span, content)| hir::NatSpecItem {
+            kind,
+            span,
+            content: self.arena.alloc_str(&content),
+        });
+        let items = self.arena.alloc_slice_fill_iter(items);
+        self.hir.natspec.insert(item, items);
+    }
+
+    /// Checks that the given tag is valid for the kind of `item`, reporting an error if not.
+    fn check_natspec_tag(&self, item: hir::ItemId, kind: NatSpecKind, span: Span) -> bool {
+        use NatSpecKind::*;
+
+        if let Custom { .. } = kind {
+            return true;
+        }
+        let hir_item = self.hir.item(item);
+        let valid = match hir_item {
+            hir::Item::Contract(_) => matches!(kind, Title | Author | Notice | Dev),
+            hir::Item::Function(f) if f.kind.is_modifier() => {
+                matches!(kind, Author | Notice | Dev | Param { .. } | Inheritdoc { .. })
+            }
+            hir::Item::Function(_) => {
+                matches!(kind, Author | Notice | Dev | Param { .. } | Return | Inheritdoc { .. })
+            }
+            hir::Item::Event(_) | hir::Item::Error(_) => {
+                matches!(kind, Author | Notice | Dev | Param { .. })
+            }
+            hir::Item::Variable(v) if v.is_state_variable => {
+                let public_only = matches!(kind, Notice | Return | Inheritdoc { .. });
+                if public_only && v.visibility != Some(ast::Visibility::Public) {
+                    let msg =
+                        format!("NatSpec tag `{kind}` is only valid for public state variables");
+                    self.dcx().err(msg).span(span).emit();
+                    return false;
+                }
+                public_only || kind == Dev
+            }
+            hir::Item::Variable(_) => matches!(kind, Notice | Dev),
+            hir::Item::Struct(_) | hir::Item::Enum(_) | hir::Item::Udvt(_) => {
+                matches!(kind, Title | Author | Notice | Dev)
+            }
+        };
+        if !valid {
+            let msg = format!(
+                "NatSpec tag `{kind}` is not valid for {} definitions",
+                hir_item.description()
+            );
+            self.dcx().err(msg).span(span).emit();
+        }
+        valid
+    }
+}
+
+/// An invalid NatSpec tag.
+enum TagError {
+    Unknown,
+    InvalidCustom,
+}
+
+impl TagError {
+    fn emit(self, lcx: &super::LoweringContext<'_, '_, '_>, tag: &str, span: Span) {
+        match self {
+            Self::Unknown => {
+                let msg = format!("unknown NatSpec tag `@{tag}`");
+                let mut err = lcx.dcx().err(msg).span(span);
+                if is_valid_custom_tag(tag) {
+                    err = err.help(format!(
+                        "custom tags must be prefixed with `custom:`: `@custom:{tag}`"
+                    ));
+                }
+                err.emit();
+            }
+            Self::InvalidCustom => {
+                let msg = format!("invalid custom NatSpec tag `@{tag}`");
+                let note = "custom tag names must only contain lowercase letters and hyphens, \
+                            and must start with a letter";
+                lcx.dcx().err(msg).span(span).note(note).emit();
+            }
+        }
+    }
+}
+
+/// Parses a tag name and its content.
+fn parse_tag<'a>(tag: &str, content: &'a str) -> Result<(NatSpecKind, &'a str), TagError> {
+    let kind = match tag {
+        "title" => NatSpecKind::Title,
+        "author" => NatSpecKind::Author,
+        "notice" => NatSpecKind::Notice,
+        "dev" => NatSpecKind::Dev,
+        "return" => NatSpecKind::Return,
+        "param" => {
+            let (name, content) = split_word(content);
+            return Ok((NatSpecKind::Param { name: Symbol::intern(name) }, content));
+        }
+        "inheritdoc" => {
+            let (contract, content) = split_word(content);
+            return Ok((NatSpecKind::Inheritdoc { contract: Symbol::intern(contract) }, content));
+        }
+        _ => {
+            let name = tag.strip_prefix("custom:").ok_or(TagError::Unknown)?;
+            if !is_valid_custom_tag(name) {
+                return Err(TagError::InvalidCustom);
+            }
+            NatSpecKind::Custom { name: Symbol::intern(name) }
+        }
+    };
+    Ok((kind, content))
+}
+
+/// Returns `true` if `name` is a valid `@custom:<name>` tag name.
+fn is_valid_custom_tag(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.bytes().all(|b| b.is_ascii_lowercase() || b == b'-')
+}
+
+/// Returns the trimmed lines of the given doc-comment, along with their byte offset in the
+/// comment's contents. Leading `*`s are stripped from block comment lines.
+fn doc_lines(doc: &ast::DocComment) -> Vec<(usize, &str)> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for raw in doc.symbol.as_str().split('\n') {
+        let mut line = raw.trim_start();
+        if doc.kind == CommentKind::Block {
+            line = line.strip_prefix('*').unwrap_or(line).trim_start();
+        }
+        lines.push((offset + raw.len() - line.len(), line.trim_end()));
+        offset += raw.len() + 1;
+    }
+    lines
+}
+
+/// Splits the first whitespace-delimited word from `s`, trimming both parts.
+fn split_word(s: &str) -> (&str, &str) {
+    let s = s.trim_start();
+    match s.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim()),
+        None => (s, ""),
+    }
+}
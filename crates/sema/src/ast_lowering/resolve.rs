@@ -258,6 +258,17 @@ impl super::LoweringContext<'_, '_, '_> {
             };
         }
 
+        for id in self.hir.contract_ids() {
+            let ast_item = self.hir_to_ast[&hir::ItemId::Contract(id)];
+            let ast::ItemKind::Contract(ast_contract) = &ast_item.kind else { unreachable!() };
+            let Some(layout) = &ast_contract.layout else { continue };
+            // The base slot is evaluated outside of the contract, so only file-level constants can
+            // be referenced.
+            let source = self.hir.contract(id).source;
+            let mut cx = mk_resolver!(@scopes SymbolResolverScopes::new_in(source, None));
+            self.hir.contracts[id].layout = Some(cx.lower_expr(layout.slot));
+        }
+
         for id in self.hir.udvt_ids() {
             let ast_item = self.hir_to_ast[&hir::ItemId::Udvt(id)];
             let ast::ItemKind::Udvt(ast_udvt) = &ast_item.kind else { unreachable!() };
@@ -99,7 +99,17 @@ impl<'ast> Visit<'ast> for AstValidator<'_> {
     }
 
     fn visit_stmt(&mut self, stmt: &'ast ast::Stmt<'ast>) -> ControlFlow<Self::BreakValue> {
-        let Stmt { kind, .. } = stmt;
+        let Stmt { docs, kind, .. } = stmt;
+
+        // Inline assembly can be annotated with `/// @solidity memory-safe-assembly`.
+        if !docs.is_empty() && !matches!(kind, StmtKind::Assembly(_)) {
+            let span = docs.iter().map(|doc| doc.span).reduce(Span::to).unwrap();
+            self.dcx()
+                .warn("doc comments on statements are ignored")
+                .span(span)
+                .help("use a regular comment instead: `//` or `/*`")
+                .emit();
+        }
 
         match kind {
             StmtKind::While(_, body, ..)
@@ -0,0 +1,686 @@
+//! HIR pretty-printer for `-Zdump=hir`.
+//!
+//! The output resembles Solidity, but identifiers are printed as the canonical names of the items
+//! they resolve to, and declarations are printed with their resolved types, including data
+//! locations where they were written out. Loops are printed in their desugared form.
+
+use super::Selection;
+use crate::{
+    hir::{self, FunctionKind, StateMutability, Visibility},
+    ty::{Gcx, Ty, TyKind},
+};
+use solar_ast::ast::{ElementaryType, LitKind, StrKind};
+use solar_data_structures::map::FxHashSet;
+use std::fmt::{self, Write};
+
+/// Pretty-prints the selected sources and items.
+pub(super) fn print(gcx: Gcx<'_>, selections: &[Selection]) -> String {
+    let mut printer = Printer { gcx, out: String::new(), indent: 0 };
+    for (i, selection) in selections.iter().enumerate() {
+        if i > 0 {
+            printer.out.push('\n');
+        }
+        printer.selection(selection);
+    }
+    printer.out
+}
+
+struct Printer<'gcx> {
+    gcx: Gcx<'gcx>,
+    out: String,
+    indent: usize,
+}
+
+impl<'gcx> Printer<'gcx> {
+    fn word(&mut self, s: impl fmt::Display) {
+        write!(self.out, "{s}").unwrap();
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+    }
+
+    fn comma_sep<T: Copy>(&mut self, items: &[T], mut f: impl FnMut(&mut Self, T)) {
+        for (i, &item) in items.iter().enumerate() {
+            if i > 0 {
+                self.word(", ");
+            }
+            f(self, item);
+        }
+    }
+
+    fn selection(&mut self, selection: &Selection) {
+        let gcx = self.gcx;
+        let source = gcx.hir.source(selection.source);
+        self.word(format_args!("// {}", source.file.name.display()));
+        let Some(items) = &selection.items else {
+            let mut imported = FxHashSet::default();
+            for &(_, import) in source.imports {
+                if !imported.insert(import) {
+                    continue;
+                }
+                self.newline();
+                self.word(format_args!("import {};", self.source_name(import)));
+            }
+            if !source.imports.is_empty() {
+                self.newline();
+            }
+            for &id in source.items {
+                self.newline();
+                self.item(id);
+                self.newline();
+            }
+            return;
+        };
+
+        // Print the selected contract members inside of their contract.
+        let mut i = 0;
+        while i < items.len() {
+            self.newline();
+            let contract = gcx.hir.item(items[i]).contract();
+            let end = items[i..]
+                .iter()
+                .position(|&id| gcx.hir.item(id).contract() != contract)
+                .map_or(items.len(), |n| i + n);
+            if let Some(contract) = contract {
+                self.contract_header(contract);
+                self.word(" {");
+                self.indent += 1;
+                for &id in &items[i..end] {
+                    self.newline();
+                    self.item(id);
+                }
+                self.indent -= 1;
+                self.newline();
+                self.word("}");
+            } else {
+                for (j, &id) in items[i..end].iter().enumerate() {
+                    if j > 0 {
+                        self.newline();
+                    }
+                    self.item(id);
+                }
+            }
+            self.newline();
+            i = end;
+        }
+    }
+
+    fn item(&mut self, id: hir::ItemId) {
+        let gcx = self.gcx;
+        match gcx.hir.item(id) {
+            hir::Item::Contract(contract) => {
+                self.contract_header(id.as_contract().unwrap());
+                if contract.items.is_empty() {
+                    self.word(" {}");
+                    return;
+                }
+                self.word(" {");
+                self.indent += 1;
+                for &id in contract.items {
+                    self.newline();
+                    self.item(id);
+                }
+                self.indent -= 1;
+                self.newline();
+                self.word("}");
+            }
+            hir::Item::Function(_) => self.function(id.as_function().unwrap()),
+            hir::Item::Variable(_) => {
+                self.variable(id.as_variable().unwrap());
+                self.word(";");
+            }
+            hir::Item::Struct(strukt) => {
+                self.word(format_args!("struct {} {{", strukt.name));
+                self.indent += 1;
+                for &field in strukt.fields {
+                    self.newline();
+                    self.variable(field);
+                    self.word(";");
+                }
+                self.indent -= 1;
+                self.newline();
+                self.word("}");
+            }
+            hir::Item::Enum(enumm) => {
+                self.word(format_args!("enum {} {{ ", enumm.name));
+                self.comma_sep(enumm.variants, |this, variant| this.word(variant));
+                self.word(" }");
+            }
+            hir::Item::Udvt(udvt) => {
+                let ty = match gcx.type_of_item(id).kind {
+                    TyKind::Udvt(ty, _) => ty,
+                    _ => gcx.type_of_item(id),
+                };
+                self.word(format_args!("type {} is ", udvt.name));
+                self.ty(ty);
+                self.word(";");
+            }
+            hir::Item::Error(error) => {
+                self.word(format_args!("error {}(", error.name));
+                self.comma_sep(error.parameters, Self::variable);
+                self.word(");");
+            }
+            hir::Item::Event(event) => {
+                self.word(format_args!("event {}(", event.name));
+                self.comma_sep(event.parameters, Self::variable);
+                self.word(")");
+                if event.anonymous {
+                    self.word(" anonymous");
+                }
+                self.word(";");
+            }
+        }
+    }
+
+    fn contract_header(&mut self, id: hir::ContractId) {
+        let contract = self.gcx.hir.contract(id);
+        self.word(format_args!("{} {}", contract.kind.to_str(), contract.name));
+        if !contract.bases.is_empty() {
+            self.word(" is ");
+            self.comma_sep(contract.bases, |this, base| this.item_name(base.into()));
+        }
+    }
+
+    fn function(&mut self, id: hir::FunctionId) {
+        let gcx = self.gcx;
+        let func = gcx.hir.function(id);
+        if let Some(var) = func.gettee {
+            // Getters have no declared parameters, so print them from their type.
+            let ty = gcx.type_of_item(id.into());
+            self.word(format_args!("function {}(", func.name.unwrap()));
+            self.comma_sep(ty.parameters().unwrap_or_default(), Self::ty);
+            self.word(format_args!(") {} {}", func.visibility, func.state_mutability));
+            self.word(" returns (");
+            self.comma_sep(ty.returns().unwrap_or_default(), Self::ty);
+            self.word("); // getter of ");
+            self.item_name(var.into());
+            return;
+        }
+
+        match func.kind {
+            FunctionKind::Function | FunctionKind::Modifier => {
+                self.word(format_args!("{} ", func.kind.to_str()));
+                if let Some(name) = func.name {
+                    self.word(name);
+                }
+            }
+            kind => self.word(kind.to_str()),
+        }
+        self.word("(");
+        self.comma_sep(func.parameters, Self::variable);
+        self.word(")");
+        if func.contract.is_some() && func.kind != FunctionKind::Modifier {
+            self.word(format_args!(" {}", func.visibility));
+        }
+        if func.state_mutability != StateMutability::NonPayable {
+            self.word(format_args!(" {}", func.state_mutability));
+        }
+        if func.marked_virtual {
+            self.word(" virtual");
+        }
+        if func.override_ {
+            self.overrides(func.overrides);
+        }
+        for &modifier in func.modifiers {
+            self.word(" ");
+            self.item_name(modifier);
+        }
+        if !func.returns.is_empty() {
+            self.word(" returns (");
+            self.comma_sep(func.returns, Self::variable);
+            self.word(")");
+        }
+        match func.body {
+            Some(body) => {
+                self.word(" ");
+                self.block(body);
+            }
+            None => self.word(";"),
+        }
+    }
+
+    fn overrides(&mut self, overrides: &[hir::ContractId]) {
+        self.word(" override");
+        if !overrides.is_empty() {
+            self.word("(");
+            self.comma_sep(overrides, |this, id| this.item_name(id.into()));
+            self.word(")");
+        }
+    }
+
+    /// Prints a variable declaration, without the trailing semicolon.
+    fn variable(&mut self, id: hir::VariableId) {
+        let gcx = self.gcx;
+        let var = gcx.hir.variable(id);
+        let mut ty = gcx.type_of_item(id.into());
+        if var.is_state_variable || var.data_location.is_none() {
+            // Only print data locations that were written out in the source.
+            ty = ty.peel_refs();
+        }
+        self.ty(ty);
+        if var.indexed {
+            self.word(" indexed");
+        }
+        if var.is_state_variable {
+            if let Some(visibility) = var.visibility {
+                self.word(format_args!(" {visibility}"));
+            }
+        }
+        if let Some(mutability) = var.mutability {
+            self.word(format_args!(" {}", mutability.to_str()));
+        }
+        if var.override_ {
+            self.overrides(var.overrides);
+        }
+        if let Some(name) = var.name {
+            self.word(format_args!(" {name}"));
+        }
+        if let Some(init) = var.initializer {
+            self.word(" = ");
+            self.expr(init);
+        }
+    }
+
+    fn block(&mut self, block: hir::Block<'_>) {
+        if block.is_empty() {
+            self.word("{}");
+            return;
+        }
+        self.word("{");
+        self.indent += 1;
+        for stmt in block {
+            self.newline();
+            self.stmt(stmt);
+        }
+        self.indent -= 1;
+        self.newline();
+        self.word("}");
+    }
+
+    fn stmt(&mut self, stmt: &hir::Stmt<'_>) {
+        match stmt.kind {
+            hir::StmtKind::DeclSingle(var) => {
+                self.variable(var);
+                self.word(";");
+            }
+            hir::StmtKind::DeclMulti(vars, init) => {
+                self.word("(");
+                self.comma_sep(vars, |this, var| {
+                    if let Some(var) = var {
+                        this.variable(var);
+                    }
+                });
+                self.word(") = ");
+                self.expr(init);
+                self.word(";");
+            }
+            hir::StmtKind::Block(block) => self.block(block),
+            hir::StmtKind::UncheckedBlock(block) => {
+                self.word("unchecked ");
+                self.block(block);
+            }
+            hir::StmtKind::Emit(res, ref args) => {
+                self.word("emit ");
+                self.res(res);
+                self.call_args(args);
+                self.word(";");
+            }
+            hir::StmtKind::Revert(res, ref args) => {
+                self.word("revert ");
+                self.res(res);
+                self.call_args(args);
+                self.word(";");
+            }
+            hir::StmtKind::Return(expr) => {
+                self.word("return");
+                if let Some(expr) = expr {
+                    self.word(" ");
+                    self.expr(expr);
+                }
+                self.word(";");
+            }
+            hir::StmtKind::Break => self.word("break;"),
+            hir::StmtKind::Continue => self.word("continue;"),
+            hir::StmtKind::Loop(block, source) => {
+                self.word(format_args!("loop /* {} */ ", source.name()));
+                self.block(block);
+            }
+            hir::StmtKind::If(cond, then, else_) => {
+                self.word("if (");
+                self.expr(cond);
+                self.word(") ");
+                self.stmt(then);
+                if let Some(else_) = else_ {
+                    self.word(" else ");
+                    self.stmt(else_);
+                }
+            }
+            hir::StmtKind::Try(try_) => {
+                self.word("try ");
+                self.expr(&try_.expr);
+                if !try_.returns.is_empty() {
+                    self.word(" returns (");
+                    self.comma_sep(try_.returns, Self::variable);
+                    self.word(")");
+                }
+                self.word(" ");
+                self.block(try_.block);
+                for catch in try_.catch {
+                    self.word(" catch ");
+                    if let Some(name) = catch.name {
+                        self.word(name);
+                    }
+                    if catch.name.is_some() || !catch.args.is_empty() {
+                        self.word("(");
+                        self.comma_sep(catch.args, Self::variable);
+                        self.word(") ");
+                    }
+                    self.block(catch.block);
+                }
+            }
+            hir::StmtKind::Expr(expr) => {
+                self.expr(expr);
+                self.word(";");
+            }
+            hir::StmtKind::Placeholder => self.word("_;"),
+            hir::StmtKind::Err(_) => self.word("<error>;"),
+        }
+    }
+
+    /// Prints an expression, parenthesized if it's a compound expression.
+    fn operand(&mut self, expr: &hir::Expr<'_>) {
+        let compound = matches!(
+            expr.kind,
+            hir::ExprKind::Assign(..) | hir::ExprKind::Binary(..) | hir::ExprKind::Ternary(..)
+        );
+        if compound {
+            self.word("(");
+        }
+        self.expr(expr);
+        if compound {
+            self.word(")");
+        }
+    }
+
+    fn expr(&mut self, expr: &hir::Expr<'_>) {
+        match expr.kind {
+            hir::ExprKind::Array(exprs) => {
+                self.word("[");
+                self.comma_sep_exprs(exprs);
+                self.word("]");
+            }
+            hir::ExprKind::Assign(lhs, op, rhs) => {
+                self.operand(lhs);
+                match op {
+                    Some(op) => self.word(format_args!(" {op}= ")),
+                    None => self.word(" = "),
+                }
+                self.operand(rhs);
+            }
+            hir::ExprKind::Binary(lhs, op, rhs) => {
+                self.operand(lhs);
+                self.word(format_args!(" {op} "));
+                self.operand(rhs);
+            }
+            hir::ExprKind::Call(callee, ref args) => {
+                self.operand(callee);
+                self.call_args(args);
+            }
+            hir::ExprKind::CallOptions(callee, options) => {
+                self.operand(callee);
+                self.word("{");
+                self.named_args(options);
+                self.word("}");
+            }
+            hir::ExprKind::Delete(expr) => {
+                self.word("delete ");
+                self.operand(expr);
+            }
+            hir::ExprKind::Ident(res) => self.res(res),
+            hir::ExprKind::Index(expr, index) => {
+                self.operand(expr);
+                self.word("[");
+                if let Some(index) = index {
+                    self.expr(index);
+                }
+                self.word("]");
+            }
+            hir::ExprKind::Slice(expr, start, end) => {
+                self.operand(expr);
+                self.word("[");
+                if let Some(start) = start {
+                    self.expr(start);
+                }
+                self.word(":");
+                if let Some(end) = end {
+                    self.expr(end);
+                }
+                self.word("]");
+            }
+            hir::ExprKind::Lit(lit) => match lit.kind {
+                LitKind::Str(kind, _) => {
+                    let prefix = match kind {
+                        StrKind::Str => "",
+                        StrKind::Unicode => "unicode",
+                        StrKind::Hex => "hex",
+                    };
+                    self.word(format_args!("{prefix}\"{}\"", lit.symbol));
+                }
+                _ => self.word(lit.symbol),
+            },
+            hir::ExprKind::Member(expr, member) => {
+                self.operand(expr);
+                self.word(format_args!(".{member}"));
+            }
+            hir::ExprKind::New(ref ty) => {
+                self.word("new ");
+                self.ty(self.gcx.type_of_hir_ty(ty));
+            }
+            hir::ExprKind::Payable(expr) => {
+                self.word("payable(");
+                self.expr(expr);
+                self.word(")");
+            }
+            hir::ExprKind::Ternary(cond, then, else_) => {
+                self.operand(cond);
+                self.word(" ? ");
+                self.operand(then);
+                self.word(" : ");
+                self.operand(else_);
+            }
+            hir::ExprKind::Tuple(exprs) => {
+                self.word("(");
+                self.comma_sep(exprs, |this, expr| {
+                    if let Some(expr) = expr {
+                        this.expr(expr);
+                    }
+                });
+                self.word(")");
+            }
+            hir::ExprKind::TypeCall(ref ty) => {
+                self.word("type(");
+                self.ty(self.gcx.type_of_hir_ty(ty));
+                self.word(")");
+            }
+            hir::ExprKind::Type(ref ty) => self.ty(self.gcx.type_of_hir_ty(ty)),
+            hir::ExprKind::Unary(op, expr) => {
+                if op.kind.is_prefix() {
+                    self.word(op);
+                    self.operand(expr);
+                } else {
+                    self.operand(expr);
+                    self.word(op);
+                }
+            }
+            hir::ExprKind::Err(_) => self.word("<error>"),
+        }
+    }
+
+    fn comma_sep_exprs(&mut self, exprs: &[hir::Expr<'_>]) {
+        for (i, expr) in exprs.iter().enumerate() {
+            if i > 0 {
+                self.word(", ");
+            }
+            self.expr(expr);
+        }
+    }
+
+    fn named_args(&mut self, args: &[hir::NamedArg<'_>]) {
+        for (i, arg) in args.iter().enumerate() {
+            if i > 0 {
+                self.word(", ");
+            }
+            self.word(format_args!("{}: ", arg.name));
+            self.expr(&arg.value);
+        }
+    }
+
+    fn call_args(&mut self, args: &hir::CallArgs<'_>) {
+        match args {
+            hir::CallArgs::Unnamed(exprs) => {
+                self.word("(");
+                self.comma_sep_exprs(exprs);
+                self.word(")");
+            }
+            hir::CallArgs::Named(args) => {
+                self.word("({");
+                self.named_args(args);
+                self.word("})");
+            }
+        }
+    }
+
+    /// Prints the resolutions of a name. Multiple candidates are printed with their types.
+    fn res(&mut self, res: &[hir::Res]) {
+        if let [res] = res {
+            return self.single_res(*res);
+        }
+        // Public state variables also resolve to their getters; only print the variable.
+        let is_getter = |res: &hir::Res| matches!(res, hir::Res::Item(hir::ItemId::Function(id)) if self.gcx.hir.function(*id).is_getter());
+        let res = res.iter().copied().filter(|res| !is_getter(res)).collect::<Vec<_>>();
+        if let [res] = res[..] {
+            return self.single_res(res);
+        }
+        self.word("{");
+        for (i, &res) in res.iter().enumerate() {
+            if i > 0 {
+                self.word(" | ");
+            }
+            self.single_res(res);
+            self.word(": ");
+            self.ty(self.gcx.type_of_res(res));
+        }
+        self.word("}");
+    }
+
+    fn single_res(&mut self, res: hir::Res) {
+        match res {
+            hir::Res::Item(hir::ItemId::Variable(id))
+                if !self.gcx.hir.variable(id).is_state_variable =>
+            {
+                match self.gcx.hir.variable(id).name {
+                    Some(name) => self.word(name),
+                    None => self.word("<unnamed>"),
+                }
+            }
+            hir::Res::Item(id) => self.item_name(id),
+            hir::Res::Namespace(id) => self.word(self.source_name(id)),
+            hir::Res::Builtin(builtin) => self.word(builtin.name()),
+            hir::Res::Err(_) => self.word("<error>"),
+        }
+    }
+
+    fn item_name(&mut self, id: hir::ItemId) {
+        if self.gcx.item_name_opt(id).is_some() {
+            self.word(self.gcx.item_canonical_name(id));
+        } else {
+            self.word(format_args!("<{}>", self.gcx.hir.item(id).description()));
+        }
+    }
+
+    fn source_name(&self, id: hir::SourceId) -> String {
+        format!("{:?}", self.gcx.hir.source(id).file.name.display().to_string())
+    }
+
+    fn ty(&mut self, ty: Ty<'gcx>) {
+        match ty.kind {
+            TyKind::Elementary(ty) => match ty {
+                ElementaryType::Address(true) => self.word("address payable"),
+                ElementaryType::Fixed(m, n) => {
+                    self.word(format_args!("fixed{}x{}", m.bits(), n.get()))
+                }
+                ElementaryType::UFixed(m, n) => {
+                    self.word(format_args!("ufixed{}x{}", m.bits(), n.get()))
+                }
+                _ => self.word(ty.to_abi_str()),
+            },
+            TyKind::StringLiteral(..) => self.word("literal_string"),
+            TyKind::IntLiteral(_) => self.word("int_const"),
+            // Value types don't have a data location.
+            TyKind::Ref(ty, _) if ty.is_value_type() => self.ty(ty),
+            TyKind::Ref(ty, loc) => {
+                self.ty(ty);
+                self.word(format_args!(" {loc}"));
+            }
+            TyKind::DynArray(ty) => {
+                self.ty(ty);
+                self.word("[]");
+            }
+            TyKind::Array(ty, len) => {
+                self.ty(ty);
+                self.word(format_args!("[{len}]"));
+            }
+            TyKind::Tuple(tys) => {
+                self.word("(");
+                self.comma_sep(tys, Self::ty);
+                self.word(")");
+            }
+            TyKind::Mapping(key, value) => {
+                self.word("mapping(");
+                self.ty(key);
+                self.word(" => ");
+                self.ty(value);
+                self.word(")");
+            }
+            TyKind::FnPtr(f) => {
+                self.word("function(");
+                self.comma_sep(f.parameters, Self::ty);
+                self.word(")");
+                if f.visibility == Visibility::External {
+                    self.word(" external");
+                }
+                if f.state_mutability != StateMutability::NonPayable {
+                    self.word(format_args!(" {}", f.state_mutability));
+                }
+                if !f.returns.is_empty() {
+                    self.word(" returns (");
+                    self.comma_sep(f.returns, Self::ty);
+                    self.word(")");
+                }
+            }
+            TyKind::Contract(id) => self.item_name(id.into()),
+            TyKind::Struct(id) => self.item_name(id.into()),
+            TyKind::Enum(id) => self.item_name(id.into()),
+            TyKind::Udvt(_, id) => self.item_name(id.into()),
+            TyKind::Error(_, id) => self.item_name(id.into()),
+            TyKind::Event(_, id) => self.item_name(id.into()),
+            TyKind::Module(id) => self.word(self.source_name(id)),
+            TyKind::BuiltinModule(builtin) => self.word(builtin.name()),
+            TyKind::Type(ty) => {
+                self.word("type(");
+                self.ty(ty);
+                self.word(")");
+            }
+            TyKind::Meta(ty) => {
+                self.word("meta(");
+                self.ty(ty);
+                self.word(")");
+            }
+            TyKind::Err(_) => self.word("<error>"),
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! `-Zdump` implementation.
+
+use crate::{hir, ty::Gcx, ParsedSources};
+use solar_interface::{config::DumpKind, source_map::FileName, Result, Session};
+
+mod hir_pretty;
+
+pub(crate) fn dump_ast(
+    sess: &Session,
+    sources: &ParsedSources<'_>,
+    kind: DumpKind,
+    paths: Option<&[String]>,
+) -> Result<()> {
+    let Some(paths) = paths else {
+        if kind.is_ast_json() {
+            return dump_json(sess, &sources.raw);
+        }
+        println!("{sources:#?}");
+        return Ok(());
+    };
+
+    let mut selected = Vec::with_capacity(paths.len());
+    for path in paths {
+        if let Some(source) = sources.iter().find(|&s| match_file_name(&s.file.name, path)) {
+            selected.push(source);
+        } else {
+            let files = sources.iter().map(|s| &s.file.name);
+            return Err(no_matching_source(sess, kind, path, files));
+        }
+    }
+    if kind.is_ast_json() {
+        return dump_json(sess, &selected);
+    }
+    for source in selected {
+        println!("{source:#?}");
+    }
+    Ok(())
+}
+
+pub(crate) fn dump_hir(gcx: Gcx<'_>, kind: DumpKind, paths: Option<&[String]>) -> Result<()> {
+    let selections = match paths {
+        Some(paths) => {
+            paths.iter().map(|path| select(gcx, kind, path)).collect::<Result<Vec<_>>>()?
+        }
+        None => gcx.hir.source_ids().map(|source| Selection { source, items: None }).collect(),
+    };
+
+    match kind {
+        DumpKind::HirDebug => {
+            if paths.is_none() {
+                println!("{:#?}", gcx.hir);
+                return Ok(());
+            }
+            for selection in &selections {
+                match &selection.items {
+                    Some(items) => {
+                        for &id in items {
+                            println!("{:#?}", gcx.hir.item(id));
+                        }
+                    }
+                    None => println!("{:#?}", gcx.hir.source(selection.source)),
+                }
+            }
+            Ok(())
+        }
+        DumpKind::HirJson => dump_hir_json(gcx, paths.is_some().then_some(&selections[..])),
+        _ => {
+            print!("{}", hir_pretty::print(gcx, &selections));
+            Ok(())
+        }
+    }
+}
+
+/// A source file or a list of items selected by a `-Zdump=hir=<path>[:<item>]` path.
+pub(crate) struct Selection {
+    pub(crate) source: hir::SourceId,
+    /// The selected items. `None` if the whole source was selected.
+    pub(crate) items: Option<Vec<hir::ItemId>>,
+}
+
+/// Resolves a `<path>[:<contract>[.<item>]]` selector.
+///
+/// The item part selects all top-level items with the given name, or all items with the given
+/// name in the given contract, including overloads.
+fn select(gcx: Gcx<'_>, kind: DumpKind, selector: &str) -> Result<Selection> {
+    let (path, item) = match selector.rsplit_once(':') {
+        Some((path, item))
+            if !item.is_empty()
+                && item.split('.').all(|s| !s.is_empty() && s.chars().all(is_ident_char)) =>
+        {
+            (path, Some(item))
+        }
+        _ => (selector, None),
+    };
+
+    let Some(source) =
+        gcx.hir.source_ids().find(|&id| match_file_name(&gcx.hir.source(id).file.name, path))
+    else {
+        let files = gcx.hir.sources().map(|s| &s.file.name);
+        return Err(no_matching_source(gcx.sess, kind, path, files));
+    };
+    let Some(item) = item else { return Ok(Selection { source, items: None }) };
+
+    let source_items = gcx.hir.source(source).items;
+    let items = match item.split_once('.') {
+        None => named_items(gcx, source_items, item),
+        Some((contract, name)) => named_items(gcx, source_items, contract)
+            .into_iter()
+            .filter_map(|id| id.as_contract())
+            .flat_map(|id| named_items(gcx, gcx.hir.contract(id).items, name))
+            .collect(),
+    };
+    if items.is_empty() {
+        let msg = format!("`-Zdump={kind}={selector:?}` did not match any item");
+        let mut names = source_items
+            .iter()
+            .filter_map(|&id| gcx.item_name_opt(id))
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        names.dedup();
+        let note = format!(
+            "available items in {}: {}",
+            gcx.hir.source(source).file.name.display(),
+            names.join(", ")
+        );
+        return Err(gcx.dcx().err(msg).note(note).emit());
+    }
+    Ok(Selection { source, items: Some(items) })
+}
+
+fn named_items(gcx: Gcx<'_>, items: &[hir::ItemId], name: &str) -> Vec<hir::ItemId> {
+    items
+        .iter()
+        .copied()
+        .filter(|&id| gcx.item_name_opt(id).is_some_and(|ident| ident.as_str() == name))
+        .collect()
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+#[cfg(feature = "serde")]
+fn dump_hir_json(gcx: Gcx<'_>, selections: Option<&[Selection]>) -> Result<()> {
+    use solar_data_structures::map::FxHashSet;
+
+    #[derive(serde::Serialize)]
+    struct ItemType<'gcx> {
+        item: hir::ItemId,
+        ty: crate::ty::Ty<'gcx>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct HirDump<'a, 'gcx> {
+        hir: &'a hir::Hir<'gcx>,
+        types: Vec<ItemType<'gcx>>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct SelectedItem<'a, 'gcx> {
+        id: hir::ItemId,
+        item: hir::Item<'a, 'gcx>,
+        ty: crate::ty::Ty<'gcx>,
+    }
+
+    let Some(selections) = selections else {
+        let types = gcx.hir.item_ids().map(|item| ItemType { item, ty: gcx.type_of_item(item) });
+        return dump_json(gcx.sess, &HirDump { hir: &gcx.hir, types: types.collect() });
+    };
+
+    // Include all the items that are nested inside of the selected ones, such as parameters,
+    // local variables and contract members, so that all the referenced IDs can be looked up.
+    let mut seen = FxHashSet::default();
+    let mut items = Vec::new();
+    for selection in selections {
+        let in_selection = |id: hir::ItemId| {
+            let item = gcx.hir.item(id);
+            item.source() == selection.source
+                && selection.items.as_ref().is_none_or(|selected| {
+                    selected.iter().any(|&s| gcx.item_span(s).contains(item.span()))
+                })
+        };
+        for id in gcx.hir.item_ids().filter(|&id| in_selection(id)) {
+            if seen.insert(id) {
+                items.push(SelectedItem { id, item: gcx.hir.item(id), ty: gcx.type_of_item(id) });
+            }
+        }
+    }
+    dump_json(gcx.sess, &items)
+}
+
+#[cfg(not(feature = "serde"))]
+fn dump_hir_json(gcx: Gcx<'_>, _selections: Option<&[Selection]>) -> Result<()> {
+    dump_json(gcx.sess, &())
+}
+
+#[cfg(feature = "serde")]
+fn dump_json(sess: &Session, value: &impl serde::Serialize) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    let res = if sess.pretty_json {
+        serde_json::to_writer_pretty(&mut stdout, value)
+    } else {
+        serde_json::to_writer(&mut stdout, value)
+    };
+    res.map_err(std::io::Error::from)
+        .and_then(|()| std::io::Write::write_all(&mut stdout, b"\n"))
+        .map_err(|e| sess.dcx.err(format!("failed to write JSON dump: {e}")).emit())
+}
+
+#[cfg(not(feature = "serde"))]
+fn dump_json<T: ?Sized>(sess: &Session, _value: &T) -> Result<()> {
+    let msg = "JSON dumps require the `serde` feature of `solar-sema`";
+    Err(sess.dcx.err(msg).emit())
+}
+
+fn no_matching_source<'a>(
+    sess: &Session,
+    kind: DumpKind,
+    path: &str,
+    files: impl Iterator<Item = &'a FileName>,
+) -> solar_interface::diagnostics::ErrorGuaranteed {
+    let msg = format!("`-Zdump={kind}={path:?}` did not match any source file");
+    let note = format!(
+        "available source files: {}",
+        files.map(|name| name.display().to_string()).collect::<Vec<_>>().join(", ")
+    );
+    sess.dcx.err(msg).note(note).emit()
+}
+
+fn match_file_name(name: &FileName, path: &str) -> bool {
+    match name {
+        FileName::Real(path_buf) => {
+            path_buf.as_os_str() == path
+                || path_buf.file_name() == Some(path.as_ref())
+                || path_buf.file_stem() == Some(path.as_ref())
+        }
+        FileName::Stdin => path == "stdin" || path == "<stdin>",
+        FileName::Custom(name) => path == name,
+    }
+}
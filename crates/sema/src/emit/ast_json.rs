@@ -0,0 +1,1580 @@
+//! Solc-compatible compact AST JSON.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/using-the-compiler.html#output-description>
+//!
+//! All declarations are numbered up front, so that `referencedDeclaration` can point to
+//! declarations that come later or that are in other sources. References are taken from the HIR.
+//!
+//! Expressions are not type checked yet, so `typeDescriptions` are only filled in for declarations,
+//! type names, literals and identifiers that refer to a single declaration, and are `null`
+//! otherwise. Overloaded identifiers list all candidates in `overloadedDeclarations`, and
+//! references to builtins and Yul identifiers are not resolved.
+
+use super::storage_layout::{Loc, TypeNames};
+use crate::{
+    hir::{self, Visit},
+    metadata,
+    parse::{ParsedSource, ParsedSources},
+    ty::{Gcx, Ty, TyKind},
+};
+use alloy_primitives::{hex, keccak256};
+use serde_json::{json, Value};
+use solar_ast::{
+    ast::{self, yul},
+    token::{Delimiter, Token},
+};
+use solar_data_structures::{map::FxHashMap, Never};
+use solar_interface::{kw, source_map::SourceFile, BytePos, Ident, Pos, Span, Symbol};
+use solar_parse::Lexer;
+use std::{ops::ControlFlow, sync::Arc};
+
+/// Returns the IDs of all sources sorted by name, which is the order of the file indices in `src`
+/// locations.
+pub(crate) fn source_list(gcx: Gcx<'_>) -> Vec<hir::SourceId> {
+    let mut ids = gcx.hir.source_ids().collect::<Vec<_>>();
+    ids.sort_by_cached_key(|&id| gcx.hir.source(id).file.name.display().to_string());
+    ids
+}
+
+/// Returns the AST JSON of all Solidity sources, in the order of [`source_list`].
+pub(crate) fn ast_json(gcx: Gcx<'_>, asts: &ParsedSources<'_>) -> Vec<(hir::SourceId, Value)> {
+    let source_list = source_list(gcx);
+    let mut cx = AstJson::new(gcx, &source_list);
+    source_list
+        .iter()
+        .enumerate()
+        .filter_map(|(index, &id)| Some((id, cx.source_unit(id, index, &asts.sources[id])?)))
+        .collect()
+}
+
+/// The HIR resolutions of AST nodes, keyed by span.
+#[derive(Default)]
+struct Resolutions<'hir> {
+    /// Identifier expressions.
+    idents: FxHashMap<Span, &'hir [hir::Res]>,
+    /// The event or error paths of `emit` and `revert` statements, keyed by statement span.
+    stmts: FxHashMap<Span, &'hir [hir::Res]>,
+    /// Type names.
+    types: FxHashMap<Span, &'hir hir::Type<'hir>>,
+}
+
+struct ResolutionCollector<'hir> {
+    hir: &'hir hir::Hir<'hir>,
+    res: Resolutions<'hir>,
+}
+
+impl<'hir> Visit<'hir> for ResolutionCollector<'hir> {
+    type BreakValue = Never;
+
+    fn hir(&self) -> &'hir hir::Hir<'hir> {
+        self.hir
+    }
+
+    fn visit_stmt(&mut self, stmt: &'hir hir::Stmt<'hir>) -> ControlFlow<Self::BreakValue> {
+        if let hir::StmtKind::Emit(res, _) | hir::StmtKind::Revert(res, _) = stmt.kind {
+            self.res.stmts.insert(stmt.span, res);
+        }
+        self.walk_stmt(stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'hir hir::Expr<'hir>) -> ControlFlow<Self::BreakValue> {
+        if let hir::ExprKind::Ident(res) = expr.kind {
+            self.res.idents.insert(expr.span, res);
+        }
+        self.walk_expr(expr)
+    }
+
+    fn visit_ty(&mut self, ty: &'hir hir::Type<'hir>) -> ControlFlow<Self::BreakValue> {
+        self.res.types.insert(ty.span, ty);
+        self.walk_ty(ty)
+    }
+}
+
+struct AstJson<'gcx> {
+    gcx: Gcx<'gcx>,
+    /// The node IDs of the source units.
+    source_unit_ids: FxHashMap<hir::SourceId, usize>,
+    /// The HIR items and node IDs of declarations, keyed by the span of their definition.
+    decls: FxHashMap<Span, (hir::ItemId, usize)>,
+    /// The node IDs of declarations.
+    item_ids: FxHashMap<hir::ItemId, usize>,
+    res: Resolutions<'gcx>,
+    next_id: usize,
+
+    /// The current source file.
+    file: Arc<SourceFile>,
+    /// The index of the current source file in the source list.
+    file_index: usize,
+    /// The tokens of the current source file, used to locate nodes without a span.
+    tokens: Vec<Token>,
+    /// The node ID of the current scope.
+    scope: usize,
+    /// The node ID of the return parameter list of the current function.
+    return_params: Value,
+}
+
+impl<'gcx> AstJson<'gcx> {
+    fn new(gcx: Gcx<'gcx>, source_list: &[hir::SourceId]) -> Self {
+        let source_unit_ids =
+            source_list.iter().enumerate().map(|(node_id, &id)| (id, node_id)).collect();
+        let mut next_id = source_list.len();
+        let mut decls = FxHashMap::default();
+        let mut item_ids = FxHashMap::default();
+        for id in gcx.hir.item_ids() {
+            if matches!(id, hir::ItemId::Function(f) if gcx.hir.function(f).is_getter()) {
+                continue;
+            }
+            decls.insert(gcx.hir.item(id).span(), (id, next_id));
+            item_ids.insert(id, next_id);
+            next_id += 1;
+        }
+
+        let mut collector = ResolutionCollector { hir: &gcx.hir, res: Resolutions::default() };
+        for id in gcx.hir.source_ids() {
+            let _ = collector.visit_nested_source(id);
+        }
+
+        Self {
+            gcx,
+            source_unit_ids,
+            decls,
+            item_ids,
+            res: collector.res,
+            next_id,
+            file: Arc::clone(&gcx.hir.source(source_list[0]).file),
+            file_index: 0,
+            tokens: Vec::new(),
+            scope: 0,
+            return_params: Value::Null,
+        }
+    }
+
+    fn source_unit(
+        &mut self,
+        id: hir::SourceId,
+        file_index: usize,
+        source: &ParsedSource<'_>,
+    ) -> Option<Value> {
+        let ast = source.ast.as_ref()?;
+        self.file = Arc::clone(&source.file);
+        self.file_index = file_index;
+        self.tokens = Lexer::from_source_file(self.gcx.sess, &source.file).into_tokens();
+        let unit_id = self.source_unit_ids[&id];
+        self.scope = unit_id;
+
+        let nodes = ast
+            .items
+            .iter_enumerated()
+            .map(|(item_id, item)| match &item.kind {
+                ast::ItemKind::Import(import) => {
+                    let imported = source.imports.iter().find(|&&(i, _)| i == item_id);
+                    self.import(item.span, import, imported.map(|&(_, id)| id))
+                }
+                _ => self.item(item),
+            })
+            .collect::<Vec<_>>();
+
+        let mut exported_symbols = serde_json::Map::new();
+        for &item in self.gcx.hir.source(id).items {
+            let Some(name) = self.gcx.hir.item(item).name() else { continue };
+            let ids = exported_symbols.entry(name.to_string()).or_insert_with(|| json!([]));
+            ids.as_array_mut().unwrap().push(self.item_ref(item));
+        }
+
+        let file = &source.file;
+        let span = Span::new(file.start_pos, file.start_pos + BytePos(file.src.len() as u32));
+        let fields = json!({
+            "absolutePath": file.name.display().to_string(),
+            "exportedSymbols": exported_symbols,
+            "license": metadata::spdx_license(&file.src),
+            "nodes": nodes,
+        });
+        Some(self.node(unit_id, "SourceUnit", span, fields))
+    }
+
+    // --- Items ---
+
+    fn item(&mut self, item: &ast::Item<'_>) -> Value {
+        let span = item.span;
+        match &item.kind {
+            ast::ItemKind::Pragma(_) => {
+                // The first token is `pragma` and the last one is `;`.
+                let tokens = self.tokens_in(span);
+                let tokens = &tokens[1.min(tokens.len())..tokens.len().saturating_sub(1).max(1)];
+                // Like solc, a `.` that is directly followed by a number is part of the number,
+                // e.g. `0.8.0` is `0.8` and `.0`.
+                let mut literals = Vec::<Span>::new();
+                for token in tokens {
+                    match literals.last_mut() {
+                        Some(last)
+                            if self.snippet(*last) == "." && last.hi() == token.span.lo() =>
+                        {
+                            *last = last.to(token.span);
+                        }
+                        _ => literals.push(token.span),
+                    }
+                }
+                let literals = literals
+                    .into_iter()
+                    .map(|span| self.snippet(span).to_string())
+                    .collect::<Vec<_>>();
+                self.new_node("PragmaDirective", span, json!({ "literals": literals }))
+            }
+            ast::ItemKind::Import(import) => self.import(span, import, None),
+            ast::ItemKind::Using(using) => self.using(span, using),
+            ast::ItemKind::Contract(contract) => self.contract(item, contract),
+            ast::ItemKind::Function(function) => self.function(item, function),
+            ast::ItemKind::Variable(var) => self.variable(var, item.docs, false),
+            ast::ItemKind::Struct(strukt) => {
+                let (hir_id, id) = self.decl(span);
+                let scope = std::mem::replace(&mut self.scope, id);
+                let members =
+                    strukt.fields.iter().map(|field| self.variable(field, &[], false)).collect();
+                self.scope = scope;
+                let mut fields = json!({
+                    "canonicalName": self.canonical_name(hir_id, strukt.name),
+                    "members": Value::Array(members),
+                    "name": strukt.name.as_str(),
+                    "nameLocation": self.src(strukt.name.span),
+                    "scope": scope,
+                    "visibility": "public",
+                });
+                self.insert_documentation(&mut fields, item.docs);
+                self.node(id, "StructDefinition", span, fields)
+            }
+            ast::ItemKind::Enum(enumm) => {
+                let (hir_id, id) = self.decl(span);
+                let members = enumm
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let fields = json!({
+                            "name": variant.as_str(),
+                            "nameLocation": self.src(variant.span),
+                        });
+                        self.new_node("EnumValue", variant.span, fields)
+                    })
+                    .collect::<Vec<_>>();
+                let mut fields = json!({
+                    "canonicalName": self.canonical_name(hir_id, enumm.name),
+                    "members": members,
+                    "name": enumm.name.as_str(),
+                    "nameLocation": self.src(enumm.name.span),
+                });
+                self.insert_documentation(&mut fields, item.docs);
+                self.node(id, "EnumDefinition", span, fields)
+            }
+            ast::ItemKind::Udvt(udvt) => {
+                let (hir_id, id) = self.decl(span);
+                let fields = json!({
+                    "canonicalName": self.canonical_name(hir_id, udvt.name),
+                    "name": udvt.name.as_str(),
+                    "nameLocation": self.src(udvt.name.span),
+                    "underlyingType": self.type_name(&udvt.ty),
+                });
+                self.node(id, "UserDefinedValueTypeDefinition", span, fields)
+            }
+            ast::ItemKind::Error(error) => {
+                let (hir_id, id) = self.decl(span);
+                let parameters = self.event_parameters(id, error.name, error.parameters);
+                let selector = match hir_id {
+                    Some(hir_id) => hex::encode(self.gcx.function_selector(hir_id)).into(),
+                    None => Value::Null,
+                };
+                let fields = json!({
+                    "documentation": self.documentation(item.docs),
+                    "errorSelector": selector,
+                    "name": error.name.as_str(),
+                    "nameLocation": self.src(error.name.span),
+                    "parameters": parameters,
+                });
+                self.node(id, "ErrorDefinition", span, fields)
+            }
+            ast::ItemKind::Event(event) => {
+                let (hir_id, id) = self.decl(span);
+                let parameters = self.event_parameters(id, event.name, event.parameters);
+                let mut fields = json!({
+                    "anonymous": event.anonymous,
+                    "documentation": self.documentation(item.docs),
+                    "name": event.name.as_str(),
+                    "nameLocation": self.src(event.name.span),
+                    "parameters": parameters,
+                });
+                if let Some(hir::ItemId::Event(hir_id)) = hir_id {
+                    if !event.anonymous {
+                        let selector = hex::encode(self.gcx.event_selector(hir_id));
+                        fields["eventSelector"] = selector.into();
+                    }
+                }
+                self.node(id, "EventDefinition", span, fields)
+            }
+        }
+    }
+
+    fn import(
+        &mut self,
+        span: Span,
+        import: &ast::ImportDirective<'_>,
+        imported: Option<hir::SourceId>,
+    ) -> Value {
+        let (symbol_aliases, unit_alias) = match &import.items {
+            ast::ImportItems::Plain(alias) | ast::ImportItems::Glob(alias) => {
+                (Vec::new(), alias.as_ref().map_or("", |alias| alias.as_str()))
+            }
+            ast::ImportItems::Aliases(aliases) => {
+                let symbol_aliases = aliases
+                    .iter()
+                    .map(|&(name, alias)| {
+                        let item = imported.and_then(|source| {
+                            self.gcx.hir.source(source).items.iter().copied().find(|&item| {
+                                self.gcx.hir.item(item).name().is_some_and(|n| n.name == name.name)
+                            })
+                        });
+                        let foreign = self.identifier(name, item.map(hir::Res::Item).as_slice());
+                        json!({
+                            "foreign": foreign,
+                            "local": alias.map(|alias| alias.to_string()),
+                            "nameLocation": alias.map_or_else(|| self.src(Span::DUMMY), |alias| self.src(alias.span)),
+                        })
+                    })
+                    .collect();
+                (symbol_aliases, "")
+            }
+        };
+        let fields = json!({
+            "absolutePath": imported.map(|id| self.gcx.hir.source(id).file.name.display().to_string()),
+            "file": import.path.value.as_str(),
+            "nameLocation": self.src(Span::DUMMY),
+            "scope": self.scope,
+            "sourceUnit": imported.map(|id| self.source_unit_ids[&id]),
+            "symbolAliases": symbol_aliases,
+            "unitAlias": unit_alias,
+        });
+        self.new_node("ImportDirective", span, fields)
+    }
+
+    fn using(&mut self, span: Span, using: &ast::UsingDirective<'_>) -> Value {
+        let mut fields = json!({
+            "global": using.global,
+            "typeName": using.ty.as_ref().map(|ty| self.type_name(ty)),
+        });
+        match &using.list {
+            ast::UsingList::Single(path) => {
+                fields["libraryName"] = self.identifier_path(path, None);
+            }
+            ast::UsingList::Multiple(list) => {
+                let list = list
+                    .iter()
+                    .map(|(path, op)| match op {
+                        Some(op) => json!({
+                            "definition": self.identifier_path(path, None),
+                            "operator": op.to_op().either(ast::UnOpKind::to_str, ast::BinOpKind::to_str),
+                        }),
+                        None => json!({ "function": self.identifier_path(path, None) }),
+                    })
+                    .collect::<Vec<_>>();
+                fields["functionList"] = list.into();
+            }
+        }
+        self.new_node("UsingForDirective", span, fields)
+    }
+
+    fn contract(&mut self, item: &ast::Item<'_>, contract: &ast::ItemContract<'_>) -> Value {
+        let (hir_id, id) = self.decl(item.span);
+        let hir_contract = match hir_id {
+            Some(hir::ItemId::Contract(id)) => Some((id, self.gcx.hir.contract(id))),
+            _ => None,
+        };
+        let scope = std::mem::replace(&mut self.scope, id);
+
+        let base_contracts = contract
+            .bases
+            .iter()
+            .enumerate()
+            .map(|(i, base)| {
+                let res = hir_contract.and_then(|(_, c)| c.bases.get(i)).map(|&b| b.into());
+                let (span, arguments) = self.with_call_args(base.name.span(), &base.arguments);
+                let fields = json!({
+                    "arguments": arguments,
+                    "baseName": self.identifier_path(base.name, res),
+                });
+                self.new_node("InheritanceSpecifier", span, fields)
+            })
+            .collect::<Vec<_>>();
+        let documentation = self.documentation(item.docs);
+        let nodes = contract.body.iter().map(|item| self.item(item)).collect::<Vec<_>>();
+        self.scope = scope;
+
+        let (linearized_base_contracts, used_errors, used_events, fully_implemented) =
+            match hir_contract {
+                Some((hir_id, c)) => {
+                    let mut used_errors = Vec::new();
+                    let mut used_events = Vec::new();
+                    for &item in self.gcx.interface_events_and_errors(hir_id) {
+                        match item {
+                            hir::ItemId::Error(_) => used_errors.push(self.item_ref(item)),
+                            _ => used_events.push(self.item_ref(item)),
+                        }
+                    }
+                    let bases = c.linearized_bases.iter().map(|&b| self.item_ref(b.into()));
+                    (bases.collect(), used_errors, used_events, self.is_fully_implemented(c))
+                }
+                None => (Vec::new(), Vec::new(), Vec::new(), false),
+            };
+
+        let contract_kind = match contract.kind {
+            ast::ContractKind::Contract | ast::ContractKind::AbstractContract => "contract",
+            ast::ContractKind::Interface => "interface",
+            ast::ContractKind::Library => "library",
+        };
+        let fields = json!({
+            "abstract": contract.kind.is_abstract_contract(),
+            "baseContracts": base_contracts,
+            "canonicalName": contract.name.as_str(),
+            "contractDependencies": [],
+            "contractKind": contract_kind,
+            "documentation": documentation,
+            "fullyImplemented": fully_implemented,
+            "linearizedBaseContracts": linearized_base_contracts,
+            "name": contract.name.as_str(),
+            "nameLocation": self.src(contract.name.span),
+            "nodes": nodes,
+            "scope": scope,
+            "usedErrors": used_errors,
+            "usedEvents": used_events,
+        });
+        self.node(id, "ContractDefinition", item.span, fields)
+    }
+
+    /// Returns `true` if all the functions of the given contract and its bases are implemented.
+    fn is_fully_implemented(&self, contract: &hir::Contract<'_>) -> bool {
+        if contract.kind.is_interface() {
+            return false;
+        }
+        let hir = &self.gcx.hir;
+        let mut implemented = Vec::new();
+        for &base in contract.linearized_bases {
+            for f in hir.contract(base).all_functions() {
+                let function = hir.function(f);
+                if function.is_getter() {
+                    continue;
+                }
+                let signature = self.function_signature(f);
+                if function.body.is_some() {
+                    implemented.push(signature);
+                } else if !implemented.contains(&signature) {
+                    return false;
+                }
+            }
+            for v in hir.contract(base).variables() {
+                if let Some(getter) = hir.variable(v).getter {
+                    implemented.push(self.function_signature(getter));
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the kind, name and parameter types of the given function, ignoring data locations.
+    ///
+    /// Constructors, fallback and receive functions are unnamed, so they are matched by kind only.
+    /// Parameter types are compared instead of ABI signatures, since internal functions can have
+    /// parameters that are not ABI-encodable.
+    fn function_signature(
+        &self,
+        id: hir::FunctionId,
+    ) -> (hir::FunctionKind, Option<Symbol>, Vec<Ty<'_>>) {
+        let function = self.gcx.hir.function(id);
+        let params = self.gcx.item_parameter_types(id.into()).iter().map(|ty| ty.peel_refs());
+        (function.kind, function.name.map(|name| name.name), params.collect())
+    }
+
+    fn function(&mut self, item: &ast::Item<'_>, function: &ast::ItemFunction<'_>) -> Value {
+        let header = &function.header;
+        let (hir_id, id) = self.decl(item.span);
+        let hir_function = match hir_id {
+            Some(hir::ItemId::Function(id)) => Some((id, self.gcx.hir.function(id))),
+            _ => None,
+        };
+        let in_contract = hir_function.is_some_and(|(_, f)| f.contract.is_some());
+        let scope = std::mem::replace(&mut self.scope, id);
+
+        // `constructor`, `fallback` and `receive` are followed by the parameters.
+        let name_end = header.name.map_or_else(|| self.token_end(item.span.lo()), |n| n.span.hi());
+        let parameters_span = self.parens_at(name_end);
+        let parameters = self.parameter_list(parameters_span, header.parameters);
+        let body_span = function.body.as_ref().map(|_| self.braces_after(parameters_span.hi()));
+        let returns_end = body_span.map_or(item.span.hi(), Span::lo);
+        let returns_span = match self.find_keyword(parameters_span.hi(), returns_end, kw::Returns) {
+            Some(returns) => self.parens_at(returns.hi()),
+            None => Span::new(parameters_span.hi(), parameters_span.hi()),
+        };
+        let return_parameters = self.parameter_list(returns_span, header.returns);
+        let modifiers = header
+            .modifiers
+            .iter()
+            .enumerate()
+            .map(|(i, modifier)| {
+                let res = hir_function.and_then(|(_, f)| f.modifiers.get(i).copied());
+                self.modifier_invocation(modifier, res)
+            })
+            .collect::<Vec<_>>();
+        let overrides = header.override_.as_ref().map(|o| {
+            self.override_specifier(o, hir_function.map(|(_, f)| f.overrides).unwrap_or_default())
+        });
+        let documentation = self.documentation(item.docs);
+
+        let return_params =
+            std::mem::replace(&mut self.return_params, return_parameters["id"].clone());
+        let body = function.body.as_ref().map(|body| self.block("Block", body_span.unwrap(), body));
+        self.return_params = return_params;
+        self.scope = scope;
+
+        let name = header.name.as_ref().map_or("", |name| name.as_str());
+        let name_location = self.src(header.name.map_or(Span::DUMMY, |name| name.span));
+        if function.kind.is_modifier() {
+            let fields = json!({
+                "body": body,
+                "documentation": documentation,
+                "name": name,
+                "nameLocation": name_location,
+                "overrides": overrides,
+                "parameters": parameters,
+                "virtual": header.virtual_,
+                "visibility": header.visibility.unwrap_or(ast::Visibility::Internal).to_str(),
+            });
+            return self.node(id, "ModifierDefinition", item.span, fields);
+        }
+
+        let visibility = header.visibility.unwrap_or(if in_contract {
+            ast::Visibility::Public
+        } else {
+            ast::Visibility::Internal
+        });
+        let kind = match function.kind {
+            ast::FunctionKind::Function if !in_contract => "freeFunction",
+            kind => kind.to_str(),
+        };
+        let mut fields = json!({
+            "body": body,
+            "documentation": documentation,
+            "implemented": function.body.is_some(),
+            "kind": kind,
+            "modifiers": modifiers,
+            "name": name,
+            "nameLocation": name_location,
+            "overrides": overrides,
+            "parameters": parameters,
+            "returnParameters": return_parameters,
+            "scope": scope,
+            "stateMutability": header.state_mutability.to_str(),
+            "virtual": header.virtual_,
+            "visibility": visibility.to_str(),
+        });
+        if let Some((hir_id, _)) = hir_function {
+            if function.kind.is_function() && in_contract && visibility >= ast::Visibility::Public {
+                fields["functionSelector"] = hex::encode(self.gcx.function_selector(hir_id)).into();
+            }
+        }
+        self.node(id, "FunctionDefinition", item.span, fields)
+    }
+
+    fn modifier_invocation(
+        &mut self,
+        modifier: &ast::Modifier<'_>,
+        res: Option<hir::ItemId>,
+    ) -> Value {
+        let (span, arguments) = self.with_call_args(modifier.name.span(), &modifier.arguments);
+        let kind = if matches!(res, Some(hir::ItemId::Contract(_))) {
+            "baseConstructorSpecifier"
+        } else {
+            "modifierInvocation"
+        };
+        let fields = json!({
+            "arguments": arguments,
+            "kind": kind,
+            "modifierName": self.identifier_path(modifier.name, res),
+        });
+        self.new_node("ModifierInvocation", span, fields)
+    }
+
+    fn override_specifier(
+        &mut self,
+        override_: &ast::Override<'_>,
+        res: &[hir::ContractId],
+    ) -> Value {
+        let overrides = override_
+            .paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| self.identifier_path(path, res.get(i).map(|&c| c.into())))
+            .collect::<Vec<_>>();
+        self.new_node("OverrideSpecifier", override_.span, json!({ "overrides": overrides }))
+    }
+
+    fn parameter_list(&mut self, span: Span, parameters: &[ast::VariableDefinition<'_>]) -> Value {
+        let parameters =
+            parameters.iter().map(|param| self.variable(param, &[], false)).collect::<Vec<_>>();
+        self.new_node("ParameterList", span, json!({ "parameters": parameters }))
+    }
+
+    /// Returns the parameter list of an event or error, whose parameters have an `indexed` field.
+    fn event_parameters(
+        &mut self,
+        id: usize,
+        name: Ident,
+        parameters: &[ast::VariableDefinition<'_>],
+    ) -> Value {
+        let scope = std::mem::replace(&mut self.scope, id);
+        let mut list = self.parameter_list(self.parens_at(name.span.hi()), parameters);
+        self.scope = scope;
+        for (json, param) in list["parameters"].as_array_mut().unwrap().iter_mut().zip(parameters) {
+            json["indexed"] = param.indexed.into();
+        }
+        list
+    }
+
+    /// Returns a `VariableDeclaration`.
+    ///
+    /// The initializer of local variables is part of the enclosing statement instead.
+    fn variable(
+        &mut self,
+        var: &ast::VariableDefinition<'_>,
+        docs: &[ast::DocComment],
+        local: bool,
+    ) -> Value {
+        let (hir_id, id) = self.decl(var.span);
+        let hir_var = match hir_id {
+            Some(hir::ItemId::Variable(id)) => Some(self.gcx.hir.variable(id)),
+            _ => None,
+        };
+        let state = hir_var.is_some_and(|v| v.is_state_variable);
+        let ty = hir_id.map(|id| self.gcx.type_of_item(id));
+        let type_name = self.type_name(&var.ty);
+        let mut fields = json!({
+            "constant": var.mutability.is_some_and(ast::VarMut::is_constant),
+            "mutability": var.mutability.map_or("mutable", ast::VarMut::to_str),
+            "name": var.name.as_ref().map_or("", |name| name.as_str()),
+            "nameLocation": self.src(var.name.map_or(Span::DUMMY, |name| name.span)),
+            "overrides": var.override_.as_ref().map(|o| {
+                self.override_specifier(o, hir_var.map(|v| v.overrides).unwrap_or_default())
+            }),
+            "scope": self.scope,
+            "stateVariable": state,
+            "storageLocation": var.data_location.map_or("default", ast::DataLocation::to_str),
+            // State variable types are not references.
+            "typeDescriptions": match ty {
+                Some(ty) if state => self.type_descriptions(Some(ty.peel_refs()), Loc::Storage),
+                _ => self.type_descriptions(ty, Loc::StoragePtr),
+            },
+            "typeName": type_name,
+            "visibility": var.visibility.unwrap_or(ast::Visibility::Internal).to_str(),
+        });
+        if state && !docs.is_empty() {
+            fields["documentation"] = self.documentation(docs);
+        }
+        if let Some(getter) = hir_var.and_then(|v| v.getter) {
+            fields["functionSelector"] = hex::encode(self.gcx.function_selector(getter)).into();
+        }
+        if let (false, Some(value)) = (local, &var.initializer) {
+            fields["value"] = self.expr(value);
+        }
+        self.node(id, "VariableDeclaration", var.span, fields)
+    }
+
+    fn documentation(&mut self, docs: &[ast::DocComment]) -> Value {
+        let (Some(first), Some(last)) = (docs.first(), docs.last()) else { return Value::Null };
+        let text = docs.iter().map(|doc| doc.symbol.as_str()).collect::<Vec<_>>().join("\n");
+        self.new_node("StructuredDocumentation", first.span.to(last.span), json!({ "text": text }))
+    }
+
+    /// Inserts the documentation into `fields` if there is any.
+    fn insert_documentation(&mut self, fields: &mut Value, docs: &[ast::DocComment]) {
+        if !docs.is_empty() {
+            fields["documentation"] = self.documentation(docs);
+        }
+    }
+
+    // --- Types ---
+
+    fn type_name(&mut self, ty: &ast::Type<'_>) -> Value {
+        let hir_ty = self.res.types.get(&ty.span).copied();
+        let (node_type, mut fields) = match &ty.kind {
+            ast::TypeKind::Elementary(elementary) => {
+                let mut fields = json!({ "name": self.snippet(ty.span) });
+                if let ast::ElementaryType::Address(payable) = elementary {
+                    fields["name"] = "address".into();
+                    fields["stateMutability"] =
+                        if *payable { "payable" } else { "nonpayable" }.into();
+                }
+                ("ElementaryTypeName", fields)
+            }
+            ast::TypeKind::Array(array) => {
+                let fields = json!({
+                    "baseType": self.type_name(&array.element),
+                    "length": array.size.as_ref().map(|size| self.expr(size)),
+                });
+                ("ArrayTypeName", fields)
+            }
+            ast::TypeKind::Function(function) => {
+                // Skip the `function` keyword.
+                let parameters_span = self.parens_at(self.token_end(ty.span.lo()));
+                let returns_span =
+                    match self.find_keyword(parameters_span.hi(), ty.span.hi(), kw::Returns) {
+                        Some(returns) => self.parens_at(returns.hi()),
+                        None => Span::new(ty.span.hi(), ty.span.hi()),
+                    };
+                let fields = json!({
+                    "parameterTypes": self.parameter_list(parameters_span, function.parameters),
+                    "returnParameterTypes": self.parameter_list(returns_span, function.returns),
+                    "stateMutability": function.state_mutability.to_str(),
+                    "visibility": function.visibility.unwrap_or(ast::Visibility::Internal).to_str(),
+                });
+                ("FunctionTypeName", fields)
+            }
+            ast::TypeKind::Mapping(mapping) => {
+                let fields = json!({
+                    "keyName": mapping.key_name.as_ref().map_or("", |name| name.as_str()),
+                    "keyNameLocation": self.src(mapping.key_name.map_or(Span::DUMMY, |name| name.span)),
+                    "keyType": self.type_name(&mapping.key),
+                    "valueName": mapping.value_name.as_ref().map_or("", |name| name.as_str()),
+                    "valueNameLocation": self.src(mapping.value_name.map_or(Span::DUMMY, |name| name.span)),
+                    "valueType": self.type_name(&mapping.value),
+                });
+                ("Mapping", fields)
+            }
+            ast::TypeKind::Custom(path) => {
+                let item = hir_ty.and_then(|ty| match ty.kind {
+                    hir::TypeKind::Custom(item) => Some(item),
+                    _ => None,
+                });
+                let fields = json!({
+                    "pathNode": self.identifier_path(path, item),
+                    "referencedDeclaration": item.map(|item| self.item_ref(item)),
+                });
+                ("UserDefinedTypeName", fields)
+            }
+        };
+        let resolved = hir_ty.map(|ty| self.gcx.type_of_hir_ty(ty));
+        fields["typeDescriptions"] = self.type_descriptions(resolved, Loc::StoragePtr);
+        self.new_node(node_type, ty.span, fields)
+    }
+
+    fn identifier_path(&mut self, path: &ast::PathSlice, res: Option<hir::ItemId>) -> Value {
+        let name_locations =
+            path.segments().iter().map(|segment| self.src(segment.span)).collect::<Vec<_>>();
+        let fields = json!({
+            "name": path.to_string(),
+            "nameLocations": name_locations,
+            "referencedDeclaration": res.map(|item| self.item_ref(item)),
+        });
+        self.new_node("IdentifierPath", path.span(), fields)
+    }
+
+    // --- Statements ---
+
+    fn block(&mut self, node_type: &str, span: Span, stmts: &[ast::Stmt<'_>]) -> Value {
+        let statements = stmts.iter().map(|stmt| self.stmt(stmt)).collect::<Vec<_>>();
+        self.new_node(node_type, span, json!({ "statements": statements }))
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt<'_>) -> Value {
+        let span = stmt.span;
+        match &stmt.kind {
+            ast::StmtKind::Assembly(assembly) => self.inline_assembly(span, assembly),
+            ast::StmtKind::DeclSingle(var) => {
+                let declaration = self.variable(var, &[], true);
+                let fields = json!({
+                    "assignments": [declaration["id"]],
+                    "declarations": [declaration],
+                    "initialValue": var.initializer.as_ref().map(|value| self.expr(value)),
+                });
+                self.new_node("VariableDeclarationStatement", span, fields)
+            }
+            ast::StmtKind::DeclMulti(vars, value) => {
+                let declarations = vars
+                    .iter()
+                    .map(|var| var.as_ref().map(|var| self.variable(var, &[], true)))
+                    .collect::<Vec<_>>();
+                let assignments = declarations
+                    .iter()
+                    .map(|decl| decl.as_ref().map(|decl| decl["id"].clone()))
+                    .collect::<Vec<_>>();
+                let fields = json!({
+                    "assignments": assignments,
+                    "declarations": declarations,
+                    "initialValue": self.expr(value),
+                });
+                self.new_node("VariableDeclarationStatement", span, fields)
+            }
+            ast::StmtKind::Block(stmts) => self.block("Block", span, stmts),
+            ast::StmtKind::Break => self.new_node("Break", span, json!({})),
+            ast::StmtKind::Continue => self.new_node("Continue", span, json!({})),
+            ast::StmtKind::DoWhile(body, condition) => {
+                let fields = json!({ "body": self.stmt(body), "condition": self.expr(condition) });
+                self.new_node("DoWhileStatement", span, fields)
+            }
+            ast::StmtKind::Emit(path, args) => {
+                let event_call = self.path_call(span, path, args);
+                self.new_node("EmitStatement", span, json!({ "eventCall": event_call }))
+            }
+            ast::StmtKind::Expr(expr) => {
+                let fields = json!({ "expression": self.expr(expr) });
+                self.new_node("ExpressionStatement", span, fields)
+            }
+            ast::StmtKind::For { init, cond, next, body } => {
+                let fields = json!({
+                    "body": self.stmt(body),
+                    "condition": cond.as_ref().map(|cond| self.expr(cond)),
+                    "initializationExpression": init.as_ref().map(|init| self.stmt(init)),
+                    "loopExpression": next.as_ref().map(|next| {
+                        let fields = json!({ "expression": self.expr(next) });
+                        self.new_node("ExpressionStatement", next.span, fields)
+                    }),
+                });
+                self.new_node("ForStatement", span, fields)
+            }
+            ast::StmtKind::If(condition, true_body, false_body) => {
+                let fields = json!({
+                    "condition": self.expr(condition),
+                    "falseBody": false_body.as_ref().map(|body| self.stmt(body)),
+                    "trueBody": self.stmt(true_body),
+                });
+                self.new_node("IfStatement", span, fields)
+            }
+            ast::StmtKind::Return(expr) => {
+                let fields = json!({
+                    "expression": expr.as_ref().map(|expr| self.expr(expr)),
+                    "functionReturnParameters": self.return_params,
+                });
+                self.new_node("Return", span, fields)
+            }
+            ast::StmtKind::Revert(path, args) => {
+                let error_call = self.path_call(span, path, args);
+                self.new_node("RevertStatement", span, json!({ "errorCall": error_call }))
+            }
+            ast::StmtKind::Try(try_) => self.try_stmt(span, try_),
+            ast::StmtKind::UncheckedBlock(stmts) => self.block("UncheckedBlock", span, stmts),
+            ast::StmtKind::While(condition, body) => {
+                let fields = json!({ "body": self.stmt(body), "condition": self.expr(condition) });
+                self.new_node("WhileStatement", span, fields)
+            }
+            ast::StmtKind::Placeholder => self.new_node("PlaceholderStatement", span, json!({})),
+        }
+    }
+
+    /// Returns the `FunctionCall` of an `emit` or `revert` statement.
+    fn path_call(
+        &mut self,
+        stmt_span: Span,
+        path: &ast::PathSlice,
+        args: &ast::CallArgs<'_>,
+    ) -> Value {
+        let res = self.res.stmts.get(&stmt_span).copied().unwrap_or_default();
+        let callee = self.path_expr(path, res);
+        let span = path.span().to(self.parens_at(path.span().hi()));
+        self.call(span, callee, "functionCall", args)
+    }
+
+    fn try_stmt(&mut self, span: Span, try_: &ast::StmtTry<'_>) -> Value {
+        let mut external_call = self.expr(try_.expr);
+        if let Some(try_call) = external_call.get_mut("tryCall") {
+            *try_call = true.into();
+        }
+
+        let block_span = self.braces_after(try_.expr.span.hi());
+        let returns = self.find_keyword(try_.expr.span.hi(), block_span.lo(), kw::Returns);
+        let parameters =
+            returns.map(|returns| self.parameter_list(self.parens_at(returns.hi()), try_.returns));
+        let fields = json!({
+            "block": self.block("Block", block_span, try_.block),
+            "errorName": "",
+            "parameters": parameters,
+        });
+        let clause_span = returns.unwrap_or(block_span).to(block_span);
+        let mut clauses = vec![self.new_node("TryCatchClause", clause_span, fields)];
+
+        let mut pos = block_span.hi();
+        for catch in try_.catch.iter() {
+            let catch_span = self.find_keyword(pos, span.hi(), kw::Catch).unwrap_or(span);
+            let params_start = catch.name.map_or(catch_span.hi(), |name| name.span.hi());
+            let block_span = self.braces_after(params_start);
+            let parameters = (!catch.args.is_empty())
+                .then(|| self.parameter_list(self.parens_at(params_start), catch.args));
+            let fields = json!({
+                "block": self.block("Block", block_span, catch.block),
+                "errorName": catch.name.as_ref().map_or("", |name| name.as_str()),
+                "parameters": parameters,
+            });
+            clauses.push(self.new_node("TryCatchClause", catch_span.to(block_span), fields));
+            pos = block_span.hi();
+        }
+
+        let fields = json!({ "clauses": clauses, "externalCall": external_call });
+        self.new_node("TryStatement", span, fields)
+    }
+
+    // --- Expressions ---
+
+    fn expr(&mut self, expr: &ast::Expr<'_>) -> Value {
+        let span = expr.span;
+        let (node_type, fields) = match &expr.kind {
+            ast::ExprKind::Array(exprs) => {
+                let components = exprs.iter().map(|expr| self.expr(expr)).collect::<Vec<_>>();
+                ("TupleExpression", json!({ "components": components, "isInlineArray": true }))
+            }
+            ast::ExprKind::Assign(lhs, op, rhs) => {
+                let operator = op.map_or_else(|| "=".into(), |op| format!("{}=", op.kind.to_str()));
+                let fields = json!({
+                    "leftHandSide": self.expr(lhs),
+                    "operator": operator,
+                    "rightHandSide": self.expr(rhs),
+                });
+                ("Assignment", fields)
+            }
+            ast::ExprKind::Binary(lhs, op, rhs) => {
+                let fields = json!({
+                    "leftExpression": self.expr(lhs),
+                    "operator": op.kind.to_str(),
+                    "rightExpression": self.expr(rhs),
+                });
+                ("BinaryOperation", fields)
+            }
+            ast::ExprKind::Call(callee, args) => {
+                let kind = match &callee.kind {
+                    ast::ExprKind::Type(_) => "typeConversion",
+                    ast::ExprKind::Ident(_) => match self.res.idents.get(&callee.span) {
+                        Some([hir::Res::Item(hir::ItemId::Struct(_))]) => "structConstructorCall",
+                        Some(
+                            [hir::Res::Item(
+                                hir::ItemId::Contract(_)
+                                | hir::ItemId::Enum(_)
+                                | hir::ItemId::Udvt(_),
+                            )],
+                        ) => "typeConversion",
+                        _ => "functionCall",
+                    },
+                    _ => "functionCall",
+                };
+                let callee = self.expr(callee);
+                return self.call(span, callee, kind, args);
+            }
+            ast::ExprKind::CallOptions(callee, options) => {
+                let fields = json!({
+                    "expression": self.expr(callee),
+                    "names": options.iter().map(|option| option.name.as_str()).collect::<Vec<_>>(),
+                    "options": options.iter().map(|option| self.expr(option.value)).collect::<Vec<_>>(),
+                });
+                ("FunctionCallOptions", fields)
+            }
+            ast::ExprKind::Delete(expr) => {
+                let fields = json!({
+                    "operator": "delete",
+                    "prefix": true,
+                    "subExpression": self.expr(expr),
+                });
+                ("UnaryOperation", fields)
+            }
+            ast::ExprKind::Ident(ident) => {
+                let res = self.res.idents.get(&span).copied().unwrap_or_default();
+                return self.identifier(*ident, res);
+            }
+            ast::ExprKind::Index(base, ast::IndexKind::Index(index)) => {
+                let fields = json!({
+                    "baseExpression": self.expr(base),
+                    "indexExpression": index.as_ref().map(|index| self.expr(index)),
+                });
+                ("IndexAccess", fields)
+            }
+            ast::ExprKind::Index(base, ast::IndexKind::Range(start, end)) => {
+                let fields = json!({
+                    "baseExpression": self.expr(base),
+                    "endExpression": end.as_ref().map(|end| self.expr(end)),
+                    "startExpression": start.as_ref().map(|start| self.expr(start)),
+                });
+                ("IndexRangeAccess", fields)
+            }
+            ast::ExprKind::Lit(lit, sub_denomination) => {
+                return self.literal(span, lit, *sub_denomination);
+            }
+            ast::ExprKind::Member(base, member) => {
+                // Paths such as `Library.function` may have been resolved during lowering.
+                let res = self.res.idents.get(&span).copied().unwrap_or_default();
+                let referenced_declaration = match res {
+                    [hir::Res::Item(item)] => self.item_ref(*item),
+                    _ => Value::Null,
+                };
+                let fields = json!({
+                    "expression": self.expr(base),
+                    "memberLocation": self.src(member.span),
+                    "memberName": member.as_str(),
+                    "referencedDeclaration": referenced_declaration,
+                });
+                ("MemberAccess", fields)
+            }
+            ast::ExprKind::New(ty) => ("NewExpression", json!({ "typeName": self.type_name(ty) })),
+            ast::ExprKind::Payable(args) => {
+                let keyword = Span::new(span.lo(), self.token_end(span.lo()));
+                let type_name = json!({ "name": "address", "stateMutability": "payable" });
+                let type_name = self.typed_node("ElementaryTypeName", keyword, type_name);
+                let callee = self.typed_node(
+                    "ElementaryTypeNameExpression",
+                    keyword,
+                    json!({ "typeName": type_name }),
+                );
+                return self.call(span, callee, "typeConversion", args);
+            }
+            ast::ExprKind::Ternary(condition, true_expr, false_expr) => {
+                let fields = json!({
+                    "condition": self.expr(condition),
+                    "falseExpression": self.expr(false_expr),
+                    "trueExpression": self.expr(true_expr),
+                });
+                ("Conditional", fields)
+            }
+            ast::ExprKind::Tuple(exprs) => {
+                let components = exprs
+                    .iter()
+                    .map(|expr| expr.as_ref().map(|expr| self.expr(expr)))
+                    .collect::<Vec<_>>();
+                ("TupleExpression", json!({ "components": components, "isInlineArray": false }))
+            }
+            ast::ExprKind::TypeCall(ty) => {
+                let keyword = Span::new(span.lo(), self.token_end(span.lo()));
+                let callee = self.identifier(Ident::new(kw::Type, keyword), &[]);
+                let fields = json!({
+                    "arguments": [self.type_expr(ty)],
+                    "expression": callee,
+                    "kind": "functionCall",
+                    "nameLocations": [],
+                    "names": [],
+                    "tryCall": false,
+                });
+                ("FunctionCall", fields)
+            }
+            ast::ExprKind::Type(ty) => return self.type_expr(ty),
+            ast::ExprKind::Unary(op, expr) => {
+                let fields = json!({
+                    "operator": op.kind.to_str(),
+                    "prefix": op.kind.is_prefix(),
+                    "subExpression": self.expr(expr),
+                });
+                ("UnaryOperation", fields)
+            }
+        };
+        self.typed_node(node_type, span, fields)
+    }
+
+    fn call(&mut self, span: Span, callee: Value, kind: &str, args: &ast::CallArgs<'_>) -> Value {
+        let (names, name_locations, arguments) = match args {
+            ast::CallArgs::Unnamed(args) => {
+                (Vec::new(), Vec::new(), args.iter().map(|arg| self.expr(arg)).collect())
+            }
+            ast::CallArgs::Named(args) => (
+                args.iter().map(|arg| arg.name.as_str()).collect(),
+                args.iter().map(|arg| self.src(arg.name.span)).collect(),
+                args.iter().map(|arg| self.expr(arg.value)).collect::<Vec<_>>(),
+            ),
+        };
+        let fields = json!({
+            "arguments": arguments,
+            "expression": callee,
+            "kind": kind,
+            "nameLocations": name_locations,
+            "names": names,
+            "tryCall": false,
+        });
+        self.typed_node("FunctionCall", span, fields)
+    }
+
+    /// Returns the span of the given callee extended to the closing parenthesis and the
+    /// arguments, or `null` if there are no parentheses.
+    fn with_call_args(&mut self, span: Span, args: &ast::CallArgs<'_>) -> (Span, Value) {
+        let parens = self.parens_at(span.hi());
+        if parens.lo() == parens.hi() {
+            return (span, Value::Null);
+        }
+        let arguments = match args {
+            ast::CallArgs::Unnamed(args) => args.iter().map(|arg| self.expr(arg)).collect(),
+            ast::CallArgs::Named(args) => args.iter().map(|arg| self.expr(arg.value)).collect(),
+        };
+        (span.to(parens), Value::Array(arguments))
+    }
+
+    /// Returns an `Identifier` referring to the given resolutions.
+    fn identifier(&mut self, ident: Ident, res: &[hir::Res]) -> Value {
+        let items = res
+            .iter()
+            .filter_map(|res| match *res {
+                // Getters are not part of the AST.
+                hir::Res::Item(hir::ItemId::Function(f))
+                    if self.gcx.hir.function(f).is_getter() =>
+                {
+                    None
+                }
+                hir::Res::Item(item) => Some(item),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let (referenced_declaration, overloaded_declarations, ty) = match items[..] {
+            [item] => (self.item_ref(item), Vec::new(), self.type_of_item(item)),
+            _ => (Value::Null, items.iter().map(|&item| self.item_ref(item)).collect(), None),
+        };
+        let fields = json!({
+            "name": ident.as_str(),
+            "overloadedDeclarations": overloaded_declarations,
+            "referencedDeclaration": referenced_declaration,
+            "typeDescriptions": self.type_descriptions(ty, Loc::Storage),
+        });
+        self.new_node("Identifier", ident.span, fields)
+    }
+
+    /// Returns a path in expression position, such as the event of an `emit` statement, as an
+    /// `Identifier` followed by `MemberAccess`es.
+    fn path_expr(&mut self, path: &ast::PathSlice, res: &[hir::Res]) -> Value {
+        let segments = path.segments();
+        let first_res = if segments.len() == 1 { res } else { &[] };
+        let mut expr = self.identifier(segments[0], first_res);
+        for (i, segment) in segments.iter().enumerate().skip(1) {
+            let referenced_declaration = match res {
+                [hir::Res::Item(item)] if i == segments.len() - 1 => self.item_ref(*item),
+                _ => Value::Null,
+            };
+            let fields = json!({
+                "expression": expr,
+                "memberLocation": self.src(segment.span),
+                "memberName": segment.as_str(),
+                "referencedDeclaration": referenced_declaration,
+            });
+            expr = self.typed_node("MemberAccess", segments[0].span.to(segment.span), fields);
+        }
+        expr
+    }
+
+    /// Returns a type in expression position, such as the argument of `type(...)`.
+    fn type_expr(&mut self, ty: &ast::Type<'_>) -> Value {
+        match &ty.kind {
+            ast::TypeKind::Custom(path) => {
+                let res = match self.res.types.get(&ty.span).map(|ty| &ty.kind) {
+                    Some(&hir::TypeKind::Custom(item)) => vec![hir::Res::Item(item)],
+                    _ => Vec::new(),
+                };
+                self.path_expr(path, &res)
+            }
+            _ => {
+                let fields = json!({ "typeName": self.type_name(ty) });
+                self.typed_node("ElementaryTypeNameExpression", ty.span, fields)
+            }
+        }
+    }
+
+    fn literal(
+        &mut self,
+        span: Span,
+        lit: &ast::Lit,
+        sub_denomination: Option<ast::SubDenomination>,
+    ) -> Value {
+        let mut value = Value::from(lit.symbol.as_str());
+        let mut hex_value = hex::encode(lit.symbol.as_str());
+        let (kind, type_identifier, type_string) = match &lit.kind {
+            ast::LitKind::Str(kind, bytes) => {
+                let kind = match kind {
+                    ast::StrKind::Str => "string",
+                    ast::StrKind::Unicode => "unicodeString",
+                    ast::StrKind::Hex => "hexString",
+                };
+                hex_value = hex::encode(bytes);
+                value = std::str::from_utf8(bytes).map_or(Value::Null, Value::from);
+                let type_string = match &value {
+                    Value::String(s) => format!("literal_string {s:?}"),
+                    _ => format!("literal_string hex\"{hex_value}\""),
+                };
+                let type_identifier = format!("t_stringliteral_{}", hex::encode(keccak256(bytes)));
+                (kind, Some(type_identifier), Some(type_string))
+            }
+            ast::LitKind::Number(n) if sub_denomination.is_none() => {
+                ("number", Some(format!("t_rational_{n}_by_1")), Some(format!("int_const {n}")))
+            }
+            ast::LitKind::Address(_) => {
+                ("number", Some("t_address".into()), Some("address".into()))
+            }
+            ast::LitKind::Bool(_) => ("bool", Some("t_bool".into()), Some("bool".into())),
+            ast::LitKind::Number(_) | ast::LitKind::Rational(_) | ast::LitKind::Err(_) => {
+                ("number", None, None)
+            }
+        };
+        let fields = json!({
+            "hexValue": hex_value,
+            "kind": kind,
+            "subdenomination": sub_denomination.map(|sub| sub.to_str()),
+            "typeDescriptions": {
+                "typeIdentifier": type_identifier.map(|id| escape_type_identifier(&id)),
+                "typeString": type_string,
+            },
+            "value": value,
+        });
+        self.new_node("Literal", span, fields)
+    }
+
+    // --- Inline assembly ---
+
+    fn inline_assembly(&mut self, span: Span, assembly: &ast::StmtAssembly<'_>) -> Value {
+        let block_span = self.braces_after(span.lo());
+        let mut fields = json!({
+            "AST": self.yul_block(block_span, assembly.block),
+            "evmVersion": self.gcx.sess.evm_version.to_string(),
+            "externalReferences": [],
+        });
+        if !assembly.flags.is_empty() {
+            let flags = assembly.flags.iter().map(|flag| flag.value.as_str()).collect::<Vec<_>>();
+            fields["flags"] = flags.into();
+        }
+        self.new_node("InlineAssembly", span, fields)
+    }
+
+    fn yul_block(&mut self, span: Span, stmts: &[yul::Stmt<'_>]) -> Value {
+        let statements = stmts.iter().map(|stmt| self.yul_stmt(stmt)).collect::<Vec<_>>();
+        self.yul_node("YulBlock", span, json!({ "statements": statements }))
+    }
+
+    fn yul_stmt(&mut self, stmt: &yul::Stmt<'_>) -> Value {
+        let span = stmt.span;
+        match &stmt.kind {
+            yul::StmtKind::Block(stmts) => self.yul_block(span, stmts),
+            yul::StmtKind::AssignSingle(path, value) => {
+                let fields = json!({
+                    "value": self.yul_expr(value),
+                    "variableNames": [self.yul_identifier(path)],
+                });
+                self.yul_node("YulAssignment", span, fields)
+            }
+            yul::StmtKind::AssignMulti(paths, call) => {
+                let fields = json!({
+                    "value": self.yul_call(call),
+                    "variableNames": paths.iter().map(|path| self.yul_identifier(path)).collect::<Vec<_>>(),
+                });
+                self.yul_node("YulAssignment", span, fields)
+            }
+            yul::StmtKind::Expr(call) => {
+                let fields = json!({ "expression": self.yul_call(call) });
+                self.yul_node("YulExpressionStatement", span, fields)
+            }
+            yul::StmtKind::If(condition, body) => {
+                let body_span = self.braces_after(condition.span.hi());
+                let fields = json!({
+                    "body": self.yul_block(body_span, body),
+                    "condition": self.yul_expr(condition),
+                });
+                self.yul_node("YulIf", span, fields)
+            }
+            yul::StmtKind::For { init, cond, step, body } => {
+                let init_span = self.braces_after(span.lo());
+                let step_span = self.braces_after(cond.span.hi());
+                let body_span = self.braces_after(step_span.hi());
+                let fields = json!({
+                    "body": self.yul_block(body_span, body),
+                    "condition": self.yul_expr(cond),
+                    "post": self.yul_block(step_span, step),
+                    "pre": self.yul_block(init_span, init),
+                });
+                self.yul_node("YulForLoop", span, fields)
+            }
+            yul::StmtKind::Switch(switch) => {
+                let mut pos = switch.selector.span.hi();
+                let mut cases = Vec::new();
+                for case in switch.branches.iter() {
+                    let case_span = self.token_before(case.constant.span.lo());
+                    let body_span = self.braces_after(case.constant.span.hi());
+                    let fields = json!({
+                        "body": self.yul_block(body_span, case.body),
+                        "value": self.yul_literal(case.constant),
+                    });
+                    cases.push(self.yul_node("YulCase", case_span.to(body_span), fields));
+                    pos = body_span.hi();
+                }
+                if let Some(body) = &switch.default_case {
+                    let body_span = self.braces_after(pos);
+                    let case_span = self.token_before(body_span.lo());
+                    let fields =
+                        json!({ "body": self.yul_block(body_span, body), "value": "default" });
+                    cases.push(self.yul_node("YulCase", case_span.to(body_span), fields));
+                }
+                let fields =
+                    json!({ "cases": cases, "expression": self.yul_expr(&switch.selector) });
+                self.yul_node("YulSwitch", span, fields)
+            }
+            yul::StmtKind::Leave => self.yul_node("YulLeave", span, json!({})),
+            yul::StmtKind::Break => self.yul_node("YulBreak", span, json!({})),
+            yul::StmtKind::Continue => self.yul_node("YulContinue", span, json!({})),
+            yul::StmtKind::FunctionDef(function) => {
+                let body_span = self.braces_after(function.name.span.hi());
+                let mut fields = json!({
+                    "body": self.yul_block(body_span, function.body),
+                    "name": function.name.as_str(),
+                });
+                if !function.parameters.is_empty() {
+                    fields["parameters"] = self.yul_typed_names(function.parameters);
+                }
+                if !function.returns.is_empty() {
+                    fields["returnVariables"] = self.yul_typed_names(function.returns);
+                }
+                self.yul_node("YulFunctionDefinition", span, fields)
+            }
+            yul::StmtKind::VarDecl(names, value) => {
+                let fields = json!({
+                    "value": value.as_ref().map(|value| self.yul_expr(value)),
+                    "variables": self.yul_typed_names(names),
+                });
+                self.yul_node("YulVariableDeclaration", span, fields)
+            }
+        }
+    }
+
+    fn yul_typed_names(&mut self, names: &[Ident]) -> Value {
+        names
+            .iter()
+            .map(|name| {
+                self.yul_node(
+                    "YulTypedName",
+                    name.span,
+                    json!({ "name": name.as_str(), "type": "" }),
+                )
+            })
+            .collect()
+    }
+
+    fn yul_expr(&mut self, expr: &yul::Expr<'_>) -> Value {
+        match &expr.kind {
+            yul::ExprKind::Path(path) => self.yul_identifier(path),
+            yul::ExprKind::Call(call) => self.yul_call(call),
+            yul::ExprKind::Lit(lit) => self.yul_literal(lit),
+        }
+    }
+
+    fn yul_call(&mut self, call: &yul::ExprCall<'_>) -> Value {
+        let span = call.name.span.to(self.parens_at(call.name.span.hi()));
+        let function_name =
+            self.yul_node("YulIdentifier", call.name.span, json!({ "name": call.name.as_str() }));
+        let fields = json!({
+            "arguments": call.arguments.iter().map(|arg| self.yul_expr(arg)).collect::<Vec<_>>(),
+            "functionName": function_name,
+        });
+        self.yul_node("YulFunctionCall", span, fields)
+    }
+
+    fn yul_identifier(&mut self, path: &ast::PathSlice) -> Value {
+        self.yul_node("YulIdentifier", path.span(), json!({ "name": path.to_string() }))
+    }
+
+    fn yul_literal(&mut self, lit: &ast::Lit) -> Value {
+        let mut fields = match &lit.kind {
+            ast::LitKind::Str(_, bytes) => json!({
+                "hexValue": hex::encode(bytes),
+                "kind": "string",
+                "value": std::str::from_utf8(bytes).ok(),
+            }),
+            ast::LitKind::Bool(_) => json!({ "kind": "bool", "value": lit.symbol.as_str() }),
+            _ => json!({ "kind": "number", "value": lit.symbol.as_str() }),
+        };
+        fields["type"] = "".into();
+        self.yul_node("YulLiteral", lit.span, fields)
+    }
+
+    // --- Helpers ---
+
+    fn next_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Returns the HIR item and the node ID of the declaration defined at `span`, or a new node ID
+    /// if there is none.
+    fn decl(&mut self, span: Span) -> (Option<hir::ItemId>, usize) {
+        match self.decls.get(&span) {
+            Some(&(item, id)) => (Some(item), id),
+            None => (None, self.next_id()),
+        }
+    }
+
+    /// Returns the node ID of the given declaration.
+    fn item_ref(&self, item: hir::ItemId) -> Value {
+        self.item_ids.get(&item).copied().into()
+    }
+
+    fn canonical_name(&self, item: Option<hir::ItemId>, name: Ident) -> String {
+        match item {
+            Some(item) => self.gcx.item_canonical_name(item).to_string(),
+            None => name.to_string(),
+        }
+    }
+
+    /// Returns the type of the given declaration in expression position.
+    fn type_of_item(&self, item: hir::ItemId) -> Option<Ty<'gcx>> {
+        match item {
+            hir::ItemId::Variable(_) | hir::ItemId::Function(_) => {
+                Some(self.gcx.type_of_item(item))
+            }
+            hir::ItemId::Contract(_)
+            | hir::ItemId::Struct(_)
+            | hir::ItemId::Enum(_)
+            | hir::ItemId::Udvt(_) => {
+                Some(self.gcx.mk_ty(TyKind::Type(self.gcx.type_of_item(item))))
+            }
+            hir::ItemId::Error(_) | hir::ItemId::Event(_) => None,
+        }
+    }
+
+    /// Returns the `typeDescriptions` of the given type.
+    ///
+    /// `storage` is used for references to storage, which are pointers unless they refer to a
+    /// state variable.
+    fn type_descriptions(&self, ty: Option<Ty<'gcx>>, storage: Loc) -> Value {
+        let Some(ty) = ty else {
+            return json!({ "typeIdentifier": null, "typeString": null });
+        };
+        let has_location =
+            !ty.peel_refs().is_value_type() && !matches!(ty.peel_refs().kind, TyKind::Mapping(..));
+        let (loc, suffix) = match ty.kind {
+            _ if !has_location => (storage, ""),
+            TyKind::Ref(_, ast::DataLocation::Memory) => (Loc::MemoryPtr, " memory"),
+            TyKind::Ref(_, ast::DataLocation::Calldata) => (Loc::CalldataPtr, " calldata"),
+            TyKind::Ref(..) => match storage {
+                Loc::Storage => (Loc::Storage, " storage ref"),
+                _ => (Loc::StoragePtr, " storage pointer"),
+            },
+            _ => (storage, ""),
+        };
+        let names = TypeNames { gcx: self.gcx, ast_ids: Some(&self.item_ids) };
+        json!({
+            "typeIdentifier": escape_type_identifier(&names.identifier(ty, loc)),
+            "typeString": names.label(ty) + suffix,
+        })
+    }
+
+    /// Returns a node with the given ID.
+    fn node(&self, id: usize, node_type: &str, span: Span, mut fields: Value) -> Value {
+        let map = fields.as_object_mut().unwrap();
+        map.insert("id".into(), id.into());
+        map.insert("nodeType".into(), node_type.into());
+        map.insert("src".into(), self.src(span).into());
+        fields
+    }
+
+    /// Returns a node with a new ID.
+    fn new_node(&mut self, node_type: &str, span: Span, fields: Value) -> Value {
+        let id = self.next_id();
+        self.node(id, node_type, span, fields)
+    }
+
+    /// Returns an expression node with unknown type descriptions, unless they are already set.
+    fn typed_node(&mut self, node_type: &str, span: Span, mut fields: Value) -> Value {
+        if fields.get("typeDescriptions").is_none() {
+            fields["typeDescriptions"] = self.type_descriptions(None, Loc::Storage);
+        }
+        self.new_node(node_type, span, fields)
+    }
+
+    /// Returns a Yul node, which has no ID.
+    fn yul_node(&self, node_type: &str, span: Span, mut fields: Value) -> Value {
+        let map = fields.as_object_mut().unwrap();
+        map.insert("nodeType".into(), node_type.into());
+        map.insert("src".into(), self.src(span).into());
+        fields
+    }
+
+    /// Returns the `start:length:fileIndex` location of the given span in the current source.
+    fn src(&self, span: Span) -> String {
+        if span.is_dummy() {
+            return "-1:-1:-1".into();
+        }
+        let start = span.lo().to_usize() - self.file.start_pos.to_usize();
+        let len = span.hi().to_usize() - span.lo().to_usize();
+        format!("{start}:{len}:{}", self.file_index)
+    }
+
+    fn snippet(&self, span: Span) -> &str {
+        let start = span.lo().to_usize() - self.file.start_pos.to_usize();
+        let end = span.hi().to_usize() - self.file.start_pos.to_usize();
+        &self.file.src[start..end]
+    }
+
+    /// Returns the tokens within the given span.
+    fn tokens_in(&self, span: Span) -> &[Token] {
+        let start = self.token_index(span.lo());
+        let end = self.token_index(span.hi());
+        &self.tokens[start..end]
+    }
+
+    /// Returns the index of the first token at or after `pos`.
+    fn token_index(&self, pos: BytePos) -> usize {
+        self.tokens.partition_point(|token| token.span.lo() < pos)
+    }
+
+    /// Returns the end of the first token at or after `pos`.
+    fn token_end(&self, pos: BytePos) -> BytePos {
+        self.tokens.get(self.token_index(pos)).map_or(pos, |token| token.span.hi())
+    }
+
+    /// Returns the span of the last token before `pos`.
+    fn token_before(&self, pos: BytePos) -> Span {
+        let index = self.token_index(pos);
+        if index == 0 {
+            return Span::new(pos, pos);
+        }
+        self.tokens[index - 1].span
+    }
+
+    /// Returns the span of the first `kw` keyword between `lo` and `hi`.
+    fn find_keyword(&self, lo: BytePos, hi: BytePos, kw: Symbol) -> Option<Span> {
+        self.tokens[self.token_index(lo)..]
+            .iter()
+            .take_while(|token| token.span.hi() <= hi)
+            .find(|token| token.is_keyword(kw))
+            .map(|token| token.span)
+    }
+
+    /// Returns the span of the parentheses that start at `pos`, or an empty span at `pos` if the
+    /// next token is not an opening parenthesis.
+    fn parens_at(&self, pos: BytePos) -> Span {
+        let index = self.token_index(pos);
+        match self.tokens.get(index) {
+            Some(token) if token.is_open_delim(Delimiter::Parenthesis) => {
+                self.delimited(index, Delimiter::Parenthesis)
+            }
+            _ => Span::new(pos, pos),
+        }
+    }
+
+    /// Returns the span of the first braces after `pos`.
+    fn braces_after(&self, pos: BytePos) -> Span {
+        let start = self.token_index(pos);
+        match self.tokens[start..].iter().position(|t| t.is_open_delim(Delimiter::Brace)) {
+            Some(offset) => self.delimited(start + offset, Delimiter::Brace),
+            None => Span::new(pos, pos),
+        }
+    }
+
+    /// Returns the span from the opening delimiter at `index` to its closing delimiter.
+    fn delimited(&self, index: usize, delim: Delimiter) -> Span {
+        let open = self.tokens[index].span;
+        let mut depth = 0usize;
+        for token in &self.tokens[index..] {
+            if token.is_open_delim(delim) {
+                depth += 1;
+            } else if token.is_close_delim(delim) {
+                depth -= 1;
+                if depth == 0 {
+                    return open.to(token.span);
+                }
+            }
+        }
+        open
+    }
+}
+
+/// Escapes a type identifier the way solc does in the AST, e.g. `t_mapping(t_address,t_bool)`
+/// becomes `t_mapping$_t_address_$_t_bool_$`.
+fn escape_type_identifier(identifier: &str) -> String {
+    identifier.replace('$', "$$$").replace('(', "$_").replace(')', "_$").replace(',', "_$_")
+}
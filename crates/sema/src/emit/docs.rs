@@ -0,0 +1,327 @@
+//! Markdown documentation, in the style of `forge doc`.
+//!
+//! Generates one page per contract, listing its inheritance chain, state variables, functions,
+//! events and errors along with their NatSpec documentation.
+
+use crate::{
+    hir::{self, NatSpecItem, NatSpecKind},
+    ty::Gcx,
+};
+use alloy_primitives::hex;
+use rayon::prelude::*;
+use solar_ast::ast::{ContractKind, FunctionKind, StateMutability};
+use solar_interface::Span;
+use std::{fmt::Write, path::PathBuf};
+
+/// Writes the documentation of all contracts to `<out_dir>/docs`, or to stdout if no output
+/// directory is specified.
+pub(crate) fn emit(gcx: Gcx<'_>) {
+    let pages = gcx
+        .hir
+        .par_contract_ids()
+        .map(|id| (contract_page_path(gcx, id), contract_docs(gcx, id)))
+        .collect::<Vec<_>>();
+
+    let Some(out_dir) = gcx.sess.out_dir.as_deref() else {
+        for (_, page) in &pages {
+            println!("{page}");
+        }
+        return;
+    };
+    let docs_dir = out_dir.join("docs");
+    pages.par_iter().for_each(|(path, page)| {
+        let path = docs_dir.join(path);
+        let r = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, page));
+        if let Err(e) = r {
+            let msg = format!("failed to write documentation to {}: {e}", path.display());
+            gcx.dcx().err(msg).emit();
+        }
+    });
+}
+
+/// Returns the path of the documentation page of the given contract, relative to the
+/// documentation root: `<source path>/<kind>.<name>.md`.
+pub(crate) fn contract_page_path(gcx: Gcx<'_>, id: hir::ContractId) -> PathBuf {
+    let c = gcx.hir.contract(id);
+    let mut path = super::source_relative_path(gcx, c.source);
+    let kind = match c.kind {
+        ContractKind::AbstractContract => "abstract",
+        kind => kind.to_str(),
+    };
+    path.push(format!("{kind}.{}.md", c.name));
+    path
+}
+
+/// Returns the Markdown documentation page of the given contract.
+pub(crate) fn contract_docs(gcx: Gcx<'_>, id: hir::ContractId) -> String {
+    let mut page = Page { gcx, out: String::new() };
+    page.contract(id);
+    page.out
+}
+
+struct Page<'gcx> {
+    gcx: Gcx<'gcx>,
+    out: String,
+}
+
+impl<'gcx> Page<'gcx> {
+    fn contract(&mut self, id: hir::ContractId) {
+        let gcx = self.gcx;
+        let c = gcx.hir.contract(id);
+        writeln!(self.out, "# {}", c.name).unwrap();
+
+        if let Some(bases) = c.linearized_bases.get(1..).filter(|bases| !bases.is_empty()) {
+            let links = bases
+                .iter()
+                .map(|&base| format!("[{}]({})", gcx.item_name(base), link(gcx, base)))
+                .collect::<Vec<_>>();
+            writeln!(self.out, "\n**Inherits:**\n{}", links.join(", ")).unwrap();
+        }
+        if c.kind.is_interface() {
+            let interface_id = gcx.interface_id(id);
+            writeln!(self.out, "\n**Interface ID:** `{interface_id}`").unwrap();
+        }
+
+        let natspec = gcx.hir.natspec(id);
+        if let Some(title) = joined(natspec, NatSpecKind::Title) {
+            writeln!(self.out, "\n**Title:**\n{title}").unwrap();
+        }
+        if let Some(author) = joined(natspec, NatSpecKind::Author) {
+            writeln!(self.out, "\n**Author:**\n{author}").unwrap();
+        }
+        self.notice_and_details(natspec);
+
+        self.state_variables(c);
+        self.functions(c);
+        self.events_and_errors(c);
+    }
+
+    fn state_variables(&mut self, c: &hir::Contract<'gcx>) {
+        let mut vars = c.variables().peekable();
+        if vars.peek().is_none() {
+            return;
+        }
+        self.out.push_str("\n\n## State Variables\n");
+        for (i, id) in vars.enumerate() {
+            let var = self.gcx.hir.variable(id);
+            self.item_heading(i, self.gcx.item_name(id).as_str());
+            let natspec = self.gcx.item_natspec(id.into());
+            self.notice_and_details(&natspec);
+            let declaration = format!("{};", self.snippet(var.span).trim_end_matches(';'));
+            self.code_block(&declaration);
+            if let Some(getter) = var.getter {
+                self.selector(getter.into());
+            }
+        }
+    }
+
+    fn functions(&mut self, c: &hir::Contract<'gcx>) {
+        let functions = c
+            .functions()
+            .filter(|&f| {
+                let f = self.gcx.hir.function(f);
+                !f.is_getter() && !f.kind.is_modifier()
+            })
+            .collect::<Vec<_>>();
+        if functions.is_empty() {
+            return;
+        }
+        self.out.push_str("\n\n## Functions\n");
+        for (i, id) in functions.into_iter().enumerate() {
+            let f = self.gcx.hir.function(id);
+            let name = f.name.map_or_else(|| f.kind.to_string(), |name| name.to_string());
+            self.item_heading(i, &name);
+            let natspec = self.gcx.item_natspec(id.into());
+            self.notice_and_details(&natspec);
+            self.code_block(&self.function_declaration(f));
+            if f.is_part_of_external_interface() {
+                self.selector(id.into());
+            }
+            let docs = params_docs(self.gcx, &natspec, f.parameters);
+            self.parameters("Parameters", f.parameters, &docs);
+            let docs = returns_docs(self.gcx, &natspec, f.returns);
+            self.parameters("Returns", f.returns, &docs);
+        }
+    }
+
+    fn events_and_errors(&mut self, c: &hir::Contract<'gcx>) {
+        let events = c.items.iter().filter_map(|item| match *item {
+            hir::ItemId::Event(id) => Some(id),
+            _ => None,
+        });
+        let events = events.collect::<Vec<_>>();
+        if !events.is_empty() {
+            self.out.push_str("\n\n## Events\n");
+            for (i, id) in events.into_iter().enumerate() {
+                let event = self.gcx.hir.event(id);
+                self.item_heading(i, event.name.as_str());
+                let natspec = self.gcx.hir.natspec(id);
+                self.notice_and_details(natspec);
+                let anonymous = if event.anonymous { " anonymous" } else { "" };
+                let params = self.parameter_list(event.parameters);
+                self.code_block(&format!("event {}({params}){anonymous};", event.name));
+                let signature = self.gcx.item_signature(id.into());
+                writeln!(self.out, "\n**Signature:** `{signature}`").unwrap();
+                if !event.anonymous {
+                    let topic = self.gcx.event_selector(id);
+                    writeln!(self.out, "\n**Topic:** `{topic}`").unwrap();
+                }
+                let docs = params_docs(self.gcx, natspec, event.parameters);
+                self.parameters("Parameters", event.parameters, &docs);
+            }
+        }
+
+        let errors = c.items.iter().filter_map(|item| match *item {
+            hir::ItemId::Error(id) => Some(id),
+            _ => None,
+        });
+        let errors = errors.collect::<Vec<_>>();
+        if !errors.is_empty() {
+            self.out.push_str("\n\n## Errors\n");
+            for (i, id) in errors.into_iter().enumerate() {
+                let error = self.gcx.hir.error(id);
+                self.item_heading(i, error.name.as_str());
+                let natspec = self.gcx.hir.natspec(id);
+                self.notice_and_details(natspec);
+                let params = self.parameter_list(error.parameters);
+                self.code_block(&format!("error {}({params});", error.name));
+                self.selector(id.into());
+                let docs = params_docs(self.gcx, natspec, error.parameters);
+                self.parameters("Parameters", error.parameters, &docs);
+            }
+        }
+    }
+
+    /// Writes the heading of the `i`th item in a section.
+    fn item_heading(&mut self, i: usize, name: &str) {
+        if i > 0 {
+            self.out.push('\n');
+        }
+        writeln!(self.out, "### {name}").unwrap();
+    }
+
+    /// Writes the `@notice` and `@dev` tags.
+    fn notice_and_details(&mut self, natspec: &[NatSpecItem<'_>]) {
+        if let Some(notice) = joined(natspec, NatSpecKind::Notice) {
+            write!(self.out, "\n{notice}\n").unwrap();
+        }
+        if let Some(details) = joined(natspec, NatSpecKind::Dev) {
+            write!(self.out, "\n*{details}*\n").unwrap();
+        }
+    }
+
+    /// Writes the signature and selector of the given function or error.
+    fn selector(&mut self, id: hir::ItemId) {
+        let signature = self.gcx.item_signature(id);
+        let selector = hex::encode_prefixed(self.gcx.function_selector(id));
+        writeln!(self.out, "\n**Signature:** `{signature}`\n\n**Selector:** `{selector}`").unwrap();
+    }
+
+    /// Writes a table of the given parameters and their documentation, if any.
+    fn parameters(&mut self, title: &str, params: &[hir::VariableId], docs: &[Option<&str>]) {
+        if params.is_empty() {
+            return;
+        }
+        writeln!(self.out, "\n**{title}**\n").unwrap();
+        self.out.push_str("|Name|Type|Description|\n|----|----|-----------|\n");
+        for (&id, doc) in params.iter().zip(docs) {
+            let var = self.gcx.hir.variable(id);
+            let name = var.name.map(|name| format!("`{name}`")).unwrap_or_default();
+            let ty = self.snippet(var.ty.span);
+            writeln!(self.out, "|{name}|`{ty}`|{}|", doc.unwrap_or_default()).unwrap();
+        }
+    }
+
+    fn code_block(&mut self, code: &str) {
+        write!(self.out, "\n```solidity\n{code}\n```\n").unwrap();
+    }
+
+    fn function_declaration(&self, f: &hir::Function<'_>) -> String {
+        let mut s = f.kind.to_str().to_string();
+        if let Some(name) = f.name {
+            write!(s, " {name}").unwrap();
+        }
+        write!(s, "({})", self.parameter_list(f.parameters)).unwrap();
+        if !matches!(f.kind, FunctionKind::Constructor | FunctionKind::Modifier) {
+            write!(s, " {}", f.visibility).unwrap();
+        }
+        if f.state_mutability != StateMutability::NonPayable {
+            write!(s, " {}", f.state_mutability).unwrap();
+        }
+        if f.marked_virtual {
+            s.push_str(" virtual");
+        }
+        if f.override_ {
+            s.push_str(" override");
+        }
+        if !f.returns.is_empty() {
+            write!(s, " returns ({})", self.parameter_list(f.returns)).unwrap();
+        }
+        s.push(';');
+        s
+    }
+
+    fn parameter_list(&self, params: &[hir::VariableId]) -> String {
+        let params = params.iter().map(|&id| self.snippet(self.gcx.hir.variable(id).span));
+        params.collect::<Vec<_>>().join(", ")
+    }
+
+    fn snippet(&self, span: Span) -> String {
+        self.gcx.sess.source_map().span_to_snippet(span).unwrap_or_default()
+    }
+}
+
+/// Returns the link to the documentation page of the given contract.
+fn link(gcx: Gcx<'_>, id: hir::ContractId) -> String {
+    let path = contract_page_path(gcx, id);
+    let components = path.components().map(|c| c.as_os_str().to_string_lossy());
+    format!("/{}", components.collect::<Vec<_>>().join("/"))
+}
+
+/// Returns the contents of all tags of the given kind, joined with a space.
+fn joined(natspec: &[NatSpecItem<'_>], kind: NatSpecKind) -> Option<String> {
+    let contents = natspec.iter().filter(|item| item.kind == kind).map(|item| item.content);
+    let joined = contents.collect::<Vec<_>>().join(" ");
+    (!joined.is_empty()).then_some(joined)
+}
+
+/// Returns the `@param` documentation of each of the given parameters.
+fn params_docs<'a>(
+    gcx: Gcx<'_>,
+    natspec: &[NatSpecItem<'a>],
+    params: &[hir::VariableId],
+) -> Vec<Option<&'a str>> {
+    params
+        .iter()
+        .map(|&id| {
+            let name = gcx.hir.variable(id).name?;
+            natspec.iter().find_map(|item| match item.kind {
+                NatSpecKind::Param { name: param } if param == name.name => Some(item.content),
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+/// Returns the `@return` documentation of each of the given return parameters.
+fn returns_docs<'a>(
+    gcx: Gcx<'_>,
+    natspec: &[NatSpecItem<'a>],
+    returns: &[hir::VariableId],
+) -> Vec<Option<&'a str>> {
+    let mut tags = natspec.iter().filter(|item| item.kind == NatSpecKind::Return);
+    returns
+        .iter()
+        .map(|&id| {
+            let content = tags.next()?.content;
+            // The documentation of a named return parameter starts with its name.
+            Some(match gcx.hir.variable(id).name {
+                Some(name) => content.strip_prefix(name.as_str()).map_or(content, str::trim_start),
+                None => content,
+            })
+        })
+        .collect()
+}
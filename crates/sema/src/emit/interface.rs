@@ -0,0 +1,479 @@
+//! Solidity interface generation.
+//!
+//! Generates an `interface I<Name>` for every contract from its externally callable functions,
+//! along with the events and errors of its interface, the user-defined types they depend on, and
+//! their NatSpec documentation.
+
+use crate::{
+    hir::{self, DataLocation, NatSpecItem, NatSpecKind, StateMutability},
+    ty::{Gcx, Ty, TyKind},
+};
+use rayon::prelude::*;
+use solar_ast::ast::ElementaryType;
+use solar_data_structures::map::{FxHashSet, FxIndexMap};
+use std::{fmt::Write, path::PathBuf};
+
+/// Writes the interfaces of all contracts to `<out_dir>/interfaces`, or to stdout if no output
+/// directory is specified.
+pub(crate) fn emit(gcx: Gcx<'_>) {
+    let interfaces = gcx
+        .hir
+        .par_contract_ids()
+        .filter(|&id| has_interface(gcx, id))
+        .map(|id| (interface_path(gcx, id), contract_interface(gcx, id)))
+        .collect::<Vec<_>>();
+
+    let Some(out_dir) = gcx.sess.out_dir.as_deref() else {
+        for (_, interface) in &interfaces {
+            println!("{interface}");
+        }
+        return;
+    };
+    let interfaces_dir = out_dir.join("interfaces");
+    interfaces.par_iter().for_each(|(path, interface)| {
+        let path = interfaces_dir.join(path);
+        let r = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, interface));
+        if let Err(e) = r {
+            let msg = format!("failed to write interface to {}: {e}", path.display());
+            gcx.dcx().err(msg).emit();
+        }
+    });
+}
+
+/// Returns `true` if an interface is generated for the given contract.
+///
+/// Interfaces already are one, and the external functions of libraries have a different ABI.
+pub(crate) fn has_interface(gcx: Gcx<'_>, id: hir::ContractId) -> bool {
+    let kind = gcx.hir.contract(id).kind;
+    !kind.is_interface() && !kind.is_library()
+}
+
+/// Returns the path of the interface of the given contract, relative to the interfaces root:
+/// `<source path>/I<name>.sol`.
+pub(crate) fn interface_path(gcx: Gcx<'_>, id: hir::ContractId) -> PathBuf {
+    let c = gcx.hir.contract(id);
+    let mut path = super::source_relative_path(gcx, c.source);
+    path.push(format!("I{}.sol", c.name));
+    path
+}
+
+/// Returns the source of the `interface I<Name>` of the given contract.
+///
+/// Reference-type parameters are declared in `calldata` and return values in `memory`. Contract
+/// types are declared as `address`, since the contracts themselves are not imported. The structs,
+/// enums and user-defined value types used in the signatures are declared in the interface,
+/// prefixed with the name of their contract if their names collide.
+pub(crate) fn contract_interface(gcx: Gcx<'_>, id: hir::ContractId) -> String {
+    let mut generator = Generator { gcx, out: String::new(), types: FxIndexMap::default() };
+    generator.contract(id);
+    generator.out
+}
+
+struct Generator<'gcx> {
+    gcx: Gcx<'gcx>,
+    out: String,
+    /// The user-defined types the interface depends on, mapped to their names in the interface.
+    types: FxIndexMap<hir::ItemId, String>,
+}
+
+impl<'gcx> Generator<'gcx> {
+    fn contract(&mut self, id: hir::ContractId) {
+        let gcx = self.gcx;
+        let c = gcx.hir.contract(id);
+        let functions = gcx.interface_functions(id).all();
+        let events_and_errors = gcx.interface_events_and_errors(id);
+
+        for f in functions {
+            let TyKind::FnPtr(ty) = f.ty.kind else { unreachable!() };
+            ty.tys().for_each(|ty| self.collect_types(ty));
+        }
+        for &item in events_and_errors {
+            gcx.item_parameter_types(item).iter().for_each(|&ty| self.collect_types(ty));
+        }
+        self.name_types();
+
+        let src = &gcx.hir.source(c.source).file.src;
+        if let Some(license) = license(src) {
+            writeln!(self.out, "// SPDX-License-Identifier: {license}").unwrap();
+        }
+        // Custom errors require 0.8.4 and user-defined value types 0.8.8.
+        let minor = if self.types.keys().any(|item| matches!(item, hir::ItemId::Udvt(_))) {
+            8
+        } else if events_and_errors.iter().any(|item| matches!(item, hir::ItemId::Error(_))) {
+            4
+        } else {
+            0
+        };
+        writeln!(self.out, "pragma solidity ^0.8.{minor};\n").unwrap();
+
+        self.natspec(0, gcx.hir.natspec(id));
+        writeln!(self.out, "interface I{} {{", c.name).unwrap();
+        let mut first = true;
+        let mut separate = |out: &mut String| {
+            if !std::mem::take(&mut first) {
+                out.push('\n');
+            }
+        };
+
+        let types = self.types.clone();
+        for kind in 0..3 {
+            for (&item, name) in &types {
+                match (kind, item) {
+                    (0, hir::ItemId::Udvt(udvt)) => {
+                        let TyKind::Udvt(ty, _) = gcx.type_of_item(udvt.into()).kind else {
+                            continue;
+                        };
+                        separate(&mut self.out);
+                        writeln!(self.out, "    type {name} is {};", self.ty(ty, None)).unwrap();
+                    }
+                    (1, hir::ItemId::Enum(enm)) => {
+                        separate(&mut self.out);
+                        writeln!(self.out, "    enum {name} {{").unwrap();
+                        let variants = gcx.hir.enumm(enm).variants;
+                        for (i, variant) in variants.iter().enumerate() {
+                            let comma = if i + 1 < variants.len() { "," } else { "" };
+                            writeln!(self.out, "        {variant}{comma}").unwrap();
+                        }
+                        self.out.push_str("    }\n");
+                    }
+                    (2, hir::ItemId::Struct(strukt)) => {
+                        separate(&mut self.out);
+                        writeln!(self.out, "    struct {name} {{").unwrap();
+                        for (ty, field) in gcx.item_fields(strukt) {
+                            let ty = self.ty(ty, None);
+                            writeln!(self.out, "        {ty} {};", gcx.item_name(field)).unwrap();
+                        }
+                        self.out.push_str("    }\n");
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for &item in events_and_errors {
+            separate(&mut self.out);
+            self.natspec(1, gcx.hir.natspec(item));
+            let params = gcx.item_parameters(item).iter().zip(gcx.item_parameter_types(item));
+            let params = params
+                .map(|(&param, &ty)| {
+                    let var = gcx.hir.variable(param);
+                    let mut s = self.ty(ty, None);
+                    if var.indexed {
+                        s.push_str(" indexed");
+                    }
+                    if let Some(name) = var.name {
+                        write!(s, " {name}").unwrap();
+                    }
+                    s
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            match item {
+                hir::ItemId::Event(event) => {
+                    let event = gcx.hir.event(event);
+                    let anonymous = if event.anonymous { " anonymous" } else { "" };
+                    writeln!(self.out, "    event {}({params}){anonymous};", event.name).unwrap();
+                }
+                hir::ItemId::Error(error) => {
+                    writeln!(self.out, "    error {}({params});", gcx.hir.error(error).name)
+                        .unwrap();
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        for (kind, f) in [("fallback", c.fallback), ("receive", c.receive)] {
+            let Some(f) = f else { continue };
+            separate(&mut self.out);
+            self.natspec(1, &gcx.item_natspec(f.into()));
+            let f = gcx.hir.function(f);
+            let mutability = mutability(f.state_mutability);
+            writeln!(self.out, "    {kind}() external{mutability};").unwrap();
+        }
+
+        for f in functions {
+            separate(&mut self.out);
+            let func = gcx.hir.function(f.id);
+            let (decl, natspec) = match func.gettee {
+                Some(var) => self.getter(func, var),
+                None => (func, gcx.item_natspec(f.id.into())),
+            };
+            self.natspec(1, &natspec);
+            let TyKind::FnPtr(ty) = f.ty.kind else { unreachable!() };
+            let params =
+                self.parameter_list(decl.parameters, ty.parameters, DataLocation::Calldata);
+            let mutability = mutability(func.state_mutability);
+            let name = func.name.unwrap();
+            write!(self.out, "    function {name}({params}) external{mutability}").unwrap();
+            if !decl.returns.is_empty() {
+                let returns = self.parameter_list(decl.returns, ty.returns, DataLocation::Memory);
+                write!(self.out, " returns ({returns})").unwrap();
+            }
+            self.out.push_str(";\n");
+        }
+
+        self.out.push_str("}\n");
+    }
+
+    /// Returns the declaration whose parameter names to use for the given getter, and the
+    /// getter's documentation.
+    ///
+    /// Getter parameters are unnamed, so the names are taken from the function that the variable
+    /// inherits its documentation from, if any, which its `@param` and `@return` tags refer to.
+    fn getter(
+        &self,
+        getter: &'gcx hir::Function<'gcx>,
+        var: hir::VariableId,
+    ) -> (&'gcx hir::Function<'gcx>, Vec<NatSpecItem<'gcx>>) {
+        let gcx = self.gcx;
+        let decl = gcx
+            .natspec_base_item(var.into())
+            .and_then(|base| base.as_function())
+            .map_or(getter, |base| gcx.hir.function(base));
+        let mut natspec = gcx.item_natspec(var.into());
+        // Names of return parameters are stripped from the inherited `@return` tags.
+        let mut returns = decl.returns.iter().map(|&ret| gcx.item_name_opt(ret));
+        for item in natspec.iter_mut().filter(|item| item.kind == NatSpecKind::Return) {
+            let Some(Some(name)) = returns.next() else { continue };
+            let content = item.content;
+            let rest = content.strip_prefix(name.as_str());
+            if !rest.is_some_and(|rest| rest.is_empty() || rest.starts_with(' ')) {
+                item.content = gcx.bump().alloc_str(format!("{name} {content}").trim_end());
+            }
+        }
+        (decl, natspec)
+    }
+
+    /// Collects the user-defined types that the given type depends on.
+    fn collect_types(&mut self, ty: Ty<'gcx>) {
+        match ty.kind {
+            TyKind::Ref(ty, _) | TyKind::DynArray(ty) | TyKind::Array(ty, _) => {
+                self.collect_types(ty)
+            }
+            TyKind::Struct(id) if self.types.insert(id.into(), String::new()).is_none() => {
+                for &ty in self.gcx.struct_field_types(id) {
+                    self.collect_types(ty);
+                }
+            }
+            TyKind::Enum(id) => {
+                self.types.insert(id.into(), String::new());
+            }
+            TyKind::Udvt(_, id) => {
+                self.types.insert(id.into(), String::new());
+            }
+            TyKind::FnPtr(f) => f.tys().for_each(|ty| self.collect_types(ty)),
+            _ => {}
+        }
+    }
+
+    /// Names the collected types, prefixing the names that collide with the name of their
+    /// contract, and then with a numeric suffix.
+    fn name_types(&mut self) {
+        let gcx = self.gcx;
+        let mut names = FxHashSet::default();
+        for (&item, name) in &mut self.types {
+            let ident = gcx.item_name(item);
+            *name = ident.to_string();
+            if !names.insert(name.clone()) {
+                if let Some(c) = gcx.hir.item(item).contract() {
+                    *name = format!("{}_{ident}", gcx.item_name(c));
+                }
+                let base = name.clone();
+                let mut i = 1;
+                while !names.insert(name.clone()) {
+                    *name = format!("{base}_{i}");
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    /// Formats the given parameters, declaring reference types in the given data location.
+    fn parameter_list(
+        &self,
+        params: &[hir::VariableId],
+        tys: &[Ty<'gcx>],
+        loc: DataLocation,
+    ) -> String {
+        let params = params.iter().zip(tys).map(|(&param, &ty)| {
+            let mut s = self.ty(ty, Some(loc));
+            if let Some(name) = self.gcx.hir.variable(param).name {
+                write!(s, " {name}").unwrap();
+            }
+            s
+        });
+        params.collect::<Vec<_>>().join(", ")
+    }
+
+    /// Formats the given type, followed by the given data location if it is a reference type.
+    fn ty(&self, ty: Ty<'gcx>, loc: Option<DataLocation>) -> String {
+        let ty = ty.peel_refs();
+        let mut s = String::new();
+        self.write_ty(&mut s, ty);
+        if let Some(loc) = loc.filter(|_| !ty.is_value_type()) {
+            write!(s, " {loc}").unwrap();
+        }
+        s
+    }
+
+    fn write_ty(&self, s: &mut String, ty: Ty<'gcx>) {
+        match ty.kind {
+            TyKind::Elementary(ty) => {
+                ty.write_abi_str(&mut *s).unwrap();
+                if matches!(ty, ElementaryType::Address(true)) {
+                    s.push_str(" payable");
+                }
+            }
+            TyKind::Contract(_) => s.push_str("address"),
+            TyKind::Struct(id) => s.push_str(&self.types[&hir::ItemId::from(id)]),
+            TyKind::Enum(id) => s.push_str(&self.types[&hir::ItemId::from(id)]),
+            TyKind::Udvt(_, id) => s.push_str(&self.types[&hir::ItemId::from(id)]),
+            TyKind::Ref(ty, _) => self.write_ty(s, ty),
+            TyKind::DynArray(ty) => {
+                self.write_ty(s, ty);
+                s.push_str("[]");
+            }
+            TyKind::Array(ty, len) => {
+                self.write_ty(s, ty);
+                write!(s, "[{len}]").unwrap();
+            }
+            TyKind::FnPtr(f) => {
+                let params = f.parameters.iter().map(|&ty| self.ty(ty, Some(DataLocation::Memory)));
+                write!(s, "function({}) {}", params.collect::<Vec<_>>().join(", "), f.visibility)
+                    .unwrap();
+                s.push_str(mutability(f.state_mutability));
+                if !f.returns.is_empty() {
+                    let returns =
+                        f.returns.iter().map(|&ty| self.ty(ty, Some(DataLocation::Memory)));
+                    write!(s, " returns ({})", returns.collect::<Vec<_>>().join(", ")).unwrap();
+                }
+            }
+            _ => panic!("printing invalid interface type: {ty:?}"),
+        }
+    }
+
+    /// Writes the given NatSpec tags as `///` comments at the given indentation level.
+    fn natspec(&mut self, indent: usize, natspec: &[NatSpecItem<'_>]) {
+        for item in natspec {
+            let tag = match item.kind {
+                NatSpecKind::Param { name } => format!("@param {name}"),
+                NatSpecKind::Inheritdoc { .. } => continue,
+                kind => kind.to_string(),
+            };
+            let line = format!("/// {tag} {}", item.content);
+            writeln!(self.out, "{:indent$}{}", "", line.trim_end(), indent = indent * 4).unwrap();
+        }
+    }
+}
+
+/// Returns the state mutability of a function, prefixed with a space, or an empty string if it is
+/// non-payable.
+fn mutability(state_mutability: StateMutability) -> &'static str {
+    match state_mutability {
+        StateMutability::NonPayable => "",
+        StateMutability::Pure => " pure",
+        StateMutability::View => " view",
+        StateMutability::Payable => " payable",
+    }
+}
+
+/// Returns the SPDX license identifier of the given source, if any.
+fn license(src: &str) -> Option<&str> {
+    const SPDX: &str = "SPDX-License-Identifier:";
+    let start = src.find(SPDX)? + SPDX.len();
+    let line = src[start..].lines().next()?;
+    let license = line.trim().trim_end_matches("*/").trim_end();
+    (!license.is_empty()).then_some(license)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParsingContext;
+    use solar_interface::{source_map::FileName, Result, Session};
+
+    /// Analyzes the given source, calling `f` with the global context.
+    fn analyze(sess: &Session, name: &str, src: &str, f: impl FnOnce(Gcx<'_>)) -> Result<()> {
+        let mut pcx = ParsingContext::new(sess);
+        let name = FileName::Custom(name.into());
+        pcx.add_file(sess.source_map().new_source_file(name, || Ok(src.into())).unwrap());
+        crate::parse_and_resolve_with(pcx, |gcx, _, _| {
+            f(gcx);
+            Ok(())
+        })
+    }
+
+    /// Generates the interfaces of the contracts in the given source, and checks that they
+    /// compile.
+    fn interfaces(src: &str) -> Vec<String> {
+        let sess = Session::builder().with_test_emitter().build();
+        let mut interfaces = Vec::new();
+        sess.enter(|| {
+            analyze(&sess, "main.sol", src, |gcx| {
+                let ids = gcx.hir.contract_ids().filter(|&id| has_interface(gcx, id));
+                interfaces.extend(ids.map(|id| contract_interface(gcx, id)));
+            })
+        })
+        .unwrap();
+        for interface in &interfaces {
+            let sess = Session::builder().with_test_emitter().build();
+            let r = sess.enter(|| analyze(&sess, "interface.sol", interface, |_| {}));
+            assert!(r.is_ok(), "generated interface does not compile:\n{interface}");
+        }
+        interfaces
+    }
+
+    #[test]
+    fn getters() {
+        let src = r#"
+interface IPool {
+    /// @notice Returns the info of a tick.
+    /// @param tick The tick to look up
+    /// @return liquidity The liquidity of the tick
+    /// @return initialized Whether the tick is initialized
+    function ticks(int24 tick) external view returns (uint128 liquidity, bool initialized);
+
+    /// @return The fee
+    function fee() external view returns (uint24);
+}
+
+contract Pool is IPool {
+    struct Tick {
+        uint128 liquidity;
+        bool initialized;
+    }
+
+    /// @inheritdoc IPool
+    mapping(int24 => Tick) public override ticks;
+
+    /// @inheritdoc IPool
+    uint24 public immutable override fee = 3000;
+
+    /// @notice The balances of the pool.
+    /// @return The balance of the account
+    mapping(address => uint256) public balances;
+}
+"#;
+        let expected = r#"pragma solidity ^0.8.0;
+
+interface IPool {
+    /// @notice Returns the info of a tick.
+    /// @param tick The tick to look up
+    /// @return liquidity The liquidity of the tick
+    /// @return initialized Whether the tick is initialized
+    function ticks(int24 tick) external view returns (uint128 liquidity, bool initialized);
+
+    /// @return The fee
+    function fee() external view returns (uint24);
+
+    /// @notice The balances of the pool.
+    /// @return The balance of the account
+    function balances(address) external view returns (uint256);
+}
+"#;
+        assert_eq!(interfaces(src), [expected]);
+    }
+}
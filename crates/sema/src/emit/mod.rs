@@ -0,0 +1,545 @@
+use crate::{hir, metadata, parse::ParsedSources, ty::Gcx};
+use serde::Serialize;
+use solar_data_structures::map::FxHashMap;
+use solar_interface::{
+    config::{ArtifactLayout, CompilerOutput},
+    source_map::FileName,
+};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+};
+use storage_layout::StorageLayoutJson;
+
+pub(crate) mod ast_json;
+pub(crate) mod docs;
+pub(crate) mod interface;
+pub(crate) mod natspec;
+pub(crate) mod rust_bindings;
+pub(crate) mod storage_layout;
+
+#[derive(Default, Serialize)]
+struct CombinedJson {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    contracts: BTreeMap<String, CombinedJsonContract>,
+    #[serde(rename = "sourceList", skip_serializing_if = "Vec::is_empty")]
+    source_list: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    sources: BTreeMap<String, CombinedJsonSource>,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+struct CombinedJsonSource {
+    #[serde(rename = "AST")]
+    ast: serde_json::Value,
+    id: usize,
+}
+
+#[derive(Default, Serialize)]
+struct CombinedJsonContract {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    abi: Option<Abi>,
+    /// The AST of the contract's source. Only included in per-contract artifacts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ast: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    devdoc: Option<serde_json::Value>,
+    #[serde(rename = "error-hashes", skip_serializing_if = "Option::is_none")]
+    error_hashes: Option<Hashes>,
+    #[serde(rename = "event-hashes", skip_serializing_if = "Option::is_none")]
+    event_hashes: Option<Hashes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hashes: Option<Hashes>,
+    #[serde(rename = "interface-ids", skip_serializing_if = "Option::is_none")]
+    interface_ids: Option<Hashes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<String>,
+    #[serde(rename = "storage-layout", skip_serializing_if = "Option::is_none")]
+    storage_layout: Option<StorageLayoutJson>,
+    #[serde(rename = "transient-storage-layout", skip_serializing_if = "Option::is_none")]
+    transient_storage_layout: Option<StorageLayoutJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    userdoc: Option<serde_json::Value>,
+}
+
+/// The build-info manifest written alongside per-contract artifacts.
+#[derive(Serialize)]
+struct BuildInfo {
+    /// The fully qualified names of the contracts, mapped to their artifact paths relative to the
+    /// output directory.
+    contracts: BTreeMap<String, String>,
+    /// The source files, keyed by name.
+    sources: BTreeMap<String, BuildInfoSource>,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+struct BuildInfoSource {
+    keccak256: String,
+}
+
+type Abi = Vec<alloy_json_abi::AbiItem<'static>>;
+type Hashes = BTreeMap<String, String>;
+
+pub(crate) fn emit(
+    gcx: Gcx<'_>,
+    imports: &metadata::ImportPaths,
+    asts: Option<&ParsedSources<'_>>,
+) {
+    if gcx.sess.emit.contains(&CompilerOutput::Docs) {
+        docs::emit(gcx);
+    }
+    if gcx.sess.emit.contains(&CompilerOutput::Interface) {
+        interface::emit(gcx);
+    }
+    if gcx.sess.emit.contains(&CompilerOutput::RustBindings) {
+        rust_bindings::emit(gcx);
+    }
+    // Documentation, interfaces and bindings are not part of the JSON artifacts.
+    let is_source = |emit: &_| {
+        matches!(
+            emit,
+            CompilerOutput::Docs | CompilerOutput::Interface | CompilerOutput::RustBindings
+        )
+    };
+    if gcx.sess.emit.iter().all(is_source) {
+        return;
+    }
+
+    match gcx.sess.artifact_layout {
+        ArtifactLayout::Combined => emit_combined(gcx, imports, asts),
+        ArtifactLayout::PerContract => emit_per_contract(gcx, imports, asts),
+        ArtifactLayout::Solc => emit_solc(gcx),
+    }
+}
+
+/// Writes the outputs of all contracts to `<out_dir>/combined.json`, or to stdout if no output
+/// directory is specified.
+fn emit_combined(gcx: Gcx<'_>, imports: &metadata::ImportPaths, asts: Option<&ParsedSources<'_>>) {
+    let contracts = gcx
+        .hir
+        .contract_ids()
+        .map(|id| {
+            (gcx.contract_fully_qualified_name(id).to_string(), contract_output(gcx, id, imports))
+        })
+        .collect();
+    let mut output =
+        CombinedJson { contracts, version: solar_interface::VERSION, ..Default::default() };
+    if let Some(asts) = asts.filter(|_| gcx.sess.do_emit(CompilerOutput::AstJson)) {
+        let source_list = ast_json::source_list(gcx);
+        output.sources = ast_json::ast_json(gcx, asts)
+            .into_iter()
+            .map(|(id, ast)| {
+                let name = gcx.hir.source(id).file.name.display().to_string();
+                let id = source_list.iter().position(|&s| s == id).unwrap();
+                (name, CombinedJsonSource { ast, id })
+            })
+            .collect();
+        output.source_list = source_list
+            .into_iter()
+            .map(|id| gcx.hir.source(id).file.name.display().to_string())
+            .collect();
+    }
+    let _ = (|| {
+        let out_path = gcx.sess.out_dir.as_deref().map(|dir| dir.join("combined.json"));
+        let mut writer = out_writer(out_path.as_deref())?;
+        to_json(&mut writer, &output, gcx.sess.pretty_json)?;
+        writer.flush()?;
+        Ok::<_, io::Error>(())
+    })()
+    .map_err(|e| gcx.dcx().err(format!("failed to write to output: {e}")).emit());
+}
+
+/// Writes the outputs of each contract to `<out_dir>/<file name>/<contract name>.json`, and the
+/// content hashes of all sources to `<out_dir>/build-info.json`.
+///
+/// Contracts with the same file and contract names as another contract are written to
+/// `<out_dir>/<source path>/<contract name>.json` instead.
+fn emit_per_contract(
+    gcx: Gcx<'_>,
+    imports: &metadata::ImportPaths,
+    asts: Option<&ParsedSources<'_>>,
+) {
+    let Some(out_dir) = gcx.sess.out_dir.as_deref() else {
+        gcx.dcx().err("per-contract artifacts require an output directory").emit();
+        return;
+    };
+
+    let source_asts = match asts.filter(|_| gcx.sess.do_emit(CompilerOutput::AstJson)) {
+        Some(asts) => ast_json::ast_json(gcx, asts).into_iter().collect(),
+        None => FxHashMap::default(),
+    };
+    let source_asts = &source_asts;
+    let artifact_paths = artifact_paths(gcx);
+    gcx.sess.scope(|scope| {
+        for (id, path) in &artifact_paths {
+            scope.spawn(move |_| {
+                let mut output = contract_output(gcx, *id, imports);
+                output.ast = source_asts.get(&gcx.hir.contract(*id).source).cloned();
+                write_artifact(gcx, &out_dir.join(path), &output);
+            });
+        }
+
+        let build_info = BuildInfo {
+            version: solar_interface::VERSION,
+            sources: gcx
+                .hir
+                .sources()
+                .map(|source| {
+                    let name = source.file.name.display().to_string();
+                    (name, BuildInfoSource { keccak256: metadata::source_keccak256(&source.file) })
+                })
+                .collect(),
+            contracts: artifact_paths
+                .iter()
+                .map(|&(id, ref path)| {
+                    (gcx.contract_fully_qualified_name(id).to_string(), path.display().to_string())
+                })
+                .collect(),
+        };
+        write_artifact(gcx, &out_dir.join("build-info.json"), &build_info);
+    });
+}
+
+/// Writes the outputs of each contract in solc's layout: `<out_dir>/<name>.abi` and
+/// `<out_dir>/<name>.signatures` files, or one section per contract on stdout if no output
+/// directory is specified.
+///
+/// `<name>` is the contract name, or the fully qualified name with `/`, `:` and `.` replaced by
+/// `_` if another contract has the same name.
+fn emit_solc(gcx: Gcx<'_>) {
+    for &emit in &gcx.sess.emit {
+        if !matches!(
+            emit,
+            CompilerOutput::Abi
+                | CompilerOutput::Hashes
+                | CompilerOutput::Docs
+                | CompilerOutput::Interface
+                | CompilerOutput::RustBindings
+        ) {
+            let msg = format!("`--emit {emit}` is not supported with `--artifact-layout solc`");
+            gcx.dcx().warn(msg).emit();
+        }
+    }
+
+    let mut contracts = gcx
+        .hir
+        .contract_ids()
+        .map(|id| (gcx.contract_fully_qualified_name(id).to_string(), id))
+        .collect::<Vec<_>>();
+    contracts.sort();
+    let mut stdout = String::new();
+    for (fqn, id) in contracts {
+        let name = gcx.hir.contract(id).name;
+        let abi = gcx.sess.do_emit(CompilerOutput::Abi).then(|| {
+            let abi = gcx.contract_abi(id);
+            if gcx.sess.pretty_json {
+                serde_json::to_string_pretty(&abi)
+            } else {
+                serde_json::to_string(&abi)
+            }
+            .unwrap()
+        });
+        let signatures = gcx.sess.do_emit(CompilerOutput::Hashes).then(|| {
+            let (error_hashes, event_hashes) = error_and_event_hashes(gcx, id);
+            let mut out = String::from("Function signatures:\n");
+            for (title, hashes) in [
+                (None, method_identifiers(gcx, id)),
+                (Some("Error"), error_hashes),
+                (Some("Event"), event_hashes),
+            ] {
+                if let Some(title) = title {
+                    if hashes.is_empty() {
+                        continue;
+                    }
+                    out.push_str(&format!("\n{title} signatures:\n"));
+                }
+                for (signature, hash) in hashes {
+                    out.push_str(&format!("{hash}: {signature}\n"));
+                }
+            }
+            out
+        });
+
+        let Some(out_dir) = gcx.sess.out_dir.as_deref() else {
+            stdout.push_str(&format!("\n======= {fqn} =======\n"));
+            if let Some(signatures) = signatures {
+                stdout.push_str(&signatures);
+            }
+            if let Some(abi) = abi {
+                stdout.push_str(&format!("Contract JSON ABI\n{abi}\n"));
+            }
+            continue;
+        };
+        let unique = gcx
+            .hir
+            .contract_ids()
+            .all(|other| other == id || gcx.hir.contract(other).name.name != name.name);
+        let file_name = if unique { name.to_string() } else { fqn.replace(['/', ':', '.'], "_") };
+        for (ext, contents) in [("abi", abi), ("signatures", signatures)] {
+            let Some(contents) = contents else { continue };
+            let path = out_dir.join(format!("{file_name}.{ext}"));
+            if let Err(e) = std::fs::write(&path, contents) {
+                let msg = format!("failed to write artifact to {}: {e}", path.display());
+                gcx.dcx().err(msg).emit();
+            }
+        }
+    }
+    print!("{stdout}");
+}
+
+fn write_artifact<T: Serialize>(gcx: Gcx<'_>, path: &Path, value: &T) {
+    let r = (|| {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut writer = out_writer(Some(path))?;
+        to_json(&mut writer, value, gcx.sess.pretty_json)?;
+        writer.flush()
+    })();
+    if let Err(e) = r {
+        let msg = format!("failed to write artifact to {}: {e}", path.display());
+        gcx.dcx().err(msg).emit();
+    }
+}
+
+/// Returns the artifact paths of all contracts, relative to the output directory.
+fn artifact_paths(gcx: Gcx<'_>) -> Vec<(hir::ContractId, PathBuf)> {
+    let file_name = |id: hir::ContractId| {
+        let path = source_relative_path(gcx, gcx.hir.contract(id).source);
+        path.file_name().map(PathBuf::from).unwrap_or(path)
+    };
+    let mut counts = FxHashMap::<_, usize>::default();
+    for id in gcx.hir.contract_ids() {
+        *counts.entry((file_name(id), gcx.hir.contract(id).name.name)).or_default() += 1;
+    }
+    gcx.hir
+        .contract_ids()
+        .map(|id| {
+            let c = gcx.hir.contract(id);
+            let file_name = file_name(id);
+            let mut path = if counts[&(file_name.clone(), c.name.name)] > 1 {
+                source_relative_path(gcx, c.source)
+            } else {
+                file_name
+            };
+            path.push(format!("{}.json", c.name));
+            (id, path)
+        })
+        .collect()
+}
+
+/// Returns the path of the given source relative to the current directory, without any `.` or
+/// `..` components, for use in output directories.
+pub(crate) fn source_relative_path(gcx: Gcx<'_>, id: hir::SourceId) -> PathBuf {
+    match &gcx.hir.source(id).file.name {
+        FileName::Real(path) => {
+            let cwd = std::env::current_dir().unwrap_or_default();
+            let path = path.strip_prefix(&cwd).unwrap_or(path);
+            path.components().filter(|c| matches!(c, Component::Normal(_))).collect()
+        }
+        FileName::Stdin => PathBuf::from("stdin"),
+        FileName::Custom(name) => PathBuf::from(name),
+    }
+}
+
+/// Returns the selected outputs of the given contract.
+fn contract_output(
+    gcx: Gcx<'_>,
+    id: hir::ContractId,
+    imports: &metadata::ImportPaths,
+) -> CombinedJsonContract {
+    let mut output = CombinedJsonContract::default();
+    for &emit in &gcx.sess.emit {
+        match emit {
+            CompilerOutput::Abi => output.abi = Some(gcx.contract_abi(id)),
+            // Source outputs are added by the caller.
+            CompilerOutput::AstJson => {}
+            CompilerOutput::ErrorHashes => {
+                output.error_hashes = Some(error_and_event_hashes(gcx, id).0)
+            }
+            CompilerOutput::EventHashes => {
+                output.event_hashes = Some(error_and_event_hashes(gcx, id).1)
+            }
+            CompilerOutput::Hashes => output.hashes = Some(method_identifiers(gcx, id)),
+            CompilerOutput::InterfaceIds => {
+                // The contract itself, followed by the interfaces it inherits.
+                let c = gcx.hir.contract(id);
+                let mut interface_ids = Hashes::default();
+                for &base in c.linearized_bases {
+                    if base == id || gcx.hir.contract(base).kind.is_interface() {
+                        interface_ids.insert(
+                            gcx.hir.contract(base).name.to_string(),
+                            alloy_primitives::hex::encode(gcx.interface_id(base)),
+                        );
+                    }
+                }
+                output.interface_ids = Some(interface_ids);
+            }
+            CompilerOutput::Metadata => {
+                output.metadata = Some(metadata::contract_metadata(gcx, id, imports));
+            }
+            CompilerOutput::Userdoc => output.userdoc = Some(natspec::userdoc(gcx, id)),
+            CompilerOutput::Devdoc => output.devdoc = Some(natspec::devdoc(gcx, id)),
+            CompilerOutput::Docs | CompilerOutput::Interface | CompilerOutput::RustBindings => {}
+            CompilerOutput::StorageLayout => {
+                let layout = gcx.storage_layout(id);
+                output.storage_layout = Some(storage_layout::storage_layout_json(
+                    gcx,
+                    id,
+                    layout.storage,
+                    layout.namespaces,
+                ));
+            }
+            CompilerOutput::TransientStorageLayout => {
+                let layout = gcx.storage_layout(id).transient;
+                output.transient_storage_layout =
+                    Some(storage_layout::storage_layout_json(gcx, id, layout, &[]));
+            }
+        }
+    }
+    output
+}
+
+/// Returns the selectors of the externally callable functions of the given contract, keyed by
+/// signature.
+pub(crate) fn method_identifiers(gcx: Gcx<'_>, id: hir::ContractId) -> Hashes {
+    gcx.interface_functions(id)
+        .iter()
+        .map(|f| {
+            (gcx.item_signature(f.id.into()).to_string(), alloy_primitives::hex::encode(f.selector))
+        })
+        .collect()
+}
+
+/// Returns the selectors of the errors and the topics of the non-anonymous events of the given
+/// contract, keyed by signature.
+fn error_and_event_hashes(gcx: Gcx<'_>, id: hir::ContractId) -> (Hashes, Hashes) {
+    let mut error_hashes = Hashes::default();
+    let mut event_hashes = Hashes::default();
+    for &item in gcx.interface_events_and_errors(id) {
+        match item {
+            hir::ItemId::Error(error) => {
+                error_hashes.insert(
+                    gcx.item_signature(item).to_string(),
+                    alloy_primitives::hex::encode(gcx.function_selector(error)),
+                );
+            }
+            // Anonymous events don't have a topic.
+            hir::ItemId::Event(event) if !gcx.hir.event(event).anonymous => {
+                event_hashes.insert(
+                    gcx.item_signature(item).to_string(),
+                    alloy_primitives::hex::encode(gcx.event_selector(event)),
+                );
+            }
+            _ => {}
+        }
+    }
+    (error_hashes, event_hashes)
+}
+
+fn out_writer(path: Option<&Path>) -> io::Result<impl io::Write> {
+    let out: Box<dyn io::Write> = if let Some(path) = path {
+        Box::new(std::fs::File::create(path)?)
+    } else {
+        Box::new(std::io::stdout())
+    };
+    Ok(io::BufWriter::new(out))
+}
+
+fn to_json<W: io::Write, T: Serialize>(
+    writer: W,
+    value: &T,
+    pretty: bool,
+) -> serde_json::Result<()> {
+    if pretty {
+        serde_json::to_writer_pretty(writer, value)
+    } else {
+        serde_json::to_writer(writer, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParsingContext;
+    use alloy_primitives::{hex, keccak256};
+    use serde_json::{json, Value};
+    use solar_interface::{ColorChoice, Session};
+
+    /// Compiles the given sources with per-contract artifacts, returning the output directory.
+    fn per_contract(sources: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sess = Session::builder().with_test_emitter().build();
+        sess.emit.insert(CompilerOutput::Abi);
+        sess.artifact_layout = ArtifactLayout::PerContract;
+        sess.out_dir = Some(dir.path().to_path_buf());
+        sess.enter(|| {
+            let mut pcx = ParsingContext::new(&sess);
+            for &(name, src) in sources {
+                let name = FileName::Real(PathBuf::from(name));
+                pcx.add_file(sess.source_map().new_source_file(name, || Ok(src.into())).unwrap());
+            }
+            crate::parse_and_resolve(pcx)
+        })
+        .unwrap();
+        dir
+    }
+
+    fn read_json(path: &Path) -> Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(windows, ignore = "paths are printed with backslashes")]
+    fn per_contract_layout() {
+        let sources = [
+            ("src/Token.sol", "contract Token {}\ncontract Vault {}\n"),
+            ("test/Token.sol", "contract Token {}\n"),
+            ("lib/Vault.sol", "contract Vault {}\n"),
+        ];
+        let dir = per_contract(&sources);
+
+        // Only the contracts whose file and contract names collide are nested in their full path.
+        let build_info = read_json(&dir.path().join("build-info.json"));
+        let contracts = json!({
+            "lib/Vault.sol:Vault": "Vault.sol/Vault.json",
+            "src/Token.sol:Token": "src/Token.sol/Token.json",
+            "src/Token.sol:Vault": "Token.sol/Vault.json",
+            "test/Token.sol:Token": "test/Token.sol/Token.json",
+        });
+        assert_eq!(build_info["contracts"], contracts);
+        for path in contracts.as_object().unwrap().values() {
+            let artifact = read_json(&dir.path().join(path.as_str().unwrap()));
+            assert_eq!(artifact["abi"], json!([]), "{path}");
+        }
+
+        let keccak256 = |src: &str| format!("0x{}", hex::encode(keccak256(src)));
+        let sources = sources
+            .iter()
+            .map(|&(name, src)| (name.to_string(), json!({ "keccak256": keccak256(src) })))
+            .collect::<serde_json::Map<_, _>>();
+        assert_eq!(build_info["sources"], Value::Object(sources));
+        assert_eq!(build_info["version"], solar_interface::VERSION);
+    }
+
+    #[test]
+    fn per_contract_without_out_dir() {
+        let mut sess = Session::builder().with_buffer_emitter(ColorChoice::Never).build();
+        sess.emit.insert(CompilerOutput::Abi);
+        sess.artifact_layout = ArtifactLayout::PerContract;
+        let result = sess.enter(|| {
+            let mut pcx = ParsingContext::new(&sess);
+            let name = FileName::Real(PathBuf::from("C.sol"));
+            pcx.add_file(
+                sess.source_map().new_source_file(name, || Ok("contract C {}".into())).unwrap(),
+            );
+            crate::parse_and_resolve(pcx)
+        });
+        assert!(result.is_err());
+        let err = sess.dcx.emitted_errors().unwrap().unwrap_err().to_string();
+        assert!(err.contains("per-contract artifacts require an output directory"), "{err}");
+    }
+}
@@ -0,0 +1,209 @@
+//! Solc-compatible `userdoc` and `devdoc` JSON.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/natspec-format.html#documentation-output>
+
+use crate::{
+    hir::{self, NatSpecItem, NatSpecKind},
+    ty::Gcx,
+};
+use serde_json::{json, Map, Value};
+
+/// Returns the user documentation of the given contract.
+pub(crate) fn userdoc(gcx: Gcx<'_>, id: hir::ContractId) -> Value {
+    let mut doc = Map::new();
+    let natspec = gcx.hir.natspec(id);
+    insert_joined(&mut doc, "notice", natspec, NatSpecKind::Notice);
+
+    let mut methods = Map::new();
+    for (key, item) in documented_functions(gcx, id) {
+        let mut method = Map::new();
+        insert_joined(&mut method, "notice", &gcx.item_natspec(item), NatSpecKind::Notice);
+        if !method.is_empty() {
+            methods.insert(key, method.into());
+        }
+    }
+    let mut events = Map::new();
+    let mut errors = Map::new();
+    for (key, item) in documented_events_and_errors(gcx, id) {
+        let mut value = Map::new();
+        insert_joined(&mut value, "notice", gcx.hir.natspec(item), NatSpecKind::Notice);
+        if !value.is_empty() {
+            insert_event_or_error(&mut events, &mut errors, item, key, value);
+        }
+    }
+    if !errors.is_empty() {
+        doc.insert("errors".into(), errors.into());
+    }
+    if !events.is_empty() {
+        doc.insert("events".into(), events.into());
+    }
+
+    doc.insert("kind".into(), "user".into());
+    doc.insert("methods".into(), methods.into());
+    doc.insert("version".into(), 1.into());
+    doc.into()
+}
+
+/// Returns the developer documentation of the given contract.
+pub(crate) fn devdoc(gcx: Gcx<'_>, id: hir::ContractId) -> Value {
+    let mut doc = Map::new();
+    let natspec = gcx.hir.natspec(id);
+    insert_joined(&mut doc, "author", natspec, NatSpecKind::Author);
+    insert_joined(&mut doc, "details", natspec, NatSpecKind::Dev);
+    insert_joined(&mut doc, "title", natspec, NatSpecKind::Title);
+    insert_custom(&mut doc, natspec);
+
+    let mut methods = Map::new();
+    for (key, item) in documented_functions(gcx, id) {
+        if item.as_variable().is_some() {
+            continue;
+        }
+        let method = dev_function(gcx, item, &gcx.item_natspec(item));
+        if !method.is_empty() {
+            methods.insert(key, method.into());
+        }
+    }
+    let mut events = Map::new();
+    let mut errors = Map::new();
+    for (key, item) in documented_events_and_errors(gcx, id) {
+        let value = dev_function(gcx, item, gcx.hir.natspec(item));
+        if !value.is_empty() {
+            insert_event_or_error(&mut events, &mut errors, item, key, value);
+        }
+    }
+    let mut state_variables = Map::new();
+    for var in gcx.hir.contract(id).variables() {
+        let natspec = gcx.item_natspec(var.into());
+        let mut value = Map::new();
+        insert_joined(&mut value, "details", &natspec, NatSpecKind::Dev);
+        insert_joined(&mut value, "return", &natspec, NatSpecKind::Return);
+        if let Some(ret) = value.get("return").cloned() {
+            value.insert("returns".into(), json!({ "_0": ret }));
+        }
+        insert_custom(&mut value, &natspec);
+        if !value.is_empty() {
+            let name = gcx.item_name(var).to_string();
+            state_variables.insert(name, value.into());
+        }
+    }
+    if !errors.is_empty() {
+        doc.insert("errors".into(), errors.into());
+    }
+    if !events.is_empty() {
+        doc.insert("events".into(), events.into());
+    }
+    if !state_variables.is_empty() {
+        doc.insert("stateVariables".into(), state_variables.into());
+    }
+
+    doc.insert("kind".into(), "dev".into());
+    doc.insert("methods".into(), methods.into());
+    doc.insert("version".into(), 1.into());
+    doc.into()
+}
+
+/// Returns the developer documentation of a function, event, or error.
+fn dev_function(gcx: Gcx<'_>, id: hir::ItemId, natspec: &[NatSpecItem<'_>]) -> Map<String, Value> {
+    let mut value = Map::new();
+    insert_joined(&mut value, "details", natspec, NatSpecKind::Dev);
+    insert_custom(&mut value, natspec);
+
+    let mut params = Map::new();
+    for item in natspec {
+        if let NatSpecKind::Param { name } = item.kind {
+            params.insert(name.to_string(), item.content.into());
+        }
+    }
+    if !params.is_empty() {
+        value.insert("params".into(), params.into());
+    }
+
+    let returns_vars = match id {
+        hir::ItemId::Function(f) => gcx.hir.function(f).returns,
+        _ => &[],
+    };
+    let mut returns = Map::new();
+    let return_tags = natspec.iter().filter(|item| item.kind == NatSpecKind::Return);
+    for (i, item) in return_tags.enumerate() {
+        let name = returns_vars.get(i).and_then(|&var| gcx.hir.variable(var).name);
+        let (key, content) = match name {
+            // The content of a named return parameter starts with its name.
+            Some(name) => {
+                let content = item.content.strip_prefix(name.as_str()).unwrap_or(item.content);
+                (name.to_string(), content.trim_start())
+            }
+            None => (format!("_{i}"), item.content),
+        };
+        returns.insert(key, content.into());
+    }
+    if !returns.is_empty() {
+        value.insert("returns".into(), returns.into());
+    }
+    value
+}
+
+/// Returns the externally callable functions and public state variables of the given contract,
+/// including the constructor, keyed by signature.
+fn documented_functions(
+    gcx: Gcx<'_>,
+    id: hir::ContractId,
+) -> impl Iterator<Item = (String, hir::ItemId)> + '_ {
+    let ctor = gcx.hir.contract(id).ctor.map(|f| ("constructor".to_string(), f.into()));
+    let functions = gcx.interface_functions(id).all().iter().map(move |f| {
+        let item = match gcx.hir.function(f.id).gettee {
+            Some(var) => var.into(),
+            None => f.id.into(),
+        };
+        (gcx.item_signature(f.id.into()).to_string(), item)
+    });
+    ctor.into_iter().chain(functions)
+}
+
+/// Returns the events and errors of the interface of the given contract, keyed by signature.
+fn documented_events_and_errors(
+    gcx: Gcx<'_>,
+    id: hir::ContractId,
+) -> impl Iterator<Item = (String, hir::ItemId)> + '_ {
+    gcx.interface_events_and_errors(id)
+        .iter()
+        .map(move |&item| (gcx.item_signature(item).to_string(), item))
+}
+
+fn insert_event_or_error(
+    events: &mut Map<String, Value>,
+    errors: &mut Map<String, Value>,
+    item: hir::ItemId,
+    key: String,
+    value: Map<String, Value>,
+) {
+    if matches!(item, hir::ItemId::Event(_)) {
+        events.entry(key).or_insert(value.into());
+    } else {
+        // Errors with the same signature may be defined multiple times.
+        let errors = errors.entry(key).or_insert_with(|| Value::Array(Vec::new()));
+        errors.as_array_mut().unwrap().push(value.into());
+    }
+}
+
+/// Inserts the contents of all tags of the given kind, joined with a space.
+fn insert_joined(
+    map: &mut Map<String, Value>,
+    key: &str,
+    natspec: &[NatSpecItem<'_>],
+    kind: NatSpecKind,
+) {
+    let contents = natspec.iter().filter(|item| item.kind == kind).map(|item| item.content);
+    let joined = contents.collect::<Vec<_>>().join(" ");
+    if !joined.is_empty() {
+        map.insert(key.into(), joined.into());
+    }
+}
+
+/// Inserts the `@custom:<name>` tags as `custom:<name>` keys.
+fn insert_custom(map: &mut Map<String, Value>, natspec: &[NatSpecItem<'_>]) {
+    for item in natspec {
+        if let NatSpecKind::Custom { name } = item.kind {
+            map.insert(format!("custom:{name}"), item.content.into());
+        }
+    }
+}
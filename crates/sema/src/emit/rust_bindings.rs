@@ -0,0 +1,1244 @@
+//! Rust bindings generation.
+//!
+//! Generates a Rust module for every contract with the types, calls, events and errors of its
+//! ABI. The generated code implements the `alloy-sol-types` traits the same way as its `sol!`
+//! macro, without having to parse the Solidity sources again at build time.
+
+use crate::{
+    hir,
+    ty::{Gcx, Ty, TyKind},
+};
+use alloy_primitives::hex;
+use rayon::prelude::*;
+use solar_ast::ast::ElementaryType;
+use solar_data_structures::map::{FxHashMap, FxHashSet, FxIndexMap};
+use std::{fmt::Write, path::PathBuf};
+
+/// Writes the bindings of all contracts to `<out_dir>/bindings`, or to stdout if no output
+/// directory is specified.
+pub(crate) fn emit(gcx: Gcx<'_>) {
+    let bindings = gcx
+        .hir
+        .par_contract_ids()
+        .filter(|&id| has_bindings(gcx, id))
+        .map(|id| (bindings_path(gcx, id), contract_bindings(gcx, id)))
+        .collect::<Vec<_>>();
+
+    let Some(out_dir) = gcx.sess.out_dir.as_deref() else {
+        for (_, bindings) in &bindings {
+            println!("{bindings}");
+        }
+        return;
+    };
+    let bindings_dir = out_dir.join("bindings");
+    bindings.par_iter().for_each(|(path, bindings)| {
+        let path = bindings_dir.join(path);
+        let r = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, bindings));
+        if let Err(e) = r {
+            let msg = format!("failed to write bindings to {}: {e}", path.display());
+            gcx.dcx().err(msg).emit();
+        }
+    });
+}
+
+/// Returns `true` if bindings are generated for the given contract.
+///
+/// The external functions of libraries have a different ABI.
+pub(crate) fn has_bindings(gcx: Gcx<'_>, id: hir::ContractId) -> bool {
+    !gcx.hir.contract(id).kind.is_library()
+}
+
+/// Returns the path of the bindings of the given contract, relative to the bindings root:
+/// `<source path>/<snake_case name>.rs`.
+pub(crate) fn bindings_path(gcx: Gcx<'_>, id: hir::ContractId) -> PathBuf {
+    let c = gcx.hir.contract(id);
+    let mut path = super::source_relative_path(gcx, c.source);
+    path.push(format!("{}.rs", snake_case(c.name.as_str())));
+    path
+}
+
+/// Returns the Rust module with the bindings of the given contract.
+///
+/// The module contains, as in `sol!`:
+/// - a struct implementing `SolStruct` for every struct used in the ABI, keeping its Solidity name,
+///   and type aliases of the underlying types for enums and user-defined value types;
+/// - a `<name>Call` struct implementing `SolCall`, and a `<name>Return` struct, for every function,
+///   and a `constructorCall` struct implementing `SolConstructor`;
+/// - a struct implementing `SolEvent` for every event, and `SolError` for every error;
+/// - the `<Contract>Calls`, `<Contract>Errors` and `<Contract>Events` enums, which decode any of
+///   the contract's calls, errors and events.
+///
+/// Overloaded functions, events and errors are suffixed with their index: `<name>_<i>`.
+pub(crate) fn contract_bindings(gcx: Gcx<'_>, id: hir::ContractId) -> String {
+    let mut generator = Generator {
+        gcx,
+        w: Writer::default(),
+        types: FxIndexMap::default(),
+        names: FxHashMap::default(),
+    };
+    generator.contract(id);
+    generator.w.out
+}
+
+/// A field of a generated struct.
+struct Field {
+    name: String,
+    sol: String,
+    rust: String,
+}
+
+struct Generator<'gcx> {
+    gcx: Gcx<'gcx>,
+    w: Writer,
+    /// The user-defined types the bindings depend on, mapped to their names in the module.
+    types: FxIndexMap<hir::ItemId, String>,
+    /// The names of the functions, events and errors in the module.
+    names: FxHashMap<hir::ItemId, String>,
+}
+
+impl<'gcx> Generator<'gcx> {
+    fn contract(&mut self, id: hir::ContractId) {
+        let gcx = self.gcx;
+        let c = gcx.hir.contract(id);
+        let ctor = c.ctor.filter(|_| !c.is_abstract());
+        let functions = gcx.interface_functions(id).all();
+        let events_and_errors = gcx.interface_events_and_errors(id);
+        let events = events_and_errors.iter().filter_map(|&item| match item {
+            hir::ItemId::Event(id) => Some(id),
+            _ => None,
+        });
+        let errors = events_and_errors.iter().filter_map(|&item| match item {
+            hir::ItemId::Error(id) => Some(id),
+            _ => None,
+        });
+
+        for f in functions {
+            let TyKind::FnPtr(ty) = f.ty.kind else { unreachable!() };
+            ty.tys().for_each(|ty| self.collect_types(ty));
+        }
+        for item in ctor.map(hir::ItemId::from).iter().chain(events_and_errors) {
+            gcx.item_parameter_types(*item).iter().for_each(|&ty| self.collect_types(ty));
+        }
+        self.overloaded_names(functions.iter().map(|f| f.id.into()));
+        self.overloaded_names(events.clone().map(Into::into));
+        self.overloaded_names(errors.clone().map(Into::into));
+        self.name_types();
+
+        self.w.line(&format!("/// Bindings for the `{}` contract.", c.name));
+        self.w.line("#[allow(dead_code, missing_docs, non_camel_case_types, non_snake_case, clippy::all, clippy::pub_underscore_fields)]");
+        self.w.line(&format!("pub mod {} {{", c.name));
+        let mut first = true;
+        let mut separate = |w: &mut Writer| {
+            if !std::mem::take(&mut first) {
+                w.line("");
+            }
+        };
+
+        let types = self.types.clone();
+        for (&item, name) in &types {
+            let ty = match item {
+                hir::ItemId::Udvt(udvt) => {
+                    let TyKind::Udvt(ty, _) = gcx.type_of_item(udvt.into()).kind else {
+                        continue;
+                    };
+                    ty
+                }
+                hir::ItemId::Enum(_) => gcx.types.uint(8),
+                _ => continue,
+            };
+            separate(&mut self.w);
+            let kind =
+                if let hir::ItemId::Enum(_) = item { "Enum" } else { "User-defined value type" };
+            self.w.line(&format!(
+                "/// {kind} `{}`, encoded as `{}`.",
+                gcx.item_name(item),
+                self.eip712_ty(ty)
+            ));
+            self.w.line(&format!("pub type {name} = {};", self.sol_ty(ty)));
+        }
+        for (&item, name) in &types {
+            if let hir::ItemId::Struct(strukt) = item {
+                separate(&mut self.w);
+                self.strukt(strukt, name);
+            }
+        }
+        if let Some(ctor) = ctor {
+            separate(&mut self.w);
+            self.constructor(ctor);
+        }
+        for f in functions {
+            separate(&mut self.w);
+            self.function(f.id, f.ty);
+        }
+        for event in events.clone() {
+            separate(&mut self.w);
+            self.event(event);
+        }
+        for error in errors.clone() {
+            separate(&mut self.w);
+            self.error(error);
+        }
+
+        if !functions.is_empty() {
+            separate(&mut self.w);
+            let variants = functions.iter().map(|f| {
+                let name = self.names[&f.id.into()].clone();
+                let ty = format!("{name}Call");
+                let data_len = self.params_base_size(f.id.into());
+                (name, ty, gcx.function_selector(f.id).to_vec(), data_len)
+            });
+            self.interface_enum(c.name.as_str(), "SolCall", variants.collect());
+        }
+        if errors.clone().next().is_some() {
+            separate(&mut self.w);
+            let variants = errors.map(|error| {
+                let name = self.names[&error.into()].clone();
+                let data_len = self.params_base_size(error.into());
+                (name.clone(), name, gcx.function_selector(error).to_vec(), data_len)
+            });
+            self.interface_enum(c.name.as_str(), "SolError", variants.collect());
+        }
+        if events.clone().next().is_some() {
+            separate(&mut self.w);
+            self.events_enum(c.name.as_str(), events.collect());
+        }
+
+        self.w.line("}");
+    }
+
+    /// Collects the user-defined types that the given type depends on.
+    fn collect_types(&mut self, ty: Ty<'gcx>) {
+        match ty.kind {
+            TyKind::Ref(ty, _) | TyKind::DynArray(ty) | TyKind::Array(ty, _) => {
+                self.collect_types(ty)
+            }
+            TyKind::Struct(id) if self.types.insert(id.into(), String::new()).is_none() => {
+                for &ty in self.gcx.struct_field_types(id) {
+                    self.collect_types(ty);
+                }
+            }
+            TyKind::Enum(id) => {
+                self.types.insert(id.into(), String::new());
+            }
+            TyKind::Udvt(_, id) => {
+                self.types.insert(id.into(), String::new());
+            }
+            TyKind::FnPtr(f) => f.tys().for_each(|ty| self.collect_types(ty)),
+            _ => {}
+        }
+    }
+
+    /// Names the given items, suffixing the overloaded ones with their index.
+    fn overloaded_names(&mut self, items: impl Iterator<Item = hir::ItemId> + Clone) {
+        let gcx = self.gcx;
+        let mut counts = FxHashMap::<_, usize>::default();
+        for item in items.clone() {
+            *counts.entry(gcx.item_name(item).name).or_default() += 1;
+        }
+        let mut indexes = FxHashMap::<_, usize>::default();
+        for item in items {
+            let name = gcx.item_name(item).name;
+            let name = if counts[&name] > 1 {
+                let index = indexes.entry(name).or_default();
+                *index += 1;
+                format!("{name}_{}", *index - 1)
+            } else {
+                name.to_string()
+            };
+            self.names.insert(item, name);
+        }
+    }
+
+    /// Names the collected types, prefixing the names that collide with the name of their
+    /// contract, and then with a numeric suffix.
+    ///
+    /// Events and errors are also declared as structs, so their names are taken first.
+    fn name_types(&mut self) {
+        let gcx = self.gcx;
+        let mut names = self
+            .names
+            .iter()
+            .filter(|(item, _)| !matches!(item, hir::ItemId::Function(_)))
+            .map(|(_, name)| name.clone())
+            .collect::<FxHashSet<_>>();
+        for (&item, name) in &mut self.types {
+            let ident = gcx.item_name(item);
+            *name = ident.to_string();
+            if !names.insert(name.clone()) {
+                if let Some(c) = gcx.hir.item(item).contract() {
+                    *name = format!("{}_{ident}", gcx.item_name(c));
+                }
+                let base = name.clone();
+                let mut i = 1;
+                while !names.insert(name.clone()) {
+                    *name = format!("{base}_{i}");
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    /// Returns the fields of the given parameters.
+    fn fields(&self, params: &[hir::VariableId], tys: &[Ty<'gcx>]) -> Vec<Field> {
+        let params = params.iter().zip(tys).enumerate();
+        params.map(|(i, (&param, &ty))| self.field(i, param, ty)).collect()
+    }
+
+    fn field(&self, i: usize, param: hir::VariableId, ty: Ty<'gcx>) -> Field {
+        let name = self.gcx.hir.variable(param).name;
+        let name = field_name(i, name.as_ref().map(|name| name.as_str()));
+        Field { name, sol: self.sol_ty(ty), rust: self.rust_ty(ty) }
+    }
+
+    fn strukt(&mut self, id: hir::StructId, name: &str) {
+        let gcx = self.gcx;
+        let fields = gcx
+            .item_fields(id)
+            .enumerate()
+            .map(|(i, (ty, field))| self.field(i, field, ty))
+            .collect::<Vec<_>>();
+        let field_tys = gcx.struct_field_types(id);
+        let components = gcx
+            .item_fields(id)
+            .map(|(ty, field)| format!("{} {}", self.eip712_ty(ty), gcx.item_name(field)));
+        let sol_name = gcx.item_name(id);
+        let root = format!("{sol_name}({})", components.collect::<Vec<_>>().join(","));
+
+        self.w.line(&format!("/// Struct with the EIP-712 type `{root}`."));
+        self.struct_def(name, &fields);
+        self.w.line("");
+        self.w.line("const _: () = {");
+        self.tuple_conversions(name, &fields);
+        let tokenize = tokenize(fields.iter());
+        self.w.lines(&format!(
+            r#"
+            #[automatically_derived]
+            impl alloy_sol_types::SolValue for {name} {{
+                type SolType = Self;
+            }}
+
+            #[automatically_derived]
+            impl alloy_sol_types::private::SolTypeValue<Self> for {name} {{
+                #[inline]
+                fn stv_to_tokens(&self) -> <Self as alloy_sol_types::SolType>::Token<'_> {{
+                    {tokenize}
+                }}
+
+                #[inline]
+                fn stv_abi_encoded_size(&self) -> usize {{
+                    if let Some(size) = <Self as alloy_sol_types::SolType>::ENCODED_SIZE {{
+                        return size;
+                    }}
+                    let tuple = <UnderlyingRustTuple<'_> as ::core::convert::From<Self>>::from(self.clone());
+                    <UnderlyingSolTuple<'_> as alloy_sol_types::SolType>::abi_encoded_size(&tuple)
+                }}
+
+                #[inline]
+                fn stv_eip712_data_word(&self) -> alloy_sol_types::Word {{
+                    <Self as alloy_sol_types::SolStruct>::eip712_hash_struct(self)
+                }}
+
+                #[inline]
+                fn stv_abi_encode_packed_to(&self, out: &mut alloy_sol_types::private::Vec<u8>) {{
+                    let tuple = <UnderlyingRustTuple<'_> as ::core::convert::From<Self>>::from(self.clone());
+                    <UnderlyingSolTuple<'_> as alloy_sol_types::SolType>::abi_encode_packed_to(&tuple, out)
+                }}
+
+                #[inline]
+                fn stv_abi_packed_encoded_size(&self) -> usize {{
+                    if let Some(size) = <Self as alloy_sol_types::SolType>::PACKED_ENCODED_SIZE {{
+                        return size;
+                    }}
+                    let tuple = <UnderlyingRustTuple<'_> as ::core::convert::From<Self>>::from(self.clone());
+                    <UnderlyingSolTuple<'_> as alloy_sol_types::SolType>::abi_packed_encoded_size(&tuple)
+                }}
+            }}
+
+            #[automatically_derived]
+            impl alloy_sol_types::SolType for {name} {{
+                type RustType = Self;
+                type Token<'a> = <UnderlyingSolTuple<'a> as alloy_sol_types::SolType>::Token<'a>;
+
+                const SOL_NAME: &'static str = <Self as alloy_sol_types::SolStruct>::NAME;
+                const ENCODED_SIZE: Option<usize> = <UnderlyingSolTuple<'_> as alloy_sol_types::SolType>::ENCODED_SIZE;
+                const PACKED_ENCODED_SIZE: Option<usize> = <UnderlyingSolTuple<'_> as alloy_sol_types::SolType>::PACKED_ENCODED_SIZE;
+
+                #[inline]
+                fn valid_token(token: &Self::Token<'_>) -> bool {{
+                    <UnderlyingSolTuple<'_> as alloy_sol_types::SolType>::valid_token(token)
+                }}
+
+                #[inline]
+                fn detokenize(token: Self::Token<'_>) -> Self::RustType {{
+                    let tuple = <UnderlyingSolTuple<'_> as alloy_sol_types::SolType>::detokenize(token);
+                    <Self as ::core::convert::From<UnderlyingRustTuple<'_>>>::from(tuple)
+                }}
+            }}
+
+            #[automatically_derived]
+            impl alloy_sol_types::SolStruct for {name} {{
+                const NAME: &'static str = "{sol_name}";
+
+                #[inline]
+                fn eip712_root_type() -> alloy_sol_types::private::Cow<'static, str> {{
+                    alloy_sol_types::private::Cow::Borrowed("{root}")
+                }}
+
+                #[inline]
+                fn eip712_components() -> alloy_sol_types::private::Vec<alloy_sol_types::private::Cow<'static, str>> {{
+            "#
+        ));
+
+        // The structs that the fields depend on, directly or through arrays.
+        let custom =
+            field_tys.iter().filter_map(|&ty| self.struct_component(ty)).collect::<Vec<_>>();
+        if custom.is_empty() {
+            self.w.lines(
+                r#"
+                    alloy_sol_types::private::Vec::new()
+                }
+
+                #[inline]
+                fn eip712_encode_type() -> alloy_sol_types::private::Cow<'static, str> {
+                    <Self as alloy_sol_types::SolStruct>::eip712_root_type()
+                }
+                "#,
+            );
+        } else {
+            self.w.line(&format!(
+                "let mut components = alloy_sol_types::private::Vec::with_capacity({});",
+                custom.len()
+            ));
+            for ty in &custom {
+                self.w.line(&format!(
+                    "components.push(<{ty} as alloy_sol_types::SolStruct>::eip712_root_type());"
+                ));
+                self.w.line(&format!(
+                    "components.extend(<{ty} as alloy_sol_types::SolStruct>::eip712_components());"
+                ));
+            }
+            self.w.line("components");
+            self.w.line("}");
+        }
+
+        let encode_data = if let [field] = &fields[..] {
+            format!(
+                "<{} as alloy_sol_types::SolType>::eip712_data_word(&self.{}).0.to_vec()",
+                field.sol, field.name
+            )
+        } else {
+            let words = fields.iter().map(|field| {
+                format!(
+                    "<{} as alloy_sol_types::SolType>::eip712_data_word(&self.{}).0,",
+                    field.sol, field.name
+                )
+            });
+            format!("[\n{}\n].concat()", words.collect::<Vec<_>>().join("\n"))
+        };
+        let preimage_length = fields.iter().map(|field| {
+            format!(
+                "+ <{} as alloy_sol_types::EventTopic>::topic_preimage_length(&rust.{})",
+                field.sol, field.name
+            )
+        });
+        let encode_preimage = fields.iter().map(|field| {
+            format!(
+                "<{} as alloy_sol_types::EventTopic>::encode_topic_preimage(&rust.{}, out);",
+                field.sol, field.name
+            )
+        });
+        self.w.lines(&format!(
+            r#"
+                #[inline]
+                fn eip712_encode_data(&self) -> alloy_sol_types::private::Vec<u8> {{
+                    {encode_data}
+                }}
+            }}
+
+            #[automatically_derived]
+            impl alloy_sol_types::EventTopic for {name} {{
+                #[inline]
+                fn topic_preimage_length(rust: &Self::RustType) -> usize {{
+                    0usize
+                    {preimage_length}
+                }}
+
+                #[inline]
+                fn encode_topic_preimage(rust: &Self::RustType, out: &mut alloy_sol_types::private::Vec<u8>) {{
+                    out.reserve(<Self as alloy_sol_types::EventTopic>::topic_preimage_length(rust));
+                    {encode_preimage}
+                }}
+
+                #[inline]
+                fn encode_topic(rust: &Self::RustType) -> alloy_sol_types::abi::token::WordToken {{
+                    let mut out = alloy_sol_types::private::Vec::new();
+                    <Self as alloy_sol_types::EventTopic>::encode_topic_preimage(rust, &mut out);
+                    alloy_sol_types::abi::token::WordToken(alloy_sol_types::private::keccak256(out))
+                }}
+            }}
+            }};
+            "#,
+            preimage_length = preimage_length.collect::<Vec<_>>().join("\n"),
+            encode_preimage = encode_preimage.collect::<Vec<_>>().join("\n"),
+        ));
+    }
+
+    /// Returns the name of the struct in the given type, if any, peeling arrays.
+    fn struct_component(&self, ty: Ty<'gcx>) -> Option<String> {
+        match ty.kind {
+            TyKind::Ref(ty, _) | TyKind::DynArray(ty) | TyKind::Array(ty, _) => {
+                self.struct_component(ty)
+            }
+            TyKind::Struct(id) => Some(self.types[&hir::ItemId::from(id)].clone()),
+            _ => None,
+        }
+    }
+
+    fn constructor(&mut self, id: hir::FunctionId) {
+        let gcx = self.gcx;
+        let fields =
+            self.fields(gcx.hir.function(id).parameters, gcx.item_parameter_types(id.into()));
+        let params = tuple(fields.iter().map(|field| &field.sol));
+        let tokenize = tokenize(fields.iter());
+
+        self.w.line("/// Constructor call.");
+        self.struct_def("constructorCall", &fields);
+        self.w.line("");
+        self.w.line("const _: () = {");
+        self.tuple_conversions("constructorCall", &fields);
+        self.w.lines(&format!(
+            r#"
+            #[automatically_derived]
+            impl alloy_sol_types::SolConstructor for constructorCall {{
+                type Parameters<'a> = {params};
+                type Token<'a> = <Self::Parameters<'a> as alloy_sol_types::SolType>::Token<'a>;
+
+                #[inline]
+                fn new<'a>(tuple: <Self::Parameters<'a> as alloy_sol_types::SolType>::RustType) -> Self {{
+                    tuple.into()
+                }}
+
+                #[inline]
+                fn tokenize(&self) -> Self::Token<'_> {{
+                    {tokenize}
+                }}
+            }}
+            }};
+            "#
+        ));
+    }
+
+    fn function(&mut self, id: hir::FunctionId, ty: Ty<'gcx>) {
+        let gcx = self.gcx;
+        let TyKind::FnPtr(ty) = ty.kind else { unreachable!() };
+        let f = gcx.hir.function(id);
+        let name = &self.names[&id.into()].clone();
+        let call = format!("{name}Call");
+        let ret = format!("{name}Return");
+        let params = self.fields(f.parameters, ty.parameters);
+        let returns = self.fields(f.returns, ty.returns);
+        let signature = gcx.item_signature(id.into());
+        let selector = hex::encode(gcx.function_selector(id));
+        let params_tuple = tuple(params.iter().map(|field| &field.sol));
+        let returns_tuple = tuple(returns.iter().map(|field| &field.sol));
+        let tokenize = tokenize(params.iter());
+
+        self.w.line(&format!(
+            "/// Function with signature `{signature}` and selector `0x{selector}`."
+        ));
+        self.struct_def(&call, &params);
+        self.w.line("");
+        self.w.line(&format!(
+            "/// Container type for the return parameters of the [`{signature}`]({call}) function."
+        ));
+        self.struct_def(&ret, &returns);
+        self.w.line("");
+        self.w.line("const _: () = {");
+        self.w.line("{");
+        self.tuple_conversions(&call, &params);
+        self.w.line("}");
+        self.w.line("{");
+        self.tuple_conversions(&ret, &returns);
+        self.w.line("}");
+        self.w.lines(&format!(
+            r#"
+            #[automatically_derived]
+            impl alloy_sol_types::SolCall for {call} {{
+                type Parameters<'a> = {params_tuple};
+                type Token<'a> = <Self::Parameters<'a> as alloy_sol_types::SolType>::Token<'a>;
+
+                type Return = {ret};
+
+                type ReturnTuple<'a> = {returns_tuple};
+                type ReturnToken<'a> = <Self::ReturnTuple<'a> as alloy_sol_types::SolType>::Token<'a>;
+
+                const SIGNATURE: &'static str = "{signature}";
+                const SELECTOR: [u8; 4] = alloy_sol_types::private::primitives::hex!("{selector}");
+
+                #[inline]
+                fn new<'a>(tuple: <Self::Parameters<'a> as alloy_sol_types::SolType>::RustType) -> Self {{
+                    tuple.into()
+                }}
+
+                #[inline]
+                fn tokenize(&self) -> Self::Token<'_> {{
+                    {tokenize}
+                }}
+
+                #[inline]
+                fn abi_decode_returns(data: &[u8], validate: bool) -> alloy_sol_types::Result<Self::Return> {{
+                    <Self::ReturnTuple<'_> as alloy_sol_types::SolType>::abi_decode_sequence(data, validate).map(Into::into)
+                }}
+            }}
+            }};
+            "#
+        ));
+    }
+
+    fn event(&mut self, id: hir::EventId) {
+        let gcx = self.gcx;
+        let event = gcx.hir.event(id);
+        let name = &self.names[&id.into()].clone();
+        let signature = gcx.item_signature(id.into());
+        let selector = hex::encode(gcx.event_selector(id));
+        let anonymous = event.anonymous;
+
+        let mut fields = Vec::new();
+        // The indexed parameters, and whether they are hashed in the topic.
+        let mut topics = Vec::new();
+        let mut data = Vec::new();
+        let mut new = Vec::new();
+        for (i, (ty, param)) in gcx.item_fields(id).enumerate() {
+            let mut field = self.field(i, param, ty);
+            if gcx.hir.variable(param).indexed {
+                let hashed = !ty.peel_refs().is_value_type();
+                if hashed {
+                    field.sol = "alloy_sol_types::sol_data::FixedBytes<32>".into();
+                    field.rust = "alloy_sol_types::private::FixedBytes<32>".into();
+                }
+                new.push(format!("{}: topics.{},", field.name, topics.len() + !anonymous as usize));
+                topics.push(fields.len());
+            } else {
+                new.push(format!("{}: data.{},", field.name, data.len()));
+                data.push(fields.len());
+            }
+            fields.push(field);
+        }
+
+        let first_topic =
+            (!anonymous).then(|| "alloy_sol_types::sol_data::FixedBytes<32>".to_string());
+        let topic_list = tuple(first_topic.iter().chain(topics.iter().map(|&i| &fields[i].sol)));
+        let data_tuple = tuple(data.iter().map(|&i| &fields[i].sol));
+        let first_topic = (!anonymous).then(|| "Self::SIGNATURE_HASH.into()".to_string());
+        let topic_values = topics.iter().map(|&i| format!("self.{}.clone()", fields[i].name));
+        let topic_values = tuple(first_topic.iter().cloned().chain(topic_values));
+        let first_topic = (!anonymous)
+            .then(|| "alloy_sol_types::abi::token::WordToken(Self::SIGNATURE_HASH)".to_string());
+        let encode_topics = topics.iter().map(|&i| {
+            let field = &fields[i];
+            format!(
+                "<{} as alloy_sol_types::EventTopic>::encode_topic(&self.{})",
+                field.sol, field.name
+            )
+        });
+        let encode_topics = first_topic.into_iter().chain(encode_topics).enumerate();
+        let encode_topics = encode_topics.map(|(i, topic)| format!("out[{i}] = {topic};"));
+        let tokenize_body = tokenize(data.iter().map(|&i| &fields[i]));
+        let check_signature = if anonymous {
+            String::new()
+        } else {
+            r#"
+            #[inline]
+            fn check_signature(topics: &<Self::TopicList as alloy_sol_types::SolType>::RustType) -> alloy_sol_types::Result<()> {
+                if topics.0 != Self::SIGNATURE_HASH {
+                    return Err(alloy_sol_types::Error::invalid_event_signature_hash(Self::SIGNATURE, topics.0, Self::SIGNATURE_HASH));
+                }
+                Ok(())
+            }
+            "#
+            .into()
+        };
+
+        self.w
+            .line(&format!("/// Event with signature `{signature}` and selector `0x{selector}`."));
+        self.struct_def(name, &fields);
+        self.w.line("");
+        self.w.lines(&format!(
+            r#"
+            const _: () = {{
+            #[automatically_derived]
+            impl alloy_sol_types::SolEvent for {name} {{
+                type DataTuple<'a> = {data_tuple};
+                type DataToken<'a> = <Self::DataTuple<'a> as alloy_sol_types::SolType>::Token<'a>;
+
+                type TopicList = {topic_list};
+
+                const SIGNATURE: &'static str = "{signature}";
+                const SIGNATURE_HASH: alloy_sol_types::private::B256 = alloy_sol_types::private::B256::new(alloy_sol_types::private::primitives::hex!("{selector}"));
+
+                const ANONYMOUS: bool = {anonymous};
+
+                #[allow(unused_variables)]
+                #[inline]
+                fn new(
+                    topics: <Self::TopicList as alloy_sol_types::SolType>::RustType,
+                    data: <Self::DataTuple<'_> as alloy_sol_types::SolType>::RustType,
+                ) -> Self {{
+                    Self {{
+                        {new}
+                    }}
+                }}
+                {check_signature}
+                #[inline]
+                fn tokenize_body(&self) -> Self::DataToken<'_> {{
+                    {tokenize_body}
+                }}
+
+                #[inline]
+                fn topics(&self) -> <Self::TopicList as alloy_sol_types::SolType>::RustType {{
+                    {topic_values}
+                }}
+
+                #[inline]
+                fn encode_topics_raw(&self, out: &mut [alloy_sol_types::abi::token::WordToken]) -> alloy_sol_types::Result<()> {{
+                    if out.len() < <Self::TopicList as alloy_sol_types::TopicList>::COUNT {{
+                        return Err(alloy_sol_types::Error::Overrun);
+                    }}
+                    {encode_topics}
+                    Ok(())
+                }}
+            }}
+
+            #[automatically_derived]
+            impl alloy_sol_types::private::IntoLogData for {name} {{
+                fn to_log_data(&self) -> alloy_sol_types::private::LogData {{
+                    From::from(self)
+                }}
+
+                fn into_log_data(self) -> alloy_sol_types::private::LogData {{
+                    From::from(&self)
+                }}
+            }}
+
+            #[automatically_derived]
+            impl From<&{name}> for alloy_sol_types::private::LogData {{
+                #[inline]
+                fn from(this: &{name}) -> alloy_sol_types::private::LogData {{
+                    alloy_sol_types::SolEvent::encode_log_data(this)
+                }}
+            }}
+            }};
+            "#,
+            new = new.join("\n"),
+            encode_topics = encode_topics.collect::<Vec<_>>().join("\n"),
+        ));
+    }
+
+    fn error(&mut self, id: hir::ErrorId) {
+        let gcx = self.gcx;
+        let name = &self.names[&id.into()].clone();
+        let fields = self.fields(gcx.hir.error(id).parameters, gcx.item_parameter_types(id.into()));
+        let signature = gcx.item_signature(id.into());
+        let selector = hex::encode(gcx.function_selector(id));
+        let tokenize = tokenize(fields.iter());
+
+        self.w.line(&format!(
+            "/// Custom error with signature `{signature}` and selector `0x{selector}`."
+        ));
+        self.struct_def(name, &fields);
+        self.w.line("");
+        self.w.line("const _: () = {");
+        self.tuple_conversions(name, &fields);
+        self.w.lines(&format!(
+            r#"
+            #[automatically_derived]
+            impl alloy_sol_types::SolError for {name} {{
+                type Parameters<'a> = UnderlyingSolTuple<'a>;
+                type Token<'a> = <Self::Parameters<'a> as alloy_sol_types::SolType>::Token<'a>;
+
+                const SIGNATURE: &'static str = "{signature}";
+                const SELECTOR: [u8; 4] = alloy_sol_types::private::primitives::hex!("{selector}");
+
+                #[inline]
+                fn new<'a>(tuple: <Self::Parameters<'a> as alloy_sol_types::SolType>::RustType) -> Self {{
+                    tuple.into()
+                }}
+
+                #[inline]
+                fn tokenize(&self) -> Self::Token<'_> {{
+                    {tokenize}
+                }}
+            }}
+            }};
+            "#
+        ));
+    }
+
+    /// Writes the enum of the given calls or errors, and its `SolInterface` implementation.
+    ///
+    /// The variants are `(name, type, selector, base data size)`.
+    fn interface_enum(
+        &mut self,
+        contract: &str,
+        trait_: &str,
+        variants: Vec<(String, String, Vec<u8>, usize)>,
+    ) {
+        let (name, what) = match trait_ {
+            "SolCall" => (format!("{contract}Calls"), "function calls"),
+            _ => (format!("{contract}Errors"), "custom errors"),
+        };
+        // The selectors are sorted for binary search, and the decoding functions in the same order.
+        let mut sorted = variants.iter().collect::<Vec<_>>();
+        sorted.sort_by(|a, b| a.2.cmp(&b.2));
+        let min_data_len = variants.iter().map(|v| v.3).min().unwrap_or(0);
+        let count = variants.len();
+
+        self.w.line(&format!("/// Container for all the `{contract}` {what}."));
+        self.w.line("#[derive(Clone)]");
+        self.w.line(&format!("pub enum {name} {{"));
+        for (variant, ty, ..) in &variants {
+            self.w.line(&format!("{variant}({ty}),"));
+        }
+        self.w.line("}");
+        self.w.lines(&format!(
+            r#"
+            impl {name} {{
+                /// All the selectors of this enum, sorted.
+                pub const SELECTORS: &'static [[u8; 4]] = &[
+                    {selectors}
+                ];
+            }}
+
+            #[automatically_derived]
+            impl alloy_sol_types::SolInterface for {name} {{
+                const NAME: &'static str = "{name}";
+                const MIN_DATA_LENGTH: usize = {min_data_len};
+                const COUNT: usize = {count};
+
+                #[inline]
+                fn selector(&self) -> [u8; 4] {{
+                    match self {{
+                        {selector_arms}
+                    }}
+                }}
+
+                #[inline]
+                fn selector_at(i: usize) -> ::core::option::Option<[u8; 4]> {{
+                    Self::SELECTORS.get(i).copied()
+                }}
+
+                #[inline]
+                fn valid_selector(selector: [u8; 4]) -> bool {{
+                    Self::SELECTORS.binary_search(&selector).is_ok()
+                }}
+
+                #[inline]
+                fn abi_decode_raw(selector: [u8; 4], data: &[u8], validate: bool) -> alloy_sol_types::Result<Self> {{
+                    static DECODE_SHIMS: &[fn(&[u8], bool) -> alloy_sol_types::Result<{name}>] = &[
+                        {decode_shims}
+                    ];
+                    let Ok(idx) = Self::SELECTORS.binary_search(&selector) else {{
+                        return Err(alloy_sol_types::Error::unknown_selector(<Self as alloy_sol_types::SolInterface>::NAME, selector));
+                    }};
+                    DECODE_SHIMS[idx](data, validate)
+                }}
+
+                #[inline]
+                fn abi_encoded_size(&self) -> usize {{
+                    match self {{
+                        {encoded_size_arms}
+                    }}
+                }}
+
+                #[inline]
+                fn abi_encode_raw(&self, out: &mut alloy_sol_types::private::Vec<u8>) {{
+                    match self {{
+                        {encode_arms}
+                    }}
+                }}
+            }}
+            "#,
+            selectors = sorted
+                .iter()
+                .map(|v| format!("alloy_sol_types::private::primitives::hex!(\"{}\"),", hex::encode(&v.2)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            selector_arms = variants
+                .iter()
+                .map(|(v, ty, ..)| format!("Self::{v}(_) => <{ty} as alloy_sol_types::{trait_}>::SELECTOR,"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            decode_shims = sorted
+                .iter()
+                .map(|(v, ty, ..)| format!(
+                    "{{\nfn {v}(data: &[u8], validate: bool) -> alloy_sol_types::Result<{name}> {{\n<{ty} as alloy_sol_types::{trait_}>::abi_decode_raw(data, validate).map({name}::{v})\n}}\n{v}\n}},"
+                ))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            encoded_size_arms = variants
+                .iter()
+                .map(|(v, ty, ..)| format!("Self::{v}(inner) => <{ty} as alloy_sol_types::{trait_}>::abi_encoded_size(inner),"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            encode_arms = variants
+                .iter()
+                .map(|(v, ty, ..)| format!("Self::{v}(inner) => <{ty} as alloy_sol_types::{trait_}>::abi_encode_raw(inner, out),"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ));
+    }
+
+    /// Writes the enum of the given events, and its `SolEventInterface` implementation.
+    fn events_enum(&mut self, contract: &str, events: Vec<hir::EventId>) {
+        let gcx = self.gcx;
+        let name = format!("{contract}Events");
+        let mut selectors =
+            events.iter().map(|&event| gcx.event_selector(event)).collect::<Vec<_>>();
+        selectors.sort_unstable();
+        let variants = events.iter().map(|event| &self.names[&(*event).into()]).collect::<Vec<_>>();
+        let (anonymous, non_anonymous): (Vec<_>, Vec<_>) =
+            events.iter().zip(&variants).partition(|(&event, _)| gcx.hir.event(event).anonymous);
+
+        let invalid_log = r#"alloy_sol_types::private::Err(alloy_sol_types::Error::InvalidLog {
+            name: <Self as alloy_sol_types::SolEventInterface>::NAME,
+            log: alloy_sol_types::private::Box::new(alloy_sol_types::private::LogData::new_unchecked(topics.to_vec(), data.to_vec().into())),
+            })"#;
+        let mut decode = String::new();
+        if !non_anonymous.is_empty() {
+            let ret = if anonymous.is_empty() { "" } else { "return " };
+            decode.push_str("match topics.first().copied() {\n");
+            for (_, variant) in &non_anonymous {
+                writeln!(
+                    decode,
+                    "Some(<{variant} as alloy_sol_types::SolEvent>::SIGNATURE_HASH) => {ret}<{variant} as alloy_sol_types::SolEvent>::decode_raw_log(topics, data, validate).map(Self::{variant}),"
+                )
+                .unwrap();
+            }
+            if anonymous.is_empty() {
+                writeln!(decode, "_ => {invalid_log},\n}}").unwrap();
+            } else {
+                decode.push_str("_ => {}\n}\n");
+            }
+        }
+        if !anonymous.is_empty() {
+            for (_, variant) in &anonymous {
+                writeln!(
+                    decode,
+                    "if let Ok(res) = <{variant} as alloy_sol_types::SolEvent>::decode_raw_log(topics, data, validate) {{\nreturn Ok(Self::{variant}(res));\n}}"
+                )
+                .unwrap();
+            }
+            decode.push_str(invalid_log);
+        }
+
+        self.w.line(&format!("/// Container for all the `{contract}` events."));
+        self.w.line("#[derive(Clone)]");
+        self.w.line(&format!("pub enum {name} {{"));
+        for variant in &variants {
+            self.w.line(&format!("{variant}({variant}),"));
+        }
+        self.w.line("}");
+        self.w.lines(&format!(
+            r#"
+            impl {name} {{
+                /// All the selectors of this enum, sorted.
+                pub const SELECTORS: &'static [[u8; 32]] = &[
+                    {selectors}
+                ];
+            }}
+
+            #[automatically_derived]
+            impl alloy_sol_types::SolEventInterface for {name} {{
+                const NAME: &'static str = "{name}";
+                const COUNT: usize = {count};
+
+                fn decode_raw_log(topics: &[alloy_sol_types::Word], data: &[u8], validate: bool) -> alloy_sol_types::Result<Self> {{
+                    {decode}
+                }}
+            }}
+
+            #[automatically_derived]
+            impl alloy_sol_types::private::IntoLogData for {name} {{
+                fn to_log_data(&self) -> alloy_sol_types::private::LogData {{
+                    match self {{
+                        {to_log_data}
+                    }}
+                }}
+
+                fn into_log_data(self) -> alloy_sol_types::private::LogData {{
+                    match self {{
+                        {into_log_data}
+                    }}
+                }}
+            }}
+            "#,
+            selectors = selectors
+                .iter()
+                .map(|selector| format!("alloy_sol_types::private::primitives::hex!(\"{}\"),", hex::encode(selector)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            count = events.len(),
+            to_log_data = variants
+                .iter()
+                .map(|v| format!("Self::{v}(inner) => alloy_sol_types::private::IntoLogData::to_log_data(inner),"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            into_log_data = variants
+                .iter()
+                .map(|v| format!("Self::{v}(inner) => alloy_sol_types::private::IntoLogData::into_log_data(inner),"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ));
+    }
+
+    fn struct_def(&mut self, name: &str, fields: &[Field]) {
+        self.w.line("#[derive(Clone)]");
+        if fields.is_empty() {
+            self.w.line(&format!("pub struct {name} {{}}"));
+            return;
+        }
+        self.w.line(&format!("pub struct {name} {{"));
+        for field in fields {
+            self.w.line(&format!("pub {}: {},", field.name, field.rust));
+        }
+        self.w.line("}");
+    }
+
+    /// Writes the underlying tuple types of the given struct, and the conversions between them.
+    fn tuple_conversions(&mut self, name: &str, fields: &[Field]) {
+        let sol = tuple(fields.iter().map(|field| &field.sol));
+        let rust = tuple(fields.iter().map(|field| &field.rust));
+        let (value, tuple_param) = if fields.is_empty() { ("_", "_") } else { ("value", "tuple") };
+        let into_tuple = tuple(fields.iter().map(|field| format!("value.{}", field.name)));
+        let from_tuple =
+            fields.iter().enumerate().map(|(i, field)| format!("{}: tuple.{i}", field.name));
+        self.w.lines(&format!(
+            r#"
+            type UnderlyingSolTuple<'a> = {sol};
+            type UnderlyingRustTuple<'a> = {rust};
+
+            #[automatically_derived]
+            impl ::core::convert::From<{name}> for UnderlyingRustTuple<'_> {{
+                fn from({value}: {name}) -> Self {{
+                    {into_tuple}
+                }}
+            }}
+
+            #[automatically_derived]
+            impl ::core::convert::From<UnderlyingRustTuple<'_>> for {name} {{
+                fn from({tuple_param}: UnderlyingRustTuple<'_>) -> Self {{
+                    Self {{ {from_tuple} }}
+                }}
+            }}
+            "#,
+            from_tuple = from_tuple.collect::<Vec<_>>().join(", "),
+        ));
+    }
+
+    /// Returns the minimum ABI-encoded size of the parameters of the given item.
+    fn params_base_size(&self, id: hir::ItemId) -> usize {
+        self.gcx.item_parameter_types(id).iter().map(|&ty| self.base_size(ty)).sum()
+    }
+
+    /// Returns the minimum ABI-encoded size of the given type, without any dynamic data.
+    fn base_size(&self, ty: Ty<'gcx>) -> usize {
+        match ty.kind {
+            TyKind::Elementary(ElementaryType::String | ElementaryType::Bytes)
+            | TyKind::DynArray(_) => 64,
+            TyKind::Array(ty, len) => {
+                let len = usize::try_from(len).unwrap_or(usize::MAX);
+                self.base_size(ty).saturating_mul(len)
+            }
+            TyKind::Struct(id) => {
+                self.gcx.struct_field_types(id).iter().map(|&ty| self.base_size(ty)).sum()
+            }
+            TyKind::Ref(ty, _) => self.base_size(ty),
+            _ => 32,
+        }
+    }
+
+    /// Returns the `alloy_sol_types::sol_data` type of the given type.
+    fn sol_ty(&self, ty: Ty<'gcx>) -> String {
+        match ty.kind {
+            TyKind::Elementary(ty) => match ty {
+                ElementaryType::Address(_) => "alloy_sol_types::sol_data::Address".into(),
+                ElementaryType::Bool => "alloy_sol_types::sol_data::Bool".into(),
+                ElementaryType::String => "alloy_sol_types::sol_data::String".into(),
+                ElementaryType::Bytes => "alloy_sol_types::sol_data::Bytes".into(),
+                ElementaryType::FixedBytes(size) => {
+                    format!("alloy_sol_types::sol_data::FixedBytes<{}>", size.bytes())
+                }
+                ElementaryType::Int(size) => {
+                    format!("alloy_sol_types::sol_data::Int<{}>", size.bits())
+                }
+                ElementaryType::UInt(size) => {
+                    format!("alloy_sol_types::sol_data::Uint<{}>", size.bits())
+                }
+                ElementaryType::Fixed(..) | ElementaryType::UFixed(..) => {
+                    panic!("invalid ABI type: {ty:?}")
+                }
+            },
+            TyKind::Contract(_) => "alloy_sol_types::sol_data::Address".into(),
+            TyKind::Struct(id) => self.types[&hir::ItemId::from(id)].clone(),
+            TyKind::Enum(id) => self.types[&hir::ItemId::from(id)].clone(),
+            TyKind::Udvt(_, id) => self.types[&hir::ItemId::from(id)].clone(),
+            TyKind::Ref(ty, _) => self.sol_ty(ty),
+            TyKind::DynArray(ty) => {
+                format!("alloy_sol_types::sol_data::Array<{}>", self.sol_ty(ty))
+            }
+            TyKind::Array(ty, len) => {
+                format!("alloy_sol_types::sol_data::FixedArray<{}, {len}>", self.sol_ty(ty))
+            }
+            TyKind::FnPtr(_) => "alloy_sol_types::sol_data::Function".into(),
+            _ => panic!("invalid ABI type: {ty:?}"),
+        }
+    }
+
+    /// Returns the Rust type of the given type, which is the `SolType::RustType` of its
+    /// [`sol_ty`](Self::sol_ty).
+    fn rust_ty(&self, ty: Ty<'gcx>) -> String {
+        match ty.kind {
+            TyKind::Elementary(ty) => match ty {
+                ElementaryType::Address(_) => "alloy_sol_types::private::Address".into(),
+                ElementaryType::Bool => "bool".into(),
+                ElementaryType::String => "alloy_sol_types::private::String".into(),
+                ElementaryType::Bytes => "alloy_sol_types::private::Bytes".into(),
+                ElementaryType::FixedBytes(size) => {
+                    format!("alloy_sol_types::private::FixedBytes<{}>", size.bytes())
+                }
+                ElementaryType::Int(size) | ElementaryType::UInt(size) => {
+                    let signed = matches!(ty, ElementaryType::Int(_));
+                    match size.bits() {
+                        bits @ (8 | 16 | 32 | 64 | 128) => {
+                            format!("{}{bits}", if signed { 'i' } else { 'u' })
+                        }
+                        bits => format!(
+                            "alloy_sol_types::private::primitives::aliases::{}{bits}",
+                            if signed { 'I' } else { 'U' }
+                        ),
+                    }
+                }
+                ElementaryType::Fixed(..) | ElementaryType::UFixed(..) => {
+                    panic!("invalid ABI type: {ty:?}")
+                }
+            },
+            TyKind::Contract(_) => "alloy_sol_types::private::Address".into(),
+            TyKind::Struct(id) => self.types[&hir::ItemId::from(id)].clone(),
+            TyKind::Enum(_) => "u8".into(),
+            TyKind::Udvt(ty, _) | TyKind::Ref(ty, _) => self.rust_ty(ty),
+            TyKind::DynArray(ty) => format!("alloy_sol_types::private::Vec<{}>", self.rust_ty(ty)),
+            TyKind::Array(ty, len) => format!("[{}; {len}]", self.rust_ty(ty)),
+            TyKind::FnPtr(_) => "alloy_sol_types::private::Function".into(),
+            _ => panic!("invalid ABI type: {ty:?}"),
+        }
+    }
+
+    /// Returns the name of the given type in an EIP-712 type: structs keep their Solidity names,
+    /// even if they are renamed in the module, and the other user-defined types are replaced by
+    /// their underlying types.
+    fn eip712_ty(&self, ty: Ty<'gcx>) -> String {
+        match ty.kind {
+            TyKind::Elementary(ty) => {
+                let mut s = String::new();
+                ty.write_abi_str(&mut s).unwrap();
+                s
+            }
+            TyKind::Contract(_) => "address".into(),
+            TyKind::Struct(id) => self.gcx.item_name(id).to_string(),
+            TyKind::Enum(_) => "uint8".into(),
+            TyKind::Udvt(ty, _) | TyKind::Ref(ty, _) => self.eip712_ty(ty),
+            TyKind::DynArray(ty) => format!("{}[]", self.eip712_ty(ty)),
+            TyKind::Array(ty, len) => format!("{}[{len}]", self.eip712_ty(ty)),
+            TyKind::FnPtr(_) => "function".into(),
+            _ => panic!("invalid ABI type: {ty:?}"),
+        }
+    }
+}
+
+/// Writes lines of code, indenting them by the nesting of the brackets that end and start them.
+#[derive(Default)]
+struct Writer {
+    out: String,
+    indent: usize,
+}
+
+impl Writer {
+    /// Writes the given lines, ignoring their indentation and the surrounding empty lines.
+    fn lines(&mut self, lines: &str) {
+        for line in lines.trim_matches(|c: char| c == '\n' || c == ' ').lines() {
+            self.line(line);
+        }
+    }
+
+    fn line(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            self.out.push('\n');
+            return;
+        }
+        if line.starts_with(['}', ')', ']']) {
+            self.indent -= 1;
+        }
+        writeln!(self.out, "{:indent$}{line}", "", indent = self.indent * 4).unwrap();
+        if line.ends_with(['{', '(', '[']) {
+            self.indent += 1;
+        }
+    }
+}
+
+/// Formats the given types or values as a tuple.
+fn tuple<T: AsRef<str>>(items: impl Iterator<Item = T>) -> String {
+    let items = items.map(|item| item.as_ref().to_string()).collect::<Vec<_>>();
+    match &items[..] {
+        [item] => format!("({item},)"),
+        items => format!("({})", items.join(", ")),
+    }
+}
+
+/// Returns the tokens of the given fields of `self`.
+fn tokenize<'a>(fields: impl Iterator<Item = &'a Field>) -> String {
+    tuple(fields.map(|field| {
+        format!("<{} as alloy_sol_types::SolType>::tokenize(&self.{})", field.sol, field.name)
+    }))
+}
+
+/// Returns the Rust name of the field at the given index: its Solidity name, escaped if it is a
+/// Rust keyword, or `_<index>` if it is unnamed.
+fn field_name(i: usize, name: Option<&str>) -> String {
+    match name {
+        None | Some("_") => format!("_{i}"),
+        Some(name @ ("self" | "super" | "crate")) => format!("{name}_"),
+        Some(name) if RUST_KEYWORDS.contains(&name) => format!("r#{name}"),
+        Some(name) => name.to_string(),
+    }
+}
+
+/// The Rust keywords which are valid Solidity identifiers and can be used as raw identifiers.
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "become", "box", "dyn", "extern", "fn", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "priv", "pub", "ref", "static", "trait", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "where", "yield",
+];
+
+/// Converts the given `PascalCase` name to `snake_case`.
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut s = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(char::is_ascii_lowercase);
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                s.push('_');
+            }
+        }
+        s.push(c.to_ascii_lowercase());
+    }
+    s
+}
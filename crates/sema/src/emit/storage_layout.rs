@@ -0,0 +1,314 @@
+//! Solc-compatible `storageLayout` JSON.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html#json-output>
+
+use crate::{
+    hir,
+    ty::{Gcx, StorageItems, StorageNamespace, Ty, TyKind},
+};
+use serde::{Deserialize, Serialize};
+use solar_ast::ast::{ElementaryType, StateMutability, Visibility};
+use solar_data_structures::{index::Idx, map::FxHashMap};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct StorageLayoutJson {
+    /// The ERC-7201 namespaced storage structs, keyed by `erc7201:<namespace>`.
+    ///
+    /// This is not part of the solc output, and is only present if there are any namespaces.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) namespaces: Option<BTreeMap<String, Vec<StorageJson>>>,
+    pub(crate) storage: Vec<StorageJson>,
+    pub(crate) types: Option<BTreeMap<String, TypeJson>>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StorageJson {
+    pub(crate) ast_id: usize,
+    pub(crate) contract: String,
+    pub(crate) label: String,
+    pub(crate) offset: u8,
+    pub(crate) slot: String,
+    #[serde(rename = "type")]
+    pub(crate) ty: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TypeJson {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) base: Option<String>,
+    pub(crate) encoding: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) key: Option<String>,
+    pub(crate) label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) members: Option<Vec<StorageJson>>,
+    pub(crate) number_of_bytes: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) value: Option<String>,
+}
+
+/// Returns the JSON representation of the given storage layout of the contract `id`.
+pub(crate) fn storage_layout_json<'gcx>(
+    gcx: Gcx<'gcx>,
+    id: hir::ContractId,
+    items: StorageItems<'gcx>,
+    namespaces: &[StorageNamespace<'gcx>],
+) -> StorageLayoutJson {
+    let mut builder = Builder {
+        gcx,
+        contract: gcx.contract_fully_qualified_name(id).to_string(),
+        types: BTreeMap::new(),
+    };
+    let storage = builder.items(items);
+    let namespaces = (!namespaces.is_empty()).then(|| {
+        namespaces
+            .iter()
+            .map(|ns| (format!("erc7201:{}", ns.namespace), builder.items(ns.items)))
+            .collect()
+    });
+    let types = (!builder.types.is_empty()).then_some(builder.types);
+    StorageLayoutJson { namespaces, storage, types }
+}
+
+struct Builder<'gcx> {
+    gcx: Gcx<'gcx>,
+    contract: String,
+    types: BTreeMap<String, TypeJson>,
+}
+
+/// The data location suffix of a type identifier.
+#[derive(Clone, Copy)]
+pub(super) enum Loc {
+    Storage,
+    StoragePtr,
+    MemoryPtr,
+    CalldataPtr,
+}
+
+impl Loc {
+    fn suffix(self) -> &'static str {
+        match self {
+            Self::Storage => "_storage",
+            Self::StoragePtr => "_storage_ptr",
+            Self::MemoryPtr => "_memory_ptr",
+            Self::CalldataPtr => "_calldata_ptr",
+        }
+    }
+}
+
+impl<'gcx> Builder<'gcx> {
+    fn names(&self) -> TypeNames<'_, 'gcx> {
+        TypeNames { gcx: self.gcx, ast_ids: None }
+    }
+
+    fn items(&mut self, items: StorageItems<'gcx>) -> Vec<StorageJson> {
+        items
+            .iter()
+            .map(|item| StorageJson {
+                ast_id: item.id.index(),
+                contract: self.contract.clone(),
+                label: self.gcx.item_name(item.id).to_string(),
+                offset: item.offset,
+                slot: item.slot.to_string(),
+                ty: self.add_type(item.ty, Loc::Storage),
+            })
+            .collect()
+    }
+
+    /// Adds the given type and its component types to the type map, returning its identifier.
+    fn add_type(&mut self, ty: Ty<'gcx>, loc: Loc) -> String {
+        let ty = ty.peel_refs();
+        let id = self.names().identifier(ty, loc);
+        if self.types.contains_key(&id) {
+            return id;
+        }
+
+        let gcx = self.gcx;
+        let size = gcx.type_storage_size(ty).map(|s| s.number_of_bytes()).unwrap_or_default();
+        let mut json = TypeJson {
+            base: None,
+            encoding: "inplace".into(),
+            key: None,
+            label: self.names().label(ty),
+            members: None,
+            number_of_bytes: size.to_string(),
+            value: None,
+        };
+        // Insert first to avoid infinite recursion on recursive types.
+        self.types.insert(id.clone(), json.clone());
+        match ty.kind {
+            TyKind::Elementary(ElementaryType::String | ElementaryType::Bytes) => {
+                json.encoding = "bytes".into();
+            }
+            TyKind::DynArray(elem) => {
+                json.encoding = "dynamic_array".into();
+                json.base = Some(self.add_type(elem, loc));
+            }
+            TyKind::Array(elem, _) => json.base = Some(self.add_type(elem, loc)),
+            TyKind::Mapping(key, value) => {
+                json.encoding = "mapping".into();
+                json.key = Some(self.add_type(key, Loc::MemoryPtr));
+                json.value = Some(self.add_type(value, Loc::Storage));
+            }
+            TyKind::Struct(id) => {
+                if let Some(members) = gcx.struct_storage_layout(id) {
+                    json.members = Some(self.items(members));
+                }
+            }
+            _ => {}
+        }
+        self.types.insert(id.clone(), json);
+        id
+    }
+}
+
+/// Formats solc type identifiers and labels.
+pub(super) struct TypeNames<'a, 'gcx> {
+    pub(super) gcx: Gcx<'gcx>,
+    /// The AST IDs of user-defined types, which are part of their identifiers. Defaults to the
+    /// HIR IDs.
+    pub(super) ast_ids: Option<&'a FxHashMap<hir::ItemId, usize>>,
+}
+
+impl<'gcx> TypeNames<'_, 'gcx> {
+    fn ast_id<I: Idx + Into<hir::ItemId>>(&self, id: I) -> usize {
+        match self.ast_ids {
+            Some(ids) => ids[&id.into()],
+            None => id.index(),
+        }
+    }
+
+    /// Returns the solc type identifier of the given type, e.g. `t_array(t_uint256)dyn_storage`.
+    pub(super) fn identifier(&self, ty: Ty<'gcx>, loc: Loc) -> String {
+        let mut s = String::from("t_");
+        self.write_identifier(&mut s, ty, loc).unwrap();
+        s
+    }
+
+    fn write_identifier(&self, s: &mut String, ty: Ty<'gcx>, loc: Loc) -> fmt::Result {
+        let gcx = self.gcx;
+        match ty.peel_refs().kind {
+            TyKind::Elementary(ty) => match ty {
+                ElementaryType::Address(false) => write!(s, "address"),
+                ElementaryType::Address(true) => write!(s, "address_payable"),
+                ElementaryType::Bool => write!(s, "bool"),
+                ElementaryType::String => write!(s, "string{}", loc.suffix()),
+                ElementaryType::Bytes => write!(s, "bytes{}", loc.suffix()),
+                ElementaryType::Fixed(m, n) => write!(s, "fixed{}x{}", m.bits(), n.get()),
+                ElementaryType::UFixed(m, n) => write!(s, "ufixed{}x{}", m.bits(), n.get()),
+                ElementaryType::Int(size) => write!(s, "int{}", size.bits()),
+                ElementaryType::UInt(size) => write!(s, "uint{}", size.bits()),
+                ElementaryType::FixedBytes(size) => write!(s, "bytes{}", size.bytes()),
+            },
+            TyKind::Contract(id) => {
+                write!(s, "contract({}){}", gcx.item_name(id), self.ast_id(id))
+            }
+            TyKind::Enum(id) => write!(s, "enum({}){}", gcx.item_name(id), self.ast_id(id)),
+            TyKind::Udvt(_, id) => {
+                write!(s, "userDefinedValueType({}){}", gcx.item_name(id), self.ast_id(id))
+            }
+            TyKind::Struct(id) => {
+                let ast_id = self.ast_id(id);
+                write!(s, "struct({}){ast_id}{}", gcx.item_name(id), loc.suffix())
+            }
+            TyKind::DynArray(elem) => {
+                s.push_str("array(");
+                s.push_str(&self.identifier(elem, loc));
+                write!(s, ")dyn{}", loc.suffix())
+            }
+            TyKind::Array(elem, len) => {
+                s.push_str("array(");
+                s.push_str(&self.identifier(elem, loc));
+                write!(s, "){len}{}", loc.suffix())
+            }
+            TyKind::Mapping(key, value) => {
+                s.push_str("mapping(");
+                s.push_str(&self.identifier(key, Loc::MemoryPtr));
+                s.push(',');
+                s.push_str(&self.identifier(value, Loc::Storage));
+                write!(s, ")")
+            }
+            TyKind::FnPtr(f) => {
+                let kind =
+                    if f.visibility == Visibility::External { "external" } else { "internal" };
+                write!(s, "function_{kind}_{}(", f.state_mutability)?;
+                for (i, &ty) in f.parameters.iter().enumerate() {
+                    if i > 0 {
+                        s.push(',');
+                    }
+                    s.push_str(&self.identifier(ty, Loc::MemoryPtr));
+                }
+                s.push_str(")returns(");
+                for (i, &ty) in f.returns.iter().enumerate() {
+                    if i > 0 {
+                        s.push(',');
+                    }
+                    s.push_str(&self.identifier(ty, Loc::MemoryPtr));
+                }
+                write!(s, ")")
+            }
+            TyKind::Tuple(tys) => {
+                s.push_str("tuple(");
+                for (i, &ty) in tys.iter().enumerate() {
+                    if i > 0 {
+                        s.push(',');
+                    }
+                    s.push_str(&self.identifier(ty, loc));
+                }
+                write!(s, ")")
+            }
+            TyKind::Type(ty) => write!(s, "type({})", self.identifier(ty, loc)),
+            _ => write!(s, "unknown"),
+        }
+    }
+
+    /// Returns the solc type label of the given type, e.g. `mapping(address => uint256)`.
+    pub(super) fn label(&self, ty: Ty<'gcx>) -> String {
+        let gcx = self.gcx;
+        match ty.peel_refs().kind {
+            TyKind::Elementary(ty) => match ty {
+                ElementaryType::Address(true) => "address payable".into(),
+                ElementaryType::Fixed(m, n) => format!("fixed{}x{}", m.bits(), n.get()),
+                ElementaryType::UFixed(m, n) => format!("ufixed{}x{}", m.bits(), n.get()),
+                _ => ty.to_abi_str().into_owned(),
+            },
+            TyKind::Contract(id) => format!("contract {}", gcx.item_name(id)),
+            TyKind::Enum(id) => format!("enum {}", gcx.item_canonical_name(id)),
+            TyKind::Udvt(_, id) => gcx.item_canonical_name(id).to_string(),
+            TyKind::Struct(id) => format!("struct {}", gcx.item_canonical_name(id)),
+            TyKind::DynArray(elem) => format!("{}[]", self.label(elem)),
+            TyKind::Array(elem, len) => format!("{}[{len}]", self.label(elem)),
+            TyKind::Mapping(key, value) => {
+                format!("mapping({} => {})", self.label(key), self.label(value))
+            }
+            TyKind::FnPtr(f) => {
+                let list = |tys: &[Ty<'gcx>]| {
+                    tys.iter().map(|&ty| self.label(ty)).collect::<Vec<_>>().join(",")
+                };
+                let mut s = format!("function ({})", list(f.parameters));
+                if f.state_mutability != StateMutability::NonPayable {
+                    write!(s, " {}", f.state_mutability).unwrap();
+                }
+                if f.visibility == Visibility::External {
+                    s.push_str(" external");
+                }
+                if !f.returns.is_empty() {
+                    write!(s, " returns ({})", list(f.returns)).unwrap();
+                }
+                s
+            }
+            TyKind::Tuple(tys) => {
+                let tys = tys.iter().map(|&ty| self.label(ty)).collect::<Vec<_>>();
+                format!("tuple({})", tys.join(","))
+            }
+            TyKind::Type(ty) => format!("type({})", self.label(ty)),
+            _ => "unknown".into(),
+        }
+    }
+}
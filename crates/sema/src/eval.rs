@@ -1,19 +1,18 @@
-use crate::{hir, ty::Gcx};
-use alloy_primitives::U256;
+use crate::{builtins::Builtin, hir, ty::Gcx};
+use alloy_primitives::{keccak256, I256, U256};
 use solar_ast::ast::LitKind;
-use solar_interface::{diagnostics::ErrorGuaranteed, Span};
+use solar_interface::{diagnostics::ErrorGuaranteed, sym, Span};
 use std::fmt;
 
 const RECURSION_LIMIT: usize = 64;
 
-// TODO: `convertType` for truncating and extending correctly: https://github.com/ethereum/solidity/blob/de1a017ccb935d149ed6bcbdb730d89883f8ce02/libsolidity/analysis/ConstantEvaluator.cpp#L234
-
 /// Evaluates simple constants.
 ///
-/// This only supports basic arithmetic and logical operations, and does not support more complex
-/// operations like function calls or memory allocation.
+/// This only supports basic arithmetic and logical operations, elementary type conversions, and
+/// `keccak256` of string literals and `abi.encode`d values. It does not support more complex
+/// operations like other function calls or memory allocation.
 ///
-/// This is only supposed to be used for array sizes and other simple constants.
+/// This is only supposed to be used for array sizes, storage slots and other simple constants.
 pub struct ConstantEvaluator<'gcx> {
     pub gcx: Gcx<'gcx>,
     depth: usize,
@@ -71,7 +70,18 @@ impl<'gcx> ConstantEvaluator<'gcx> {
                 let r = self.try_eval(r)?;
                 l.binop(&r, bin_op.kind).map_err(Into::into)
             }
-            // hir::ExprKind::Call(_, _) => unimplemented!(),
+            hir::ExprKind::Call(callee, hir::CallArgs::Unnamed([arg])) => {
+                match callee.peel_parens().kind {
+                    hir::ExprKind::Type(hir::Type {
+                        kind: hir::TypeKind::Elementary(ty), ..
+                    }) => self.try_eval(arg)?.convert(ty).map_err(Into::into),
+                    hir::ExprKind::Ident(&[hir::Res::Builtin(Builtin::Keccak256)]) => {
+                        let bytes = self.eval_bytes(arg)?;
+                        Ok(IntScalar::from_be_bytes(keccak256(bytes).as_slice()))
+                    }
+                    _ => Err(EE::UnsupportedExpr.into()),
+                }
+            }
             // hir::ExprKind::CallOptions(_, _) => unimplemented!(),
             // hir::ExprKind::Delete(_) => unimplemented!(),
             hir::ExprKind::Ident(&[hir::Res::Item(hir::ItemId::Variable(v))]) => {
@@ -103,6 +113,32 @@ impl<'gcx> ConstantEvaluator<'gcx> {
         }
     }
 
+    /// Evaluates an expression of type `bytes memory`.
+    fn eval_bytes(&mut self, expr: &hir::Expr<'_>) -> Result<Vec<u8>, EvalError> {
+        let expr = expr.peel_parens();
+        match expr.kind {
+            hir::ExprKind::Lit(hir::Lit { kind: LitKind::Str(_, bytes), .. }) => Ok(bytes.to_vec()),
+            hir::ExprKind::Call(callee, hir::CallArgs::Unnamed(args)) => {
+                let hir::ExprKind::Member(base, member) = callee.peel_parens().kind else {
+                    return Err(EE::UnsupportedExpr.into());
+                };
+                let is_abi = matches!(
+                    base.peel_parens().kind,
+                    hir::ExprKind::Ident(&[hir::Res::Builtin(Builtin::Abi)])
+                );
+                if !is_abi || member.name != sym::encode {
+                    return Err(EE::UnsupportedExpr.into());
+                }
+                let mut bytes = Vec::with_capacity(args.len() * 32);
+                for arg in args {
+                    bytes.extend_from_slice(&self.try_eval(arg)?.data.to_be_bytes::<32>());
+                }
+                Ok(bytes)
+            }
+            _ => Err(EE::UnsupportedExpr.into()),
+        }
+    }
+
     fn eval_lit(&mut self, lit: &hir::Lit) -> EvalResult<'gcx> {
         match lit.kind {
             // LitKind::Str(str_kind, arc) => todo!(),
@@ -150,6 +186,33 @@ impl IntScalar {
         !self.data.is_zero()
     }
 
+    /// Converts this value to the given elementary type.
+    ///
+    /// Like in solc, conversions that would change the value, by truncating or sign-extending it,
+    /// are not evaluated. Fixed bytes shorter than 32 bytes are left-aligned, so only `bytes32` is
+    /// supported.
+    ///
+    /// Reference: <https://github.com/ethereum/solidity/blob/de1a017ccb935d149ed6bcbdb730d89883f8ce02/libsolidity/analysis/ConstantEvaluator.cpp#L234>
+    pub fn convert(self, ty: hir::ElementaryType) -> Result<Self, EE> {
+        let fits = match ty {
+            hir::ElementaryType::UInt(size) => self.data.bit_len() <= size.bits() as usize,
+            hir::ElementaryType::Int(size) => {
+                let bits = size.bits() as usize;
+                let max = I256::ONE << (bits - 1);
+                let value = I256::from_raw(self.data);
+                bits == 256 || (-max <= value && value < max)
+            }
+            hir::ElementaryType::Address(_) => self.data.bit_len() <= 160,
+            hir::ElementaryType::FixedBytes(size) if size.bytes() == 32 => true,
+            _ => return Err(EE::UnsupportedExpr),
+        };
+        if fits {
+            Ok(self)
+        } else {
+            Err(EE::LossyConversion)
+        }
+    }
+
     /// Applies the given unary operation to this value.
     pub fn unop(&self, op: hir::UnOpKind) -> Result<Self, EE> {
         Ok(match op {
@@ -211,6 +274,7 @@ pub enum EvalErrorKind {
     UnsupportedLiteral,
     UnsupportedUnaryOp,
     UnsupportedExpr,
+    LossyConversion,
     NonConstantVar,
     AlreadyEmitted(ErrorGuaranteed),
 }
@@ -230,6 +294,7 @@ impl EvalErrorKind {
             Self::UnsupportedLiteral => "unsupported literal",
             Self::UnsupportedUnaryOp => "unsupported unary operation",
             Self::UnsupportedExpr => "unsupported expression",
+            Self::LossyConversion => "conversion changes the value",
             Self::NonConstantVar => "only constant variables are allowed",
             Self::AlreadyEmitted(_) => "error already emitted",
         }
@@ -0,0 +1,650 @@
+//! Source flattening.
+//!
+//! Inlines a source file and all of its imports into a single self-contained source file, as
+//! required by block explorers for verifying contracts that were not compiled with Standard JSON.
+
+use crate::{hir::SourceId, ParsedSources};
+use solar_ast::{ast, visit::Visit};
+use solar_data_structures::{
+    index::IndexVec,
+    map::{FxHashMap, FxHashSet, FxIndexSet},
+    Never,
+};
+use solar_interface::{Ident, Pos, Result, Session, Span, Symbol};
+use std::{fmt::Write, ops::ControlFlow};
+
+/// Flattens the given sources into a single source file.
+///
+/// The sources are sorted in-place with [`ParsedSources::topo_sort`] and inlined in that order, so
+/// that every source comes after its imports.
+///
+/// Import directives are removed and references to imported items are rewritten to refer to the
+/// items' declarations directly, resolving import aliases (`import {A as B}`) and namespace
+/// imports (`import "a.sol" as A`). Top-level items that are declared with the same name in
+/// multiple sources are renamed with a numeric suffix, along with all of their references.
+///
+/// Pragma directives and SPDX license identifiers are deduplicated and merged at the top of the
+/// output.
+pub fn flatten(sess: &Session, sources: &mut ParsedSources<'_>) -> Result<String> {
+    if sess.language.is_yul() {
+        return Err(sess.dcx.err("flattening Yul sources is not supported").emit());
+    }
+    sess.dcx.has_errors()?;
+
+    sources.topo_sort();
+    let scopes = resolve_scopes(sources);
+    let renamed = rename_duplicates(sources);
+
+    let mut licenses = FxIndexSet::default();
+    let mut pragmas = Pragmas::default();
+    let mut bodies = Vec::with_capacity(sources.len());
+    for (id, source) in sources.iter_enumerated() {
+        let Some(ast) = &source.ast else { continue };
+        let file = &source.file;
+        let mut rewriter = Rewriter {
+            src: &file.src,
+            start_pos: file.start_pos.to_usize(),
+            scopes: &scopes,
+            renamed: &renamed,
+            source: id,
+            shadowed: Vec::new(),
+            nested: 0,
+            edits: Vec::new(),
+        };
+
+        for item in ast.items.iter() {
+            match &item.kind {
+                ast::ItemKind::Pragma(pragma) => {
+                    pragmas.add(pragma, rewriter.snippet(item.span));
+                    rewriter.remove_lines(item.span);
+                }
+                ast::ItemKind::Import(_) => rewriter.remove_lines(item.span),
+                _ => {
+                    if let Some(name) = item.name() {
+                        let decl = Decl { source: id, name: name.name };
+                        if let Some(&new_name) = renamed.get(&decl) {
+                            rewriter.replace(name.span, new_name.as_str());
+                        }
+                    }
+                }
+            }
+        }
+        let _ = rewriter.visit_source_unit(ast);
+        rewriter.remove_licenses(&mut licenses);
+
+        let body = rewriter.finish();
+        let body = body.trim();
+        if !body.is_empty() {
+            bodies.push((file.name.display().to_string(), body.to_string()));
+        }
+    }
+
+    let mut out = String::new();
+    if !licenses.is_empty() {
+        let licenses = licenses.into_iter().collect::<Vec<_>>();
+        writeln!(out, "// SPDX-License-Identifier: {}", licenses.join(" AND ")).unwrap();
+    }
+    pragmas.write(&mut out);
+    for (name, body) in bodies {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        writeln!(out, "// {name}\n{body}").unwrap();
+    }
+    Ok(out)
+}
+
+/// A top-level declaration. Overloaded free functions share the same declaration.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Decl {
+    source: SourceId,
+    name: Symbol,
+}
+
+/// What a name in the scope of a source refers to.
+#[derive(Clone, Copy)]
+enum Binding {
+    Decl(Decl),
+    /// `import "a.sol" as A;` or `import * as A from "a.sol";`.
+    Namespace(SourceId),
+}
+
+/// The top-level names that are visible in a source.
+type Scope = FxHashMap<Symbol, Binding>;
+
+/// Resolves the top-level scope of every source.
+fn resolve_scopes(sources: &ParsedSources<'_>) -> IndexVec<SourceId, Scope> {
+    let mut scopes: IndexVec<SourceId, Scope> =
+        sources.indices().map(|_| Scope::default()).collect();
+    // Imports may be cyclic, so keep importing symbols until there are none left to import.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (id, source) in sources.iter_enumerated() {
+            let Some(ast) = &source.ast else { continue };
+            let mut bindings = Vec::new();
+            for item in ast.items.iter() {
+                if let Some(name) = item.name() {
+                    let decl = Decl { source: id, name: name.name };
+                    bindings.push((name.name, Binding::Decl(decl)));
+                }
+            }
+            for &(item_id, import_id) in &source.imports {
+                let ast::ItemKind::Import(import) = &ast.items[item_id].kind else { continue };
+                let imported = &scopes[import_id];
+                match &import.items {
+                    ast::ImportItems::Plain(Some(alias)) | ast::ImportItems::Glob(Some(alias)) => {
+                        bindings.push((alias.name, Binding::Namespace(import_id)));
+                    }
+                    ast::ImportItems::Plain(None) | ast::ImportItems::Glob(None) => {
+                        bindings.extend(imported.iter().map(|(&name, &binding)| (name, binding)));
+                    }
+                    ast::ImportItems::Aliases(aliases) => {
+                        for (name, alias) in aliases.iter() {
+                            if let Some(&binding) = imported.get(&name.name) {
+                                bindings.push((alias.unwrap_or(*name).name, binding));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let scope = &mut scopes[id];
+            for (name, binding) in bindings {
+                if let std::collections::hash_map::Entry::Vacant(entry) = scope.entry(name) {
+                    entry.insert(binding);
+                    changed = true;
+                }
+            }
+        }
+    }
+    scopes
+}
+
+/// Returns the new names of the top-level declarations whose names are already declared in
+/// previous sources.
+fn rename_duplicates(sources: &ParsedSources<'_>) -> FxHashMap<Decl, Symbol> {
+    let all_names = sources
+        .asts()
+        .flat_map(|ast| ast.items.iter().filter_map(|item| item.name()))
+        .map(|name| name.name)
+        .collect::<FxHashSet<_>>();
+    let mut declared = FxHashSet::default();
+    let mut renamed = FxHashMap::default();
+    for (id, source) in sources.iter_enumerated() {
+        let Some(ast) = &source.ast else { continue };
+        let mut in_source = FxHashSet::default();
+        for name in ast.items.iter().filter_map(|item| item.name()) {
+            if !in_source.insert(name.name) || declared.insert(name.name) {
+                continue;
+            }
+            let new_name = (1..)
+                .map(|i| Symbol::intern(&format!("{name}_{i}")))
+                .find(|new_name| !all_names.contains(new_name) && !declared.contains(new_name))
+                .unwrap();
+            declared.insert(new_name);
+            renamed.insert(Decl { source: id, name: name.name }, new_name);
+        }
+    }
+    renamed
+}
+
+/// The merged pragma directives of all the sources.
+#[derive(Default)]
+struct Pragmas {
+    /// The components of all the `pragma solidity` requirements without alternatives, which are
+    /// merged into a single requirement.
+    version: FxIndexSet<String>,
+    /// `pragma solidity` requirements with alternatives (`||`), which can't be merged.
+    versions: FxIndexSet<String>,
+    /// All the other pragma directives.
+    other: FxIndexSet<String>,
+}
+
+impl Pragmas {
+    fn add(&mut self, pragma: &ast::PragmaDirective<'_>, snippet: &str) {
+        match &pragma.tokens {
+            ast::PragmaTokens::Version(_, req) if req.dis.len() == 1 => {
+                self.version.extend(req.dis[0].components.iter().map(ToString::to_string));
+            }
+            ast::PragmaTokens::Version(_, req) => {
+                self.versions.insert(req.to_string());
+            }
+            _ => {
+                self.other.insert(snippet.to_string());
+            }
+        }
+    }
+
+    fn write(&self, out: &mut String) {
+        if !self.version.is_empty() {
+            let version = self.version.iter().map(String::as_str).collect::<Vec<_>>();
+            writeln!(out, "pragma solidity {};", version.join(" ")).unwrap();
+        }
+        for version in &self.versions {
+            writeln!(out, "pragma solidity {version};").unwrap();
+        }
+        for pragma in &self.other {
+            writeln!(out, "{pragma}").unwrap();
+        }
+    }
+}
+
+/// Rewrites the references to top-level declarations in a single source.
+struct Rewriter<'a> {
+    src: &'a str,
+    start_pos: usize,
+    scopes: &'a IndexVec<SourceId, Scope>,
+    renamed: &'a FxHashMap<Decl, Symbol>,
+    source: SourceId,
+    /// The names of the contract members and local variables that are in scope, which shadow
+    /// top-level declarations.
+    shadowed: Vec<Symbol>,
+    /// The depth of contracts and functions that are being visited.
+    nested: usize,
+    /// Replacements of byte ranges of `src`.
+    edits: Vec<(usize, usize, String)>,
+}
+
+impl Rewriter<'_> {
+    fn range(&self, span: Span) -> (usize, usize) {
+        (span.lo().to_usize() - self.start_pos, span.hi().to_usize() - self.start_pos)
+    }
+
+    fn snippet(&self, span: Span) -> &str {
+        let (lo, hi) = self.range(span);
+        &self.src[lo..hi]
+    }
+
+    fn replace(&mut self, span: Span, with: &str) {
+        if self.snippet(span) != with {
+            let (lo, hi) = self.range(span);
+            self.edits.push((lo, hi, with.to_string()));
+        }
+    }
+
+    /// Removes the given span, including the whole line if nothing else is on it.
+    fn remove_lines(&mut self, span: Span) {
+        let (lo, hi) = self.range(span);
+        self.remove_range_lines(lo, hi);
+    }
+
+    fn remove_range_lines(&mut self, mut lo: usize, mut hi: usize) {
+        let bytes = self.src.as_bytes();
+        let line_start = self.src[..lo].trim_end_matches([' ', '\t']).len();
+        let line_end =
+            hi + (self.src[hi..].len() - self.src[hi..].trim_start_matches([' ', '\t']).len());
+        if (line_start == 0 || bytes[line_start - 1] == b'\n')
+            && (line_end == bytes.len() || matches!(bytes[line_end], b'\n' | b'\r'))
+        {
+            lo = line_start;
+            hi = line_end;
+            if self.src[hi..].starts_with("\r\n") {
+                hi += 2;
+            } else if hi < bytes.len() {
+                hi += 1;
+            }
+        }
+        self.edits.push((lo, hi, String::new()));
+    }
+
+    /// Removes the SPDX license identifiers, adding them to `licenses`.
+    fn remove_licenses(&mut self, licenses: &mut FxIndexSet<String>) {
+        const SPDX: &str = "SPDX-License-Identifier:";
+        let mut offset = 0;
+        while let Some(pos) = self.src[offset..].find(SPDX) {
+            let lo = offset + pos;
+            let value_start = lo + SPDX.len();
+            let line_end =
+                self.src[value_start..].find('\n').map_or(self.src.len(), |i| value_start + i);
+            let line = &self.src[value_start..line_end];
+            let value = line.split("*/").next().unwrap().trim();
+            offset = line_end;
+            if value.is_empty() {
+                continue;
+            }
+            licenses.insert(value.to_string());
+
+            let line_start = self.src[..lo].rfind('\n').map_or(0, |i| i + 1);
+            if self.src[line_start..lo].trim() == "//" {
+                self.remove_range_lines(line_start, line_end);
+                continue;
+            }
+            let value_end = value_start + line.find(value).unwrap() + value.len();
+            // Remove block comments that only contain the license identifier.
+            let comment_start = self.src[..lo].rfind("/*");
+            let comment_end = self.src[value_end..].find("*/").map(|i| value_end + i + 2);
+            if let (Some(start), Some(end)) = (comment_start, comment_end) {
+                let is_empty =
+                    |s: &str| s.trim_matches(|c: char| c.is_whitespace() || c == '*').is_empty();
+                if is_empty(&self.src[start + 2..lo]) && is_empty(&self.src[value_end..end - 2]) {
+                    self.remove_range_lines(start, end);
+                    continue;
+                }
+            }
+            self.edits.push((lo, value_end, String::new()));
+        }
+    }
+
+    /// Applies the edits to the source.
+    fn finish(mut self) -> String {
+        self.edits.sort_by_key(|&(lo, hi, _)| (lo, hi));
+        let mut out = String::with_capacity(self.src.len());
+        let mut pos = 0;
+        for (lo, hi, with) in self.edits {
+            if lo < pos {
+                // Overlapping edit.
+                continue;
+            }
+            out.push_str(&self.src[pos..lo]);
+            out.push_str(&with);
+            pos = hi;
+        }
+        out.push_str(&self.src[pos..]);
+        out
+    }
+
+    fn lookup(&self, name: Symbol) -> Option<Binding> {
+        if self.shadowed.contains(&name) {
+            return None;
+        }
+        self.scopes[self.source].get(&name).copied()
+    }
+
+    /// Rewrites a path to the name of the declaration it refers to. Any remaining segments after
+    /// the declaration are member accesses, and are left untouched.
+    fn path(&mut self, segments: &[Ident]) {
+        let Some((first, rest)) = segments.split_first() else { return };
+        let Some(mut binding) = self.lookup(first.name) else { return };
+        let mut span = first.span;
+        for segment in rest {
+            let Binding::Namespace(source) = binding else { break };
+            let Some(&next) = self.scopes[source].get(&segment.name) else { return };
+            binding = next;
+            span = span.to(segment.span);
+        }
+        if let Binding::Decl(decl) = binding {
+            let name = self.renamed.get(&decl).copied().unwrap_or(decl.name);
+            self.replace(span, name.as_str());
+        }
+    }
+
+    fn shadow(&mut self, f: impl FnOnce(&mut Self)) -> ControlFlow<Never> {
+        let len = self.shadowed.len();
+        self.nested += 1;
+        f(self);
+        self.nested -= 1;
+        self.shadowed.truncate(len);
+        ControlFlow::Continue(())
+    }
+}
+
+/// Returns the segments of an expression that is a path, e.g. `a.b.c`.
+fn expr_path(expr: &ast::Expr<'_>) -> Option<Vec<Ident>> {
+    match &expr.kind {
+        ast::ExprKind::Ident(ident) => Some(vec![*ident]),
+        ast::ExprKind::Member(base, member) => {
+            let mut segments = expr_path(base)?;
+            segments.push(*member);
+            Some(segments)
+        }
+        _ => None,
+    }
+}
+
+impl<'ast> Visit<'ast> for Rewriter<'_> {
+    type BreakValue = Never;
+
+    fn visit_item_contract(
+        &mut self,
+        contract: &'ast ast::ItemContract<'ast>,
+    ) -> ControlFlow<Self::BreakValue> {
+        // Bases are resolved in the enclosing scope.
+        for base in contract.bases.iter() {
+            self.visit_modifier(base)?;
+        }
+        self.shadow(|this| {
+            this.shadowed
+                .extend(contract.body.iter().filter_map(|item| item.name()).map(|n| n.name));
+            if let Some(layout) = &contract.layout {
+                let _ = this.visit_storage_layout_specifier(layout);
+            }
+            for item in contract.body.iter() {
+                let _ = this.visit_item(item);
+            }
+        })
+    }
+
+    fn visit_item_function(
+        &mut self,
+        function: &'ast ast::ItemFunction<'ast>,
+    ) -> ControlFlow<Self::BreakValue> {
+        self.shadow(|this| {
+            let _ = this.walk_item_function(function);
+        })
+    }
+
+    fn visit_function_header(
+        &mut self,
+        header: &'ast ast::FunctionHeader<'ast>,
+    ) -> ControlFlow<Self::BreakValue> {
+        if let Some(override_) = &header.override_ {
+            for path in override_.paths.iter() {
+                self.path(path.segments());
+            }
+        }
+        self.walk_function_header(header)
+    }
+
+    fn visit_item_struct(
+        &mut self,
+        strukt: &'ast ast::ItemStruct<'ast>,
+    ) -> ControlFlow<Self::BreakValue> {
+        for field in strukt.fields.iter() {
+            self.visit_ty(&field.ty)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_item_event(
+        &mut self,
+        event: &'ast ast::ItemEvent<'ast>,
+    ) -> ControlFlow<Self::BreakValue> {
+        self.shadow(|this| {
+            let _ = this.walk_item_event(event);
+        })
+    }
+
+    fn visit_item_error(
+        &mut self,
+        error: &'ast ast::ItemError<'ast>,
+    ) -> ControlFlow<Self::BreakValue> {
+        self.shadow(|this| {
+            let _ = this.walk_item_error(error);
+        })
+    }
+
+    fn visit_variable_definition(
+        &mut self,
+        var: &'ast ast::VariableDefinition<'ast>,
+    ) -> ControlFlow<Self::BreakValue> {
+        if let Some(override_) = &var.override_ {
+            for path in override_.paths.iter() {
+                self.path(path.segments());
+            }
+        }
+        self.walk_variable_definition(var)?;
+        if let (Some(name), true) = (var.name, self.nested > 0) {
+            self.shadowed.push(name.name);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_block(&mut self, block: &'ast ast::Block<'ast>) -> ControlFlow<Self::BreakValue> {
+        let len = self.shadowed.len();
+        self.walk_block(block)?;
+        self.shadowed.truncate(len);
+        ControlFlow::Continue(())
+    }
+
+    fn visit_expr(&mut self, expr: &'ast ast::Expr<'ast>) -> ControlFlow<Self::BreakValue> {
+        if let Some(segments) = expr_path(expr) {
+            self.path(&segments);
+            return ControlFlow::Continue(());
+        }
+        self.walk_expr(expr)
+    }
+
+    fn visit_path(&mut self, path: &'ast ast::PathSlice) -> ControlFlow<Self::BreakValue> {
+        self.path(path.segments());
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParsingContext;
+    use solar_interface::source_map::FileName;
+    use std::path::{Path, PathBuf};
+
+    /// Flattens the first source, loading the others into the source map for imports, and checks
+    /// that the flattened source compiles.
+    fn flatten_sources(sources: &[(&str, &str)]) -> String {
+        let sess = Session::builder().with_test_emitter().build();
+        let flattened = sess.enter(|| {
+            let mut pcx = ParsingContext::new(&sess);
+            for (i, &(name, src)) in sources.iter().enumerate() {
+                let name = FileName::Real(PathBuf::from(name));
+                let file = sess.source_map().new_source_file(name, || Ok(src.into())).unwrap();
+                if i == 0 {
+                    pcx.add_file(file);
+                }
+            }
+            pcx.flatten().unwrap()
+        });
+        assert_compiles(&flattened);
+        flattened
+    }
+
+    fn assert_compiles(src: &str) {
+        let sess = Session::builder().with_test_emitter().build();
+        let r = sess.enter(|| {
+            let mut pcx = ParsingContext::new(&sess);
+            let name = FileName::Custom("flattened.sol".into());
+            pcx.add_file(sess.source_map().new_source_file(name, || Ok(src.into())).unwrap());
+            pcx.parse_and_resolve()
+        });
+        assert!(r.is_ok(), "flattened source does not compile:\n{src}");
+    }
+
+    /// Checks that the outputs of the `flatten` UI tests compile.
+    #[test]
+    fn ui_tests_compile() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/ui/flatten");
+        let mut n = 0;
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "stdout") {
+                assert_compiles(&std::fs::read_to_string(&path).unwrap());
+                n += 1;
+            }
+        }
+        assert!(n > 0, "no flatten UI tests found");
+    }
+
+    #[test]
+    fn aliases_and_duplicates() {
+        let main = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import {Math as M, Point} from "./math.sol";
+import "./other.sol" as Other;
+import {Point as OtherPoint} from "./other.sol";
+
+contract Main {
+    Point p;
+    OtherPoint q;
+
+    function f(uint256 a, Point memory Other) public {
+        q = Other.origin(M.max(a, Other.x));
+    }
+
+    function g() public {
+        p = Point(M.max(1, 2));
+        q = Other.origin(0);
+    }
+}
+"#;
+        let math = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+library Math {
+    function max(uint256 a, uint256 b) internal pure returns (uint256) {
+        return a > b ? a : b;
+    }
+}
+
+struct Point {
+    uint256 x;
+}
+"#;
+        let other = r#"/* SPDX-License-Identifier: Apache-2.0 */
+pragma solidity >=0.8.4 <0.9.0;
+pragma abicoder v2;
+
+struct Point {
+    int256 x;
+}
+
+function origin(uint256) pure returns (Point memory) {}
+"#;
+        let expected = r#"// SPDX-License-Identifier: MIT AND Apache-2.0
+pragma solidity ^0.8.0 >=0.8.4 <0.9.0;
+pragma abicoder v2;
+
+// math.sol
+library Math {
+    function max(uint256 a, uint256 b) internal pure returns (uint256) {
+        return a > b ? a : b;
+    }
+}
+
+struct Point {
+    uint256 x;
+}
+
+// other.sol
+struct Point_1 {
+    int256 x;
+}
+
+function origin(uint256) pure returns (Point_1 memory) {}
+
+// main.sol
+contract Main {
+    Point p;
+    Point_1 q;
+
+    function f(uint256 a, Point memory Other) public {
+        q = Other.origin(Math.max(a, Other.x));
+    }
+
+    function g() public {
+        p = Point(Math.max(1, 2));
+        q = origin(0);
+    }
+}
+"#;
+        let sources = [("main.sol", main), ("math.sol", math), ("other.sol", other)];
+        assert_eq!(flatten_sources(&sources), expected);
+    }
+
+    #[test]
+    fn cyclic_imports() {
+        let a = "import \"./b.sol\";\ncontract A { B b; }\n";
+        let b = "import {A as AA} from \"./a.sol\";\ncontract B { AA a; }\n";
+        let expected = "// b.sol\ncontract B { A a; }\n\n// a.sol\ncontract A { B b; }\n";
+        assert_eq!(flatten_sources(&[("a.sol", a), ("b.sol", b)]), expected);
+    }
+}
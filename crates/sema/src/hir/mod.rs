@@ -6,12 +6,19 @@ use rayon::prelude::*;
 use solar_ast::ast;
 use solar_data_structures::{
     index::{Idx, IndexVec},
+    map::FxHashMap,
     newtype_index, BumpExt,
 };
 use solar_interface::{diagnostics::ErrorGuaranteed, source_map::SourceFile, Ident, Span};
 use std::{fmt, ops::ControlFlow, sync::Arc};
 use strum::EnumIs;
 
+mod natspec;
+pub use natspec::{NatSpecItem, NatSpecKind};
+
+mod visit;
+pub use visit::Visit;
+
 pub use ast::{
     BinOp, BinOpKind, ContractKind, DataLocation, ElementaryType, FunctionKind, Lit,
     StateMutability, UnOp, UnOpKind, VarMut, Visibility,
@@ -78,6 +85,42 @@ pub struct Hir<'hir> {
     pub(crate) errors: IndexVec<ErrorId, Error<'hir>>,
     /// All constants and variables.
     pub(crate) variables: IndexVec<VariableId, Variable<'hir>>,
+    /// The NatSpec documentation of items. Items without documentation are not present.
+    pub(crate) natspec: FxHashMap<ItemId, &'hir [NatSpecItem<'hir>]>,
+}
+
+/// Serializes the NatSpec map as a list of `{ item, tags }` objects, since item IDs cannot be used
+/// as JSON object keys.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Hir<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        #[derive(serde::Serialize)]
+        struct ItemNatSpec<'a> {
+            item: ItemId,
+            tags: &'a [NatSpecItem<'a>],
+        }
+
+        let natspec = self
+            .item_ids()
+            .map(|item| ItemNatSpec { item, tags: self.natspec(item) })
+            .filter(|natspec| !natspec.tags.is_empty())
+            .collect::<Vec<_>>();
+
+        let mut s = serializer.serialize_struct("Hir", 10)?;
+        s.serialize_field("sources", &self.sources)?;
+        s.serialize_field("contracts", &self.contracts)?;
+        s.serialize_field("functions", &self.functions)?;
+        s.serialize_field("structs", &self.structs)?;
+        s.serialize_field("enums", &self.enums)?;
+        s.serialize_field("udvts", &self.udvts)?;
+        s.serialize_field("events", &self.events)?;
+        s.serialize_field("errors", &self.errors)?;
+        s.serialize_field("variables", &self.variables)?;
+        s.serialize_field("natspec", &natspec)?;
+        s.end()
+    }
 }
 
 macro_rules! indexvec_methods {
@@ -151,6 +194,7 @@ impl<'hir> Hir<'hir> {
             events: IndexVec::new(),
             errors: IndexVec::new(),
             variables: IndexVec::new(),
+            natspec: FxHashMap::default(),
         }
     }
 
@@ -181,6 +225,12 @@ impl<'hir> Hir<'hir> {
         }
     }
 
+    /// Returns the NatSpec documentation of the given item.
+    #[inline]
+    pub fn natspec(&self, id: impl Into<ItemId>) -> &'hir [NatSpecItem<'hir>] {
+        self.natspec.get(&id.into()).copied().unwrap_or_default()
+    }
+
     /// Returns an iterator over all item IDs.
     pub fn item_ids(&self) -> impl DoubleEndedIterator<Item = ItemId> + Clone {
         std::iter::empty::<ItemId>()
@@ -267,6 +317,19 @@ pub struct Source<'hir> {
     pub items: &'hir [ItemId],
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Source<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("Source", 3)?;
+        s.serialize_field("file", &self.file.name.display().to_string())?;
+        s.serialize_field("imports", &self.imports)?;
+        s.serialize_field("items", &self.items)?;
+        s.end()
+    }
+}
+
 impl fmt::Debug for Source<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Source")
@@ -278,6 +341,7 @@ impl fmt::Debug for Source<'_> {
 }
 
 #[derive(Clone, Copy, Debug, EnumIs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Item<'a, 'hir> {
     Contract(&'a Contract<'hir>),
     Function(&'a Function<'hir>),
@@ -335,6 +399,21 @@ impl<'hir> Item<'_, 'hir> {
         }
     }
 
+    /// Returns the ID of the source file that defines this item.
+    #[inline]
+    pub fn source(self) -> SourceId {
+        match self {
+            Item::Contract(c) => c.source,
+            Item::Function(f) => f.source,
+            Item::Struct(s) => s.source,
+            Item::Enum(e) => e.source,
+            Item::Udvt(u) => u.source,
+            Item::Error(e) => e.source,
+            Item::Event(e) => e.source,
+            Item::Variable(v) => v.source,
+        }
+    }
+
     /// Returns the contract ID if this item is part of a contract.
     #[inline]
     pub fn contract(self) -> Option<ContractId> {
@@ -401,6 +480,7 @@ impl<'hir> Item<'_, 'hir> {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, From, EnumIs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ItemId {
     Contract(ContractId),
     Function(FunctionId),
@@ -475,10 +555,20 @@ impl ItemId {
             None
         }
     }
+
+    /// Returns the struct ID if this is a struct.
+    pub fn as_struct(&self) -> Option<StructId> {
+        if let Self::Struct(v) = *self {
+            Some(v)
+        } else {
+            None
+        }
+    }
 }
 
 /// A contract, interface, or library.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Contract<'hir> {
     /// The source this contract is defined in.
     pub source: SourceId,
@@ -492,6 +582,8 @@ pub struct Contract<'hir> {
     pub bases: &'hir [ContractId],
     /// The linearized contract bases.
     pub linearized_bases: &'hir [ContractId],
+    /// The base slot of the storage layout: `layout at <expr>`.
+    pub layout: Option<&'hir Expr<'hir>>,
     /// The constructor function.
     pub ctor: Option<FunctionId>,
     /// The `fallback` function.
@@ -537,6 +629,7 @@ impl Contract<'_> {
 
 /// A function.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Function<'hir> {
     /// The source this function is defined in.
     pub source: SourceId,
@@ -599,6 +692,7 @@ impl Function<'_> {
 
 /// A struct.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Struct<'hir> {
     /// The source this struct is defined in.
     pub source: SourceId,
@@ -613,6 +707,7 @@ pub struct Struct<'hir> {
 
 /// An enum.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Enum<'hir> {
     /// The source this enum is defined in.
     pub source: SourceId,
@@ -628,6 +723,7 @@ pub struct Enum<'hir> {
 
 /// A user-defined value type.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Udvt<'hir> {
     /// The source this UDVT is defined in.
     pub source: SourceId,
@@ -643,6 +739,7 @@ pub struct Udvt<'hir> {
 
 /// An event.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Event<'hir> {
     /// The source this event is defined in.
     pub source: SourceId,
@@ -659,6 +756,7 @@ pub struct Event<'hir> {
 
 /// An event parameter.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EventParameter<'hir> {
     pub ty: Type<'hir>,
     pub indexed: bool,
@@ -667,6 +765,7 @@ pub struct EventParameter<'hir> {
 
 /// A custom error.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Error<'hir> {
     /// The source this error is defined in.
     pub source: SourceId,
@@ -681,6 +780,7 @@ pub struct Error<'hir> {
 
 /// A constant or variable declaration.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Variable<'hir> {
     /// The source this variable is defined in.
     pub source: SourceId,
@@ -742,6 +842,7 @@ pub type Block<'hir> = &'hir [Stmt<'hir>];
 
 /// A statement.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Stmt<'hir> {
     /// The statement span.
     pub span: Span,
@@ -750,6 +851,7 @@ pub struct Stmt<'hir> {
 
 /// A kind of statement.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StmtKind<'hir> {
     // TODO: Yul to HIR.
     // /// An assembly block, with optional flags: `assembly "evmasm" (...) { ... }`.
@@ -805,6 +907,7 @@ pub enum StmtKind<'hir> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.tryStatement>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StmtTry<'hir> {
     pub expr: Expr<'hir>,
     pub returns: &'hir [VariableId],
@@ -818,6 +921,7 @@ pub struct StmtTry<'hir> {
 ///
 /// Reference: <https://docs.soliditylang.org/en/latest/grammar.html#a4.SolidityParser.catchClause>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CatchClause<'hir> {
     pub name: Option<Ident>,
     pub args: &'hir [VariableId],
@@ -826,6 +930,7 @@ pub struct CatchClause<'hir> {
 
 /// The loop type that yielded an [`StmtKind::Loop`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum LoopSource {
     /// A `for (...) { ... }` loop.
     For,
@@ -848,6 +953,7 @@ impl LoopSource {
 
 /// Resolved name.
 #[derive(Clone, Copy, PartialEq, Eq, From, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Res {
     /// A resolved item.
     Item(ItemId),
@@ -920,6 +1026,7 @@ impl Res {
 
 /// An expression.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Expr<'hir> {
     pub id: ExprId,
     pub kind: ExprKind<'hir>,
@@ -940,6 +1047,7 @@ impl Expr<'_> {
 
 /// A kind of expression.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ExprKind<'hir> {
     /// An array literal expression: `[a, b, c, d]`.
     Array(&'hir [Expr<'hir>]),
@@ -1002,6 +1110,7 @@ pub enum ExprKind<'hir> {
 
 /// A named argument: `name: value`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NamedArg<'hir> {
     pub name: Ident,
     pub value: Expr<'hir>,
@@ -1009,6 +1118,7 @@ pub struct NamedArg<'hir> {
 
 /// A list of function call arguments.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CallArgs<'hir> {
     /// A list of unnamed arguments: `(1, 2, 3)`.
     Unnamed(&'hir [Expr<'hir>]),
@@ -1032,6 +1142,7 @@ impl CallArgs<'_> {
 
 /// A type name.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Type<'hir> {
     pub span: Span,
     pub kind: TypeKind<'hir>,
@@ -1073,6 +1184,7 @@ impl Type<'_> {
 
 /// The kind of a type.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TypeKind<'hir> {
     /// An elementary/primitive type.
     Elementary(ElementaryType),
@@ -1099,6 +1211,7 @@ impl TypeKind<'_> {
 
 /// An array type.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TypeArray<'hir> {
     pub element: Type<'hir>,
     pub size: Option<&'hir Expr<'hir>>,
@@ -1106,6 +1219,7 @@ pub struct TypeArray<'hir> {
 
 /// A function type name.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TypeFunction<'hir> {
     pub parameters: &'hir [Type<'hir>],
     pub visibility: Visibility,
@@ -1115,6 +1229,7 @@ pub struct TypeFunction<'hir> {
 
 /// A mapping type.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TypeMapping<'hir> {
     pub key: Type<'hir>,
     pub key_name: Option<Ident>,
@@ -0,0 +1,57 @@
+//! NatSpec documentation.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/natspec-format.html>
+
+use solar_interface::{Span, Symbol};
+use std::fmt;
+
+/// A NatSpec tag, such as `@notice Does something`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NatSpecItem<'hir> {
+    /// The tag kind.
+    pub kind: NatSpecKind,
+    /// The span of the tag, from the `@` to the end of its first line.
+    pub span: Span,
+    /// The content of the tag, excluding the tag name and the name of the documented parameter
+    /// or contract, if any. Lines are joined with a single space.
+    pub content: &'hir str,
+}
+
+/// A NatSpec tag kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum NatSpecKind {
+    /// `@title`
+    Title,
+    /// `@author`
+    Author,
+    /// `@notice`, or untagged text at the start of the comment.
+    Notice,
+    /// `@dev`
+    Dev,
+    /// `@param <name>`
+    Param { name: Symbol },
+    /// `@return`
+    Return,
+    /// `@inheritdoc <contract>`
+    Inheritdoc { contract: Symbol },
+    /// `@custom:<name>`
+    Custom { name: Symbol },
+}
+
+impl fmt::Display for NatSpecKind {
+    /// Formats the tag name, such as `@param`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Title => f.write_str("@title"),
+            Self::Author => f.write_str("@author"),
+            Self::Notice => f.write_str("@notice"),
+            Self::Dev => f.write_str("@dev"),
+            Self::Param { .. } => f.write_str("@param"),
+            Self::Return => f.write_str("@return"),
+            Self::Inheritdoc { .. } => f.write_str("@inheritdoc"),
+            Self::Custom { name } => write!(f, "@custom:{name}"),
+        }
+    }
+}
@@ -11,7 +11,10 @@ extern crate tracing;
 
 use rayon::prelude::*;
 use solar_data_structures::{trustme, OnDrop};
-use solar_interface::{config::CompilerStage, Result, Session};
+use solar_interface::{
+    config::{CompilerOutput, CompilerStage, DumpKind},
+    Result, Session,
+};
 use thread_local::ThreadLocal;
 use ty::Gcx;
 
@@ -23,21 +26,50 @@ pub use solar_interface as interface;
 
 mod ast_lowering;
 mod ast_passes;
+mod dump;
 
 mod parse;
 pub use parse::{ParsedSource, ParsedSources, ParsingContext};
 
+pub mod abi2sol;
 pub mod builtins;
 pub mod eval;
+pub mod flatten;
 pub mod hir;
+pub mod link;
 pub mod ty;
 
 mod typeck;
 
 mod emit;
+mod metadata;
+pub mod standard_json;
+mod storage_diff;
 
 /// Parses and semantically analyzes all the loaded sources, recursing into imports.
 pub fn parse_and_resolve(pcx: ParsingContext<'_>) -> Result<()> {
+    parse_and_resolve_with(pcx, |gcx, imports, asts| {
+        if let Some(path) = &gcx.sess.storage_layout_baseline {
+            storage_diff::check(gcx, path, imports);
+            gcx.sess.dcx.has_errors()?;
+        }
+        if !gcx.sess.emit.is_empty() {
+            emit::emit(gcx, imports, asts);
+            gcx.sess.dcx.has_errors()?;
+        }
+        Ok(())
+    })
+}
+
+/// Parses and resolves the sources of `pcx`, calling `f` with the global context and the import
+/// paths once analysis has completed without errors.
+///
+/// The ASTs are also passed to `f` if the AST JSON output is requested, and are otherwise dropped
+/// after lowering.
+fn parse_and_resolve_with(
+    pcx: ParsingContext<'_>,
+    f: impl FnOnce(Gcx<'_>, &metadata::ImportPaths, Option<&ParsedSources<'_>>) -> Result<()>,
+) -> Result<()> {
     let sess = pcx.sess;
 
     if pcx.sources.is_empty() {
@@ -50,11 +82,12 @@ pub fn parse_and_resolve(pcx: ParsingContext<'_>) -> Result<()> {
         debug!(asts_allocated = arenas.iter_mut().map(|a| a.allocated_bytes()).sum::<usize>());
         debug_span!("dropping_ast_arenas").in_scope(|| drop(arenas));
     });
+    let imports = metadata::ImportPaths::new(&pcx.file_resolver);
     let mut sources = pcx.parse(&ast_arenas);
 
     if let Some(dump) = &sess.dump {
-        if dump.kind.is_ast() {
-            dump_ast(sess, &sources, dump.paths.as_deref())?;
+        if matches!(dump.kind, DumpKind::Ast | DumpKind::AstJson) {
+            dump::dump_ast(sess, &sources, dump.kind, dump.paths.as_deref())?;
         }
     }
 
@@ -70,17 +103,23 @@ pub fn parse_and_resolve(pcx: ParsingContext<'_>) -> Result<()> {
     });
     let (hir, symbol_resolver) = lower(sess, &sources, hir_arena.get_or_default())?;
 
-    // Drop the ASTs and AST arenas in a separate thread.
-    sess.spawn({
-        // TODO: The transmute is required because `sources` borrows from `ast_arenas`,
-        // even though both are moved in the closure.
-        let sources =
-            unsafe { std::mem::transmute::<ParsedSources<'_>, ParsedSources<'static>>(sources) };
-        move || {
-            debug_span!("drop_asts").in_scope(|| drop(sources));
-            drop(ast_arenas);
-        }
-    });
+    let asts = if sess.do_emit(CompilerOutput::AstJson) {
+        Some(sources)
+    } else {
+        // Drop the ASTs and AST arenas in a separate thread.
+        sess.spawn({
+            // TODO: The transmute is required because `sources` borrows from `ast_arenas`,
+            // even though both are moved in the closure.
+            let sources = unsafe {
+                std::mem::transmute::<ParsedSources<'_>, ParsedSources<'static>>(sources)
+            };
+            move || {
+                debug_span!("drop_asts").in_scope(|| drop(sources));
+                drop(ast_arenas);
+            }
+        });
+        None
+    };
 
     let global_context =
         OnDrop::new(ty::GlobalCtxt::new(sess, &hir_arena, hir, symbol_resolver), |gcx| {
@@ -88,8 +127,7 @@ pub fn parse_and_resolve(pcx: ParsingContext<'_>) -> Result<()> {
         });
     let gcx = ty::Gcx::new(unsafe { trustme::decouple_lt(&global_context) });
     analysis(gcx)?;
-
-    Ok(())
+    f(gcx, &imports, asts.as_ref())
 }
 
 /// Lowers the parsed ASTs into the HIR.
@@ -112,8 +150,8 @@ fn lower<'sess, 'hir>(
 #[instrument(level = "debug", skip_all)]
 fn analysis(gcx: Gcx<'_>) -> Result<()> {
     if let Some(dump) = &gcx.sess.dump {
-        if dump.kind.is_hir() {
-            dump_hir(gcx, dump.paths.as_deref())?;
+        if matches!(dump.kind, DumpKind::Hir | DumpKind::HirDebug | DumpKind::HirJson) {
+            dump::dump_hir(gcx, dump.kind, dump.paths.as_deref())?;
         }
     }
 
@@ -134,53 +172,9 @@ fn analysis(gcx: Gcx<'_>) -> Result<()> {
     typeck::check(gcx);
     gcx.sess.dcx.has_errors()?;
 
-    if !gcx.sess.emit.is_empty() {
-        emit::emit(gcx);
-        gcx.sess.dcx.has_errors()?;
+    if !gcx.sess.libraries.is_empty() {
+        link::check_libraries(gcx);
     }
 
     Ok(())
 }
-
-fn dump_ast(sess: &Session, sources: &ParsedSources<'_>, paths: Option<&[String]>) -> Result<()> {
-    if let Some(paths) = paths {
-        for path in paths {
-            if let Some(source) = sources.iter().find(|&s| match_file_name(&s.file.name, path)) {
-                println!("{source:#?}");
-            } else {
-                let msg = format!("`-Zdump=ast={path:?}` did not match any source file");
-                let note = format!(
-                    "available source files: {}",
-                    sources
-                        .iter()
-                        .map(|s| s.file.name.display().to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
-                return Err(sess.dcx.err(msg).note(note).emit());
-            }
-        }
-    } else {
-        println!("{sources:#?}");
-    }
-
-    Ok(())
-}
-
-fn dump_hir(gcx: Gcx<'_>, paths: Option<&[String]>) -> Result<()> {
-    println!("{:#?}", gcx.hir);
-    if let Some(paths) = paths {
-        println!("\nPaths not yet implemented: {paths:#?}");
-    }
-    Ok(())
-}
-
-fn match_file_name(name: &solar_interface::source_map::FileName, path: &str) -> bool {
-    match name {
-        solar_interface::source_map::FileName::Real(path_buf) => {
-            path_buf.as_os_str() == path || path_buf.file_stem() == Some(path.as_ref())
-        }
-        solar_interface::source_map::FileName::Stdin => path == "stdin" || path == "<stdin>",
-        solar_interface::source_map::FileName::Custom(name) => path == name,
-    }
-}
@@ -0,0 +1,94 @@
+//! Library linking.
+//!
+//! Calls to public and external library functions are compiled to `DELEGATECALL`s to the
+//! deployed library, whose address is not known at compile time. Such addresses are left as
+//! `__$<hash>$__` placeholders in the bytecode, which must be replaced before deployment.
+//!
+//! The placeholders and their `linkReferences` offsets will be emitted once bytecode generation
+//! is implemented.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/using-the-compiler.html#library-linking>
+
+use crate::ty::Gcx;
+use alloy_primitives::{hex, keccak256};
+use solar_interface::{config::Library, source_map::FileName};
+use std::path::Path;
+
+/// The length of a library placeholder in hex characters, which is the same as the length of an
+/// address.
+pub const PLACEHOLDER_LEN: usize = 40;
+
+/// Returns the placeholder for the address of the library with the given fully qualified name.
+///
+/// This is `__$` followed by the first 34 hex characters of the keccak256 hash of the name,
+/// followed by `$__`.
+pub fn library_placeholder(fully_qualified_name: &str) -> String {
+    let hash = hex::encode(keccak256(fully_qualified_name));
+    format!("__${}$__", &hash[..PLACEHOLDER_LEN - 6])
+}
+
+/// Replaces the placeholders of the given libraries in the hex-encoded `bytecode` with their
+/// addresses.
+///
+/// Placeholders of libraries that are not in `libraries` are left untouched.
+pub fn link_bytecode(bytecode: &str, libraries: &[Library]) -> String {
+    let mut bytecode = bytecode.to_string();
+    for library in libraries {
+        let placeholder = library_placeholder(&library.fully_qualified_name());
+        if bytecode.contains(&placeholder) {
+            bytecode = bytecode.replace(&placeholder, &hex::encode(library.address));
+        }
+    }
+    bytecode
+}
+
+/// Warns about the `--libraries` that don't refer to a library defined in the sources.
+///
+/// The path of a library matches the source files whose path ends with it, so that it can be
+/// given relative to the base path.
+pub(crate) fn check_libraries(gcx: Gcx<'_>) {
+    for library in &gcx.sess.libraries {
+        let path = Path::new(&library.path);
+        let found = gcx.hir.contracts().find(|c| {
+            c.name.as_str() == library.name
+                && match &gcx.hir.source(c.source).file.name {
+                    FileName::Real(file) => file.ends_with(path),
+                    name => name.display().to_string() == library.path,
+                }
+        });
+        let fqn = library.fully_qualified_name();
+        match found {
+            Some(c) if c.kind.is_library() => {}
+            Some(c) => {
+                let msg = format!("`{fqn}` is a {}, not a library", c.kind.to_str());
+                gcx.dcx().warn(msg).note("its address is not used").emit();
+            }
+            None => {
+                let msg = format!("library `{fqn}` is not defined in any source");
+                gcx.dcx().warn(msg).note("its address is not used").emit();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholder() {
+        let placeholder = library_placeholder("a.sol:L");
+        assert_eq!(placeholder.len(), PLACEHOLDER_LEN);
+        assert!(placeholder.starts_with("__$") && placeholder.ends_with("$__"));
+        assert_eq!(&placeholder[3..37], &hex::encode(keccak256("a.sol:L"))[..34]);
+    }
+
+    #[test]
+    fn link() {
+        let lib: Library = "a.sol:L=0x1111111111111111111111111111111111111111".parse().unwrap();
+        let other = library_placeholder("a.sol:M");
+        let bytecode = format!("6073{}5f{}", library_placeholder("a.sol:L"), other);
+        let linked = link_bytecode(&bytecode, &[lib]);
+        assert_eq!(linked, format!("6073{}5f{}", "11".repeat(20), other));
+    }
+}
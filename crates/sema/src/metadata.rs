@@ -0,0 +1,354 @@
+//! Contract metadata.
+//!
+//! The metadata follows solc's format, except that the `urls` of each source only contain the
+//! IPFS URL and not solc's `bzz-raw://` Swarm URL, so the metadata and its hash are not
+//! byte-identical to solc's.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/metadata.html>
+
+use crate::{emit, hir, ty::Gcx};
+use alloy_primitives::hex;
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use solar_interface::source_map::{
+    FileName, FileResolver, SourceFile, SourceFileHash, SourceFileHashAlgorithm,
+};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// The import paths that the sources were resolved with.
+pub(crate) struct ImportPaths {
+    /// Import remappings: `(prefix, target)`.
+    remappings: Vec<(PathBuf, PathBuf)>,
+    /// Base and include paths, in the same form as the names of the source files.
+    base_paths: Vec<PathBuf>,
+}
+
+impl ImportPaths {
+    pub(crate) fn new(file_resolver: &FileResolver<'_>) -> Self {
+        let mut remappings = Vec::new();
+        let mut base_paths = Vec::new();
+        for (map, path) in file_resolver.get_import_paths() {
+            match map {
+                Some(map) => remappings.push((map.clone(), path.clone())),
+                None => {
+                    let path = solar_interface::canonicalize(path).unwrap_or_else(|_| path.clone());
+                    let current_dir = std::env::current_dir().unwrap_or_default();
+                    match path.strip_prefix(current_dir) {
+                        Ok(path) => base_paths.push(path.to_path_buf()),
+                        Err(_) => base_paths.push(path),
+                    }
+                }
+            }
+        }
+        Self { remappings, base_paths }
+    }
+
+    /// Returns the import remappings as `(prefix, target)` pairs.
+    pub(crate) fn remappings(&self) -> &[(PathBuf, PathBuf)] {
+        &self.remappings
+    }
+
+    /// Returns the source unit name of the given file: its path relative to the first base path
+    /// that contains it, or its path if it is not in any base path.
+    pub(crate) fn source_unit_name(&self, file: &SourceFile) -> String {
+        if let FileName::Real(path) = &file.name {
+            for base in &self.base_paths {
+                if let Ok(path) = path.strip_prefix(base) {
+                    return path.display().to_string();
+                }
+            }
+        }
+        file.name.display().to_string()
+    }
+}
+
+/// Returns the metadata JSON of the given contract.
+///
+/// Keys are sorted and the output is compact, matching the format that is hashed by solc.
+pub(crate) fn contract_metadata(
+    gcx: Gcx<'_>,
+    id: hir::ContractId,
+    imports: &ImportPaths,
+) -> String {
+    let contract = gcx.hir.contract(id);
+    let source = gcx.hir.source(contract.source);
+
+    let abi = serde_json::to_value(gcx.contract_abi(id)).unwrap();
+    let output = json!({
+        "abi": abi,
+        "devdoc": emit::natspec::devdoc(gcx, id),
+        "userdoc": emit::natspec::userdoc(gcx, id),
+    });
+
+    let libraries = gcx
+        .sess
+        .libraries
+        .iter()
+        .map(|lib| {
+            (lib.fully_qualified_name(), Value::from(format!("0x{}", hex::encode(lib.address))))
+        })
+        .collect::<Map<_, _>>();
+    let settings = json!({
+        "compilationTarget": { imports.source_unit_name(&source.file): contract.name.as_str() },
+        "evmVersion": gcx.sess.evm_version.to_str(),
+        "libraries": libraries,
+        "metadata": { "bytecodeHash": "ipfs" },
+        "optimizer": { "enabled": false, "runs": 200 },
+        "remappings": imports
+            .remappings
+            .iter()
+            .map(|(map, path)| format!("{}={}", map.display(), path.display()))
+            .collect::<Vec<_>>(),
+    });
+
+    let mut sources = Map::new();
+    for file in source_files(gcx, contract.source) {
+        sources.insert(imports.source_unit_name(&file), source_metadata(&file));
+    }
+
+    let metadata = json!({
+        "compiler": { "version": solar_interface::VERSION },
+        "language": "Solidity",
+        "output": output,
+        "settings": settings,
+        "sources": sources,
+        "version": 1,
+    });
+    serde_json::to_string(&metadata).unwrap()
+}
+
+/// Returns the given source and all of its transitive imports, sorted by name.
+fn source_files(gcx: Gcx<'_>, id: hir::SourceId) -> Vec<std::sync::Arc<SourceFile>> {
+    let mut seen = BTreeMap::new();
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        let source = gcx.hir.source(id);
+        if seen.insert(source.file.name.display().to_string(), source.file.clone()).is_none() {
+            stack.extend(source.imports.iter().map(|&(_, import)| import));
+        }
+    }
+    seen.into_values().collect()
+}
+
+/// Returns the `0x`-prefixed Keccak-256 hash of the contents of the given source file.
+pub(crate) fn source_keccak256(file: &SourceFile) -> String {
+    let hash = if file.src_hash.kind() == SourceFileHashAlgorithm::Keccak256 {
+        file.src_hash
+    } else {
+        SourceFileHash::new(SourceFileHashAlgorithm::Keccak256, &file.src)
+    };
+    format!("0x{}", hex::encode(hash.hash_bytes()))
+}
+
+fn source_metadata(file: &SourceFile) -> Value {
+    let mut value = Map::new();
+    value.insert("keccak256".into(), source_keccak256(file).into());
+    if let Some(license) = spdx_license(&file.src) {
+        value.insert("license".into(), license.into());
+    }
+    let urls = [format!("dweb:/ipfs/{}", bs58_encode(&ipfs_hash(file.src.as_bytes())))];
+    value.insert("urls".into(), json!(urls));
+    Value::Object(value)
+}
+
+/// Returns the value of the first `SPDX-License-Identifier` comment in the given source.
+pub(crate) fn spdx_license(src: &str) -> Option<&str> {
+    const TAG: &str = "SPDX-License-Identifier:";
+    let start = src.find(TAG)? + TAG.len();
+    let line = src[start..].lines().next().unwrap_or_default();
+    let license = line.split("*/").next().unwrap_or_default().trim();
+    (!license.is_empty()).then_some(license)
+}
+
+/// Returns the CBOR-encoded metadata trailer that is appended to the runtime bytecode.
+///
+/// This is a CBOR map containing the IPFS hash of `metadata` and the compiler version, followed
+/// by the length of the encoded map as a big-endian `u16`.
+// TODO: Append to the runtime bytecode once bytecode generation is implemented.
+#[allow(dead_code)]
+pub(crate) fn cbor_metadata_trailer(metadata: &str) -> Vec<u8> {
+    let hash = ipfs_hash(metadata.as_bytes());
+    let version = [
+        env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap(),
+        env!("CARGO_PKG_VERSION_MINOR").parse().unwrap(),
+        env!("CARGO_PKG_VERSION_PATCH").parse().unwrap(),
+    ];
+
+    let mut out = Vec::with_capacity(54);
+    // Map with 2 entries.
+    out.push(0xa2);
+    // "ipfs": bytes(34)
+    out.push(0x64);
+    out.extend_from_slice(b"ipfs");
+    out.extend_from_slice(&[0x58, hash.len() as u8]);
+    out.extend_from_slice(&hash);
+    // "solar": bytes(3)
+    out.push(0x65);
+    out.extend_from_slice(b"solar");
+    out.push(0x43);
+    out.extend_from_slice(&version);
+    let len = out.len() as u16;
+    out.extend_from_slice(&len.to_be_bytes());
+    out
+}
+
+/// Returns the IPFS multihash of the given data, as computed by `ipfs add` with CIDv0 and
+/// non-raw leaves.
+///
+/// This is a SHA-256 multihash of the root node of the UnixFS DAG built from 256KiB chunks.
+pub(crate) fn ipfs_hash(data: &[u8]) -> [u8; 34] {
+    const MAX_CHUNK_SIZE: usize = 256 * 1024;
+
+    let mut chunks = Vec::new();
+    let mut data_chunks = data.chunks(MAX_CHUNK_SIZE).peekable();
+    if data_chunks.peek().is_none() {
+        chunks.push(leaf_chunk(&[]));
+    }
+    chunks.extend(data_chunks.map(leaf_chunk));
+    while chunks.len() > 1 {
+        chunks = chunks.chunks(IPFS_MAX_LINKS).map(combine_chunks).collect();
+    }
+    chunks[0].hash
+}
+
+/// The maximum number of links in an IPFS DAG node.
+const IPFS_MAX_LINKS: usize = 174;
+
+struct IpfsChunk {
+    hash: [u8; 34],
+    size: usize,
+    block_size: usize,
+}
+
+fn leaf_chunk(chunk: &[u8]) -> IpfsChunk {
+    // UnixFS `Data { Type: File, Data: chunk, filesize: chunk.len() }`.
+    let mut data = vec![0x08, 0x02];
+    if !chunk.is_empty() {
+        data.push(0x12);
+        encode_varint(chunk.len(), &mut data);
+        data.extend_from_slice(chunk);
+    }
+    data.push(0x18);
+    encode_varint(chunk.len(), &mut data);
+    // `PBNode { Data: data }`.
+    let block = encode_bytes(0x0a, &data);
+    IpfsChunk { hash: multihash(&block), size: chunk.len(), block_size: block.len() }
+}
+
+fn combine_chunks(links: &[IpfsChunk]) -> IpfsChunk {
+    let mut block = Vec::new();
+    let mut lengths = Vec::new();
+    let mut size = 0;
+    let mut block_size = 0;
+    for link in links {
+        size += link.size;
+        block_size += link.block_size;
+        // `PBLink { Hash: link.hash, Name: "", Tsize: link.block_size }`.
+        let mut pb_link = encode_bytes(0x0a, &link.hash);
+        pb_link.extend_from_slice(&[0x12, 0x00, 0x18]);
+        encode_varint(link.block_size, &mut pb_link);
+        block.extend_from_slice(&encode_bytes(0x12, &pb_link));
+        // UnixFS `blocksizes`.
+        lengths.push(0x20);
+        encode_varint(link.size, &mut lengths);
+    }
+    let mut data = vec![0x08, 0x02, 0x18];
+    encode_varint(size, &mut data);
+    data.extend_from_slice(&lengths);
+    block.extend_from_slice(&encode_bytes(0x0a, &data));
+    block_size += block.len();
+    IpfsChunk { hash: multihash(&block), size, block_size }
+}
+
+/// Returns the SHA-256 multihash of the given data.
+fn multihash(data: &[u8]) -> [u8; 34] {
+    let mut hash = [0; 34];
+    hash[0] = 0x12;
+    hash[1] = 0x20;
+    hash[2..].copy_from_slice(&Sha256::digest(data));
+    hash
+}
+
+/// Encodes a length-delimited protobuf field with the given key.
+fn encode_bytes(key: u8, data: &[u8]) -> Vec<u8> {
+    let mut out = vec![key];
+    encode_varint(data.len(), &mut out);
+    out.extend_from_slice(data);
+    out
+}
+
+fn encode_varint(mut n: usize, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Encodes the given data in base58 with the Bitcoin alphabet.
+pub(crate) fn bs58_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in data {
+        let mut carry = byte as usize;
+        for digit in &mut digits {
+            carry += (*digit as usize) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+    let mut out = String::with_capacity(zeros + digits.len());
+    out.extend(std::iter::repeat_n('1', zeros));
+    out.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ipfs() {
+        let hash = |data: &[u8]| bs58_encode(&ipfs_hash(data));
+        assert_eq!(hash(b""), "QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH");
+        assert_eq!(hash(b"hello world\n"), "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o");
+    }
+
+    #[test]
+    fn cbor_trailer() {
+        let metadata = "{}";
+        let trailer = cbor_metadata_trailer(metadata);
+        assert_eq!(trailer.len(), 54);
+        assert_eq!(&trailer[..7], &[0xa2, 0x64, b'i', b'p', b'f', b's', 0x58]);
+        assert_eq!(trailer[7], 34);
+        assert_eq!(&trailer[8..42], &ipfs_hash(metadata.as_bytes()));
+        assert_eq!(&trailer[42..48], &[0x65, b's', b'o', b'l', b'a', b'r']);
+        assert_eq!(trailer[48], 0x43);
+        assert_eq!(&trailer[trailer.len() - 2..], &[0x00, 0x34]);
+    }
+
+    #[test]
+    fn license() {
+        assert_eq!(spdx_license("// SPDX-License-Identifier: MIT\n"), Some("MIT"));
+        assert_eq!(spdx_license("/* SPDX-License-Identifier: GPL-3.0 */"), Some("GPL-3.0"));
+        assert_eq!(spdx_license("contract C {}"), None);
+    }
+
+    #[test]
+    fn source_unit_names() {
+        let sess = solar_interface::Session::builder().with_test_emitter().build();
+        let imports = ImportPaths { remappings: Vec::new(), base_paths: vec!["contracts".into()] };
+        let name = |name: FileName| {
+            let file = sess.source_map().new_source_file(name, || Ok(String::new())).unwrap();
+            imports.source_unit_name(&file)
+        };
+        assert_eq!(name(FileName::Real("contracts/token/A.sol".into())), "token/A.sol");
+        assert_eq!(name(FileName::Real("lib/B.sol".into())), "lib/B.sol");
+    }
+}
@@ -90,6 +90,22 @@ impl<'sess> ParsingContext<'sess> {
         crate::parse_and_resolve(self)
     }
 
+    /// Parses all the loaded sources, recursing into imports, and flattens them into a single
+    /// source file.
+    ///
+    /// See [`flatten`](crate::flatten::flatten) for more details.
+    pub fn flatten(self) -> Result<String> {
+        let sess = self.sess;
+        if self.sources.is_empty() {
+            let msg = "no files found";
+            let note = "if you wish to use the standard input, please specify `-` explicitly";
+            return Err(sess.dcx.err(msg).note(note).emit());
+        }
+        let arenas = ThreadLocal::<ast::Arena>::new();
+        let mut sources = self.parse(&arenas);
+        crate::flatten::flatten(sess, &mut sources)
+    }
+
     /// Parses all the loaded sources, recursing into imports.
     ///
     /// Sources are not guaranteed to be in any particular order, as they may be parsed in parallel.
@@ -398,6 +414,19 @@ impl fmt::Debug for ParsedSource<'_> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for ParsedSource<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("ParsedSource", 3)?;
+        s.serialize_field("file", &self.file.name.display().to_string())?;
+        s.serialize_field("imports", &self.imports)?;
+        s.serialize_field("ast", &self.ast)?;
+        s.end()
+    }
+}
+
 impl ParsedSource<'_> {
     /// Creates a new empty source.
     pub fn new(file: Arc<SourceFile>) -> Self {
@@ -0,0 +1,358 @@
+//! Solc-compatible Standard JSON input and output.
+//!
+//! The output `errors` differ from solc's in that their `type` is `Exception` for all errors,
+//! rather than the kind of error, like `ParserError` or `TypeError`, and their `component` is
+//! always `general`.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/using-the-compiler.html#compiler-input-and-output-json-description>
+
+use crate::{emit, hir, metadata, ty::Gcx, ParsingContext};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use solar_config::{EvmVersion, Library};
+use solar_interface::{source_map::FileName, Result, Session};
+use std::{collections::BTreeMap, io, path::PathBuf};
+
+/// The outputs that can be selected in `settings.outputSelection`.
+const SUPPORTED_OUTPUTS: &[&str] = &[
+    "abi",
+    "devdoc",
+    "evm.methodIdentifiers",
+    "metadata",
+    "storageLayout",
+    "transientStorageLayout",
+    "userdoc",
+];
+
+/// The outputs that require bytecode generation, which is not implemented yet.
+const BYTECODE_OUTPUTS: &[&str] = &["evm.bytecode", "evm.deployedBytecode", "evm.gasEstimates"];
+
+/// A Standard JSON input.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardJsonInput {
+    /// The source code language. Only `Solidity` is supported.
+    pub language: String,
+    /// The source files, keyed by source unit name.
+    pub sources: BTreeMap<String, InputSource>,
+    /// The compiler settings.
+    #[serde(default)]
+    pub settings: Settings,
+}
+
+/// A source file of a [`StandardJsonInput`].
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct InputSource {
+    /// The source code.
+    pub content: Option<String>,
+    /// The local paths to load the source code from if `content` is not given, tried in order.
+    #[serde(default)]
+    pub urls: Vec<String>,
+}
+
+/// The `settings` of a [`StandardJsonInput`].
+///
+/// Settings that only affect code generation, such as `optimizer`, are ignored.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    /// Import remappings: `prefix=target`.
+    #[serde(default)]
+    pub remappings: Vec<String>,
+    /// The EVM version.
+    pub evm_version: Option<EvmVersion>,
+    /// Library addresses, keyed by source unit name and library name.
+    #[serde(default)]
+    pub libraries: BTreeMap<String, BTreeMap<String, String>>,
+    /// The outputs to generate.
+    #[serde(default)]
+    pub output_selection: OutputSelection,
+}
+
+/// The outputs to generate, keyed by source unit name and contract name.
+///
+/// `*` matches all source units or contracts, and the empty contract name selects outputs of the
+/// source unit itself.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct OutputSelection(pub BTreeMap<String, BTreeMap<String, Vec<String>>>);
+
+impl OutputSelection {
+    /// Returns `true` if `output` is selected for the given contract.
+    ///
+    /// An output is also selected by `*` and by any of its dot-separated prefixes, such as `evm`
+    /// for `evm.methodIdentifiers`.
+    pub fn is_selected(&self, file: &str, contract: &str, output: &str) -> bool {
+        self.selected(file, contract).any(|selected| {
+            selected == "*"
+                || output
+                    .strip_prefix(selected)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
+
+    fn selected<'a>(
+        &'a self,
+        file: &'a str,
+        contract: &'a str,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        let files = [file, "*"].into_iter().filter_map(|file| self.0.get(file));
+        files
+            .flat_map(move |contracts| {
+                [contract, "*"].into_iter().filter_map(|contract| contracts.get(contract))
+            })
+            .flatten()
+            .map(String::as_str)
+    }
+}
+
+/// A Standard JSON output.
+#[derive(Debug, Default, Serialize)]
+pub struct StandardJsonOutput {
+    /// The diagnostics, in the solc JSON format.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<Value>,
+    /// The source units, keyed by source unit name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub sources: BTreeMap<String, OutputSource>,
+    /// The selected outputs, keyed by source unit name and contract name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub contracts: BTreeMap<String, BTreeMap<String, Map<String, Value>>>,
+}
+
+/// A source unit of a [`StandardJsonOutput`].
+#[derive(Debug, Serialize)]
+pub struct OutputSource {
+    /// The index of the source unit, as used in source locations.
+    pub id: usize,
+}
+
+impl StandardJsonOutput {
+    /// Returns an output that only contains the given input error.
+    fn json_error(message: String) -> Self {
+        let error = json!({
+            "component": "general",
+            "formattedMessage": message,
+            "message": message,
+            "severity": "error",
+            "type": "JSONError",
+        });
+        Self { errors: vec![error], ..Default::default() }
+    }
+
+    /// Serializes the output to a JSON string.
+    pub fn to_json(&self, pretty: bool) -> String {
+        if pretty { serde_json::to_string_pretty(self) } else { serde_json::to_string(self) }
+            .unwrap()
+    }
+}
+
+/// Compiles the given Standard JSON input.
+///
+/// Diagnostics are only included in the output `errors` if the session emits them to a local
+/// buffer with `JsonEmitter::buffered`.
+pub fn compile(sess: &mut Session, input: &str) -> StandardJsonOutput {
+    let input = match serde_json::from_str::<StandardJsonInput>(input) {
+        Ok(input) => input,
+        Err(e) => return StandardJsonOutput::json_error(format!("invalid input: {e}")),
+    };
+    if let Err(e) = configure(sess, &input.settings) {
+        return StandardJsonOutput::json_error(e);
+    }
+
+    let mut output = StandardJsonOutput::default();
+    let _ = sess.enter(|| {
+        if input.language != "Solidity" {
+            let msg = format!("language `{}` is not supported", input.language);
+            return Err(sess.dcx.err(msg).note("only `Solidity` is supported").emit());
+        }
+        compile_sources(sess, &input, &mut output)
+    });
+    if let Some(diagnostics) = sess.dcx.emitted_diagnostics() {
+        let diagnostics = diagnostics.to_string();
+        output.errors =
+            diagnostics.lines().filter_map(|line| serde_json::from_str(line).ok()).collect();
+    }
+    output
+}
+
+/// Applies the settings to the session.
+fn configure(sess: &mut Session, settings: &Settings) -> Result<(), String> {
+    if let Some(evm_version) = settings.evm_version {
+        sess.evm_version = evm_version;
+    }
+    for (file, libraries) in &settings.libraries {
+        for (name, address) in libraries {
+            let library = format!("{file}:{name}={address}")
+                .parse::<Library>()
+                .map_err(|e| format!("invalid address for library `{file}:{name}`: {e}"))?;
+            sess.libraries.push(library);
+        }
+    }
+    Ok(())
+}
+
+fn compile_sources(
+    sess: &Session,
+    input: &StandardJsonInput,
+    output: &mut StandardJsonOutput,
+) -> Result<()> {
+    let selection = &input.settings.output_selection;
+    let mut unsupported = selection
+        .0
+        .values()
+        .flat_map(BTreeMap::values)
+        .flatten()
+        .filter(|&output| {
+            output != "*"
+                && !SUPPORTED_OUTPUTS.iter().any(|supported| {
+                    supported
+                        .strip_prefix(output.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+                })
+        })
+        .collect::<Vec<_>>();
+    unsupported.sort();
+    unsupported.dedup();
+    for output in unsupported {
+        let msg = format!("output selection `{output}` is not supported");
+        let mut diag = sess.dcx.warn(msg);
+        if BYTECODE_OUTPUTS.iter().any(|bytecode| {
+            output
+                .strip_prefix(bytecode)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        }) {
+            diag = diag.note("bytecode generation is not implemented");
+        }
+        diag.emit();
+    }
+
+    let mut pcx = ParsingContext::new(sess);
+    for remapping in &input.settings.remappings {
+        let Some((prefix, target)) = remapping.split_once('=') else {
+            let msg = format!("invalid remapping `{remapping}`");
+            return Err(sess.dcx.err(msg).note("remappings must be `prefix=target`").emit());
+        };
+        if prefix.contains(':') {
+            let msg = format!("remapping contexts are not supported: `{remapping}`");
+            return Err(sess.dcx.err(msg).emit());
+        }
+        pcx.file_resolver.add_import_map(prefix.into(), target.into());
+    }
+    for (name, source) in &input.sources {
+        let file = sess
+            .source_map()
+            .new_source_file(FileName::Real(PathBuf::from(name)), || load_source(source))
+            .map_err(|e| sess.dcx.err(format!("failed to load source `{name}`: {e}")).emit())?;
+        pcx.add_file(file);
+    }
+
+    crate::parse_and_resolve_with(pcx, |gcx, imports, _| {
+        let mut names =
+            gcx.hir.sources().map(|s| s.file.name.display().to_string()).collect::<Vec<_>>();
+        names.sort();
+        output.sources =
+            names.into_iter().enumerate().map(|(id, name)| (name, OutputSource { id })).collect();
+
+        for id in gcx.hir.contract_ids() {
+            let contract_output = contract_output(gcx, id, selection, imports);
+            if !contract_output.is_empty() {
+                let c = gcx.hir.contract(id);
+                let file = gcx.hir.source(c.source).file.name.display().to_string();
+                output
+                    .contracts
+                    .entry(file)
+                    .or_default()
+                    .insert(c.name.to_string(), contract_output);
+            }
+        }
+        gcx.sess.dcx.has_errors()
+    })
+}
+
+/// Returns the contents of the given source, loading it from its URLs if necessary.
+fn load_source(source: &InputSource) -> io::Result<String> {
+    if let Some(content) = &source.content {
+        return Ok(content.clone());
+    }
+    let mut last_error = None;
+    for url in &source.urls {
+        match std::fs::read_to_string(url) {
+            Ok(content) => return Ok(content),
+            Err(e) => last_error = Some(io::Error::new(e.kind(), format!("{url}: {e}"))),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "source has neither `content` nor `urls`")
+    }))
+}
+
+/// Returns the selected outputs of the given contract.
+fn contract_output(
+    gcx: Gcx<'_>,
+    id: hir::ContractId,
+    selection: &OutputSelection,
+    imports: &metadata::ImportPaths,
+) -> Map<String, Value> {
+    let c = gcx.hir.contract(id);
+    let file = gcx.hir.source(c.source).file.name.display().to_string();
+    let is_selected = |output| selection.is_selected(&file, c.name.as_str(), output);
+
+    let mut output = Map::new();
+    if is_selected("abi") {
+        output.insert("abi".into(), serde_json::to_value(gcx.contract_abi(id)).unwrap());
+    }
+    if is_selected("devdoc") {
+        output.insert("devdoc".into(), emit::natspec::devdoc(gcx, id));
+    }
+    let mut evm = Map::new();
+    if is_selected("evm.methodIdentifiers") {
+        let hashes = emit::method_identifiers(gcx, id);
+        evm.insert("methodIdentifiers".into(), serde_json::to_value(hashes).unwrap());
+    }
+    if !evm.is_empty() {
+        output.insert("evm".into(), evm.into());
+    }
+    if is_selected("metadata") {
+        output.insert("metadata".into(), metadata::contract_metadata(gcx, id, imports).into());
+    }
+    if is_selected("storageLayout") {
+        let layout = gcx.storage_layout(id);
+        let json =
+            emit::storage_layout::storage_layout_json(gcx, id, layout.storage, layout.namespaces);
+        output.insert("storageLayout".into(), serde_json::to_value(json).unwrap());
+    }
+    if is_selected("transientStorageLayout") {
+        let layout = gcx.storage_layout(id).transient;
+        let json = emit::storage_layout::storage_layout_json(gcx, id, layout, &[]);
+        output.insert("transientStorageLayout".into(), serde_json::to_value(json).unwrap());
+    }
+    if is_selected("userdoc") {
+        output.insert("userdoc".into(), emit::natspec::userdoc(gcx, id));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_selection() {
+        let selection: OutputSelection = serde_json::from_value(json!({
+            "*": { "*": ["abi"] },
+            "a.sol": { "A": ["evm.methodIdentifiers"], "": ["ast"] },
+            "b.sol": { "*": ["evm"] },
+            "c.sol": { "C": ["*"] },
+        }))
+        .unwrap();
+        assert!(selection.is_selected("a.sol", "A", "abi"));
+        assert!(selection.is_selected("a.sol", "A", "evm.methodIdentifiers"));
+        assert!(!selection.is_selected("a.sol", "B", "evm.methodIdentifiers"));
+        assert!(selection.is_selected("a.sol", "", "ast"));
+        assert!(!selection.is_selected("a.sol", "A", "ast"));
+        assert!(selection.is_selected("b.sol", "B", "evm.methodIdentifiers"));
+        assert!(!selection.is_selected("b.sol", "B", "evmx"));
+        assert!(selection.is_selected("c.sol", "C", "storageLayout"));
+        assert!(!selection.is_selected("c.sol", "D", "storageLayout"));
+    }
+}
@@ -0,0 +1,419 @@
+//! Storage layout compatibility checks.
+//!
+//! Compares the storage and transient storage layouts of the contracts being compiled against a
+//! baseline, usually a previously deployed version, and reports changes that would corrupt the
+//! storage of an upgradeable proxy. The baseline is either a `storage-layout` and
+//! `transient-storage-layout` JSON output, or the source tree of the previous version.
+
+use crate::{
+    emit::storage_layout::{storage_layout_json, StorageJson, StorageLayoutJson, TypeJson},
+    hir,
+    metadata::ImportPaths,
+    ty::Gcx,
+    ParsingContext,
+};
+use alloy_primitives::U256;
+use serde_json::Value;
+use solar_data_structures::{index::Idx, map::FxHashMap};
+use solar_interface::{Result, Session};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// The label prefix of storage gap variables, which reserve slots for future variables.
+const GAP_PREFIX: &str = "__gap";
+
+/// Checks the storage layouts of all contracts against the baseline at `path`.
+///
+/// `path` is either the output of `--emit storage-layout,transient-storage-layout` or the same
+/// solc `--combined-json` output, or a directory containing the sources of the previous version,
+/// which are compiled with the same import remappings and with the directory as base path.
+///
+/// Contracts are matched by fully qualified name, falling back to the contract name if it is
+/// unique in the baseline. Contracts and layouts that are not in the baseline are not checked.
+pub(crate) fn check(gcx: Gcx<'_>, path: &Path, imports: &ImportPaths) {
+    let baseline = if path.is_dir() {
+        match compile_baseline(gcx.sess, path, imports) {
+            Ok(baseline) => baseline,
+            Err(_) => return,
+        }
+    } else {
+        match read_baseline(path) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                let msg = format!("failed to read storage layout baseline {}: {e}", path.display());
+                gcx.dcx().err(msg).emit();
+                return;
+            }
+        }
+    };
+
+    for id in gcx.hir.contract_ids() {
+        let c = gcx.hir.contract(id);
+        if c.kind.is_interface() || c.kind.is_library() {
+            continue;
+        }
+        let names = [
+            gcx.contract_fully_qualified_name(id).to_string(),
+            format!("{}:{}", imports.source_unit_name(&gcx.hir.source(c.source).file), c.name),
+        ];
+        let Some(old) = find_contract(&baseline, &names, c.name.as_str()) else { continue };
+        let layout = gcx.storage_layout(id);
+        if let Some(old) = &old.storage {
+            let new = storage_layout_json(gcx, id, layout.storage, layout.namespaces);
+            Checker { gcx, contract: c, kind: "storage variable", old, new: &new }.check();
+        }
+        if let Some(old) = &old.transient {
+            let new = storage_layout_json(gcx, id, layout.transient, &[]);
+            Checker { gcx, contract: c, kind: "transient storage variable", old, new: &new }
+                .check();
+        }
+    }
+}
+
+/// The layouts of a contract in the baseline.
+struct BaselineLayouts {
+    storage: Option<StorageLayoutJson>,
+    transient: Option<StorageLayoutJson>,
+}
+
+/// Reads the layouts of the baseline, keyed by contract name.
+fn read_baseline(path: &Path) -> Result<BTreeMap<String, BaselineLayouts>, String> {
+    let s = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let value: Value = serde_json::from_str(&s).map_err(|e| e.to_string())?;
+    let contracts = value
+        .get("contracts")
+        .and_then(Value::as_object)
+        .ok_or("missing `contracts` object, expected combined JSON output")?;
+    let mut layouts = BTreeMap::new();
+    for (name, contract) in contracts {
+        let layout = |keys: [&str; 2]| {
+            let layout = match keys.iter().find_map(|&key| contract.get(key)) {
+                // solc emits nested JSON outputs as strings.
+                Some(Value::String(s)) => serde_json::from_str(s),
+                Some(layout @ Value::Object(_)) => serde_json::from_value(layout.clone()),
+                _ => return Ok(None),
+            };
+            layout.map(Some).map_err(|e| format!("invalid {} of `{name}`: {e}", keys[0]))
+        };
+        let storage = layout(["storage-layout", "storageLayout"])?;
+        let transient = layout(["transient-storage-layout", "transientStorageLayout"])?;
+        if storage.is_some() || transient.is_some() {
+            layouts.insert(name.clone(), BaselineLayouts { storage, transient });
+        }
+    }
+    Ok(layouts)
+}
+
+/// Compiles the sources in the directory `root` and returns their layouts, keyed by
+/// `<source unit name>:<contract name>`.
+fn compile_baseline(
+    sess: &Session,
+    root: &Path,
+    imports: &ImportPaths,
+) -> Result<BTreeMap<String, BaselineLayouts>> {
+    let mut paths = Vec::new();
+    solidity_files(root, &mut paths).map_err(|e| {
+        let msg = format!("failed to read storage layout baseline {}: {e}", root.display());
+        sess.dcx.err(msg).emit()
+    })?;
+    paths.sort();
+
+    let mut pcx = ParsingContext::new(sess);
+    pcx.file_resolver.add_import_path(root.to_path_buf());
+    for (map, path) in imports.remappings() {
+        pcx.file_resolver.add_import_map(map.clone(), path.clone());
+    }
+    pcx.load_files(paths)?;
+
+    let mut layouts = BTreeMap::new();
+    crate::parse_and_resolve_with(pcx, |gcx, imports, _| {
+        for id in gcx.hir.contract_ids() {
+            let c = gcx.hir.contract(id);
+            if c.kind.is_interface() || c.kind.is_library() {
+                continue;
+            }
+            let name = imports.source_unit_name(&gcx.hir.source(c.source).file);
+            let layout = gcx.storage_layout(id);
+            let layouts_json = BaselineLayouts {
+                storage: Some(storage_layout_json(gcx, id, layout.storage, layout.namespaces)),
+                transient: Some(storage_layout_json(gcx, id, layout.transient, &[])),
+            };
+            layouts.insert(format!("{name}:{}", c.name), layouts_json);
+        }
+        Ok(())
+    })?;
+    Ok(layouts)
+}
+
+/// Collects the paths of the `.sol` files in `dir`, recursively.
+fn solidity_files(dir: &Path, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            solidity_files(&path, paths)?;
+        } else if path.extension().is_some_and(|ext| ext == "sol") {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn find_contract<'a>(
+    baseline: &'a BTreeMap<String, BaselineLayouts>,
+    names: &[String],
+    name: &str,
+) -> Option<&'a BaselineLayouts> {
+    if let Some(layout) = names.iter().find_map(|name| baseline.get(name)) {
+        return Some(layout);
+    }
+    let suffix = format!(":{name}");
+    let mut candidates = baseline.iter().filter(|(key, _)| key.ends_with(&suffix));
+    let (_, layout) = candidates.next()?;
+    candidates.next().is_none().then_some(layout)
+}
+
+struct Checker<'a, 'gcx> {
+    gcx: Gcx<'gcx>,
+    contract: &'gcx hir::Contract<'gcx>,
+    /// What the variables of the layout are called in diagnostics.
+    kind: &'static str,
+    old: &'a StorageLayoutJson,
+    new: &'a StorageLayoutJson,
+}
+
+impl Checker<'_, '_> {
+    fn check(&self) {
+        let new_by_label = self
+            .new
+            .storage
+            .iter()
+            .map(|var| (var.label.as_str(), var))
+            .collect::<FxHashMap<_, _>>();
+        let old_by_label = self
+            .old
+            .storage
+            .iter()
+            .map(|var| (var.label.as_str(), var))
+            .collect::<FxHashMap<_, _>>();
+
+        for old in &self.old.storage {
+            if let Some(&new) = new_by_label.get(old.label.as_str()) {
+                if old.label.starts_with(GAP_PREFIX) {
+                    self.check_gap(old, new);
+                } else {
+                    self.check_var(old, new);
+                }
+                continue;
+            }
+
+            // The variable is gone; check whether it was renamed in place.
+            let renamed = self.new.storage.iter().find(|new| {
+                new.slot == old.slot
+                    && new.offset == old.offset
+                    && !old_by_label.contains_key(new.label.as_str())
+            });
+            match renamed {
+                Some(new)
+                    if self.signature(self.old, &old.ty) == self.signature(self.new, &new.ty) =>
+                {
+                    let msg =
+                        format!("{} `{}` was renamed to `{}`", self.kind, old.label, new.label);
+                    self.gcx.dcx().warn(msg).span(self.span(new)).emit();
+                }
+                Some(new) => {
+                    let msg = format!(
+                        "{} `{}` was replaced by `{}` with a different type",
+                        self.kind, old.label, new.label
+                    );
+                    self.gcx
+                        .dcx()
+                        .err(msg)
+                        .span(self.span(new))
+                        .note(format!(
+                            "the type changed from `{}` to `{}`",
+                            self.label(self.old, &old.ty),
+                            self.label(self.new, &new.ty)
+                        ))
+                        .emit();
+                }
+                None => {
+                    let msg = format!("{} `{}` was removed", self.kind, old.label);
+                    self.gcx
+                        .dcx()
+                        .err(msg)
+                        .span(self.contract.name.span)
+                        .note(format!("previously at {}", location(old)))
+                        .emit();
+                }
+            }
+        }
+    }
+
+    fn check_var(&self, old: &StorageJson, new: &StorageJson) {
+        let span = self.span(new);
+        if old.slot != new.slot || old.offset != new.offset {
+            let msg = format!("{} `{}` was moved", self.kind, old.label);
+            self.gcx
+                .dcx()
+                .err(msg)
+                .span(span)
+                .note(format!("previously at {}, now at {}", location(old), location(new)))
+                .emit();
+            return;
+        }
+
+        if self.signature(self.old, &old.ty) == self.signature(self.new, &new.ty) {
+            return;
+        }
+        let old_label = self.label(self.old, &old.ty);
+        let new_label = self.label(self.new, &new.ty);
+        let kind = self.kind;
+        let label = &old.label;
+        if old_label == new_label {
+            let msg = format!("the layout of {kind} `{label}` of type `{new_label}` changed");
+            let mut diag = self.gcx.dcx().err(msg).span(span);
+            if let Some(change) = self.type_change(&old.ty, &new.ty) {
+                diag = diag.note(change);
+            }
+            diag.emit();
+            return;
+        }
+        let msg = if size(self.old, &old.ty) > size(self.new, &new.ty) {
+            format!("{kind} `{label}` was shrunk from `{old_label}` to `{new_label}`")
+        } else {
+            format!("{kind} `{label}` changed type from `{old_label}` to `{new_label}`")
+        };
+        self.gcx.dcx().err(msg).span(span).emit();
+    }
+
+    /// Describes the first incompatible change between two types with the same label, which is
+    /// a change to the members of a struct, possibly nested in mappings, arrays or other structs.
+    fn type_change(&self, old_ty: &str, new_ty: &str) -> Option<String> {
+        let (old, new) = (lookup(self.old, old_ty)?, lookup(self.new, new_ty)?);
+        let nested = |old: &Option<String>, new: &Option<String>| {
+            self.type_change(old.as_deref()?, new.as_deref()?)
+        };
+        if old_ty.starts_with("t_mapping") {
+            return nested(&old.key, &new.key).or_else(|| nested(&old.value, &new.value));
+        }
+        if old_ty.starts_with("t_array") {
+            return nested(&old.base, &new.base);
+        }
+        let (old_members, new_members) = (old.members.as_deref()?, new.members.as_deref()?);
+        let struct_label = &new.label;
+        for i in 0..old_members.len().max(new_members.len()) {
+            let (old, new) = match (old_members.get(i), new_members.get(i)) {
+                (Some(old), Some(new)) => (old, new),
+                (Some(old), None) => {
+                    return Some(format!(
+                        "member `{}` was removed from `{struct_label}`",
+                        old.label
+                    ));
+                }
+                (None, Some(new)) => {
+                    return Some(format!("member `{}` was added to `{struct_label}`", new.label));
+                }
+                (None, None) => unreachable!(),
+            };
+            let member = &new.label;
+            let old_sig = self.signature(self.old, &old.ty);
+            let new_sig = self.signature(self.new, &new.ty);
+            if old_sig != new_sig {
+                let old_label = self.label(self.old, &old.ty);
+                let new_label = self.label(self.new, &new.ty);
+                if old_label == new_label {
+                    if let Some(change) = self.type_change(&old.ty, &new.ty) {
+                        return Some(change);
+                    }
+                }
+                return Some(format!(
+                    "member `{member}` of `{struct_label}` changed type from `{old_label}` to \
+                     `{new_label}`"
+                ));
+            }
+            if old.slot != new.slot || old.offset != new.offset {
+                return Some(format!(
+                    "member `{member}` of `{struct_label}` was moved from {} to {}",
+                    location(old),
+                    location(new)
+                ));
+            }
+        }
+        None
+    }
+
+    /// Checks that a storage gap still ends at the same slot, i.e. that it was shrunk by exactly
+    /// the number of slots used by the variables added before it.
+    fn check_gap(&self, old: &StorageJson, new: &StorageJson) {
+        let slot = |var: &StorageJson| var.slot.parse::<U256>().unwrap_or_default();
+        let slots = |layout, var: &StorageJson| size(layout, &var.ty).div_ceil(U256::from(32));
+        let old_end = slot(old).saturating_add(slots(self.old, old));
+        let new_slot = slot(new);
+        let new_end = new_slot.saturating_add(slots(self.new, new));
+        if old_end == new_end {
+            return;
+        }
+        let msg = format!("storage gap `{}` was resized incorrectly", old.label);
+        let mut diag =
+            self.gcx.dcx().err(msg).span(self.span(new)).note(format!(
+                "the gap must end at slot {old_end}, but now ends at slot {new_end}"
+            ));
+        if old_end > new_slot {
+            diag = diag.help(format!("change the size of the gap to {} slots", old_end - new_slot));
+        }
+        diag.emit();
+    }
+
+    fn span(&self, var: &StorageJson) -> solar_interface::Span {
+        self.gcx.hir.variable(hir::VariableId::from_usize(var.ast_id)).span
+    }
+
+    fn label(&self, layout: &StorageLayoutJson, ty: &str) -> String {
+        lookup(layout, ty).map(|ty| ty.label.clone()).unwrap_or_else(|| ty.to_string())
+    }
+
+    /// Returns a signature of the given type that is independent of the identifiers and names
+    /// of user-defined types, so that two types are compatible iff their signatures are equal.
+    fn signature(&self, layout: &StorageLayoutJson, ty: &str) -> String {
+        let Some(json) = lookup(layout, ty) else { return ty.to_string() };
+        let sig = |ty: &Option<String>| self.signature(layout, ty.as_deref().unwrap_or_default());
+        if ty.starts_with("t_mapping") {
+            format!("mapping({} => {})", sig(&json.key), sig(&json.value))
+        } else if ty.starts_with("t_array") {
+            let len = if json.encoding == "dynamic_array" {
+                ""
+            } else {
+                json.label.rsplit_once('[').map_or("", |(_, len)| len.trim_end_matches(']'))
+            };
+            format!("{}[{len}]", sig(&json.base))
+        } else if ty.starts_with("t_struct") {
+            let members = json
+                .members
+                .iter()
+                .flatten()
+                .map(|m| format!("{}@{}:{}", self.signature(layout, &m.ty), m.slot, m.offset))
+                .collect::<Vec<_>>();
+            format!("struct({})", members.join(","))
+        } else if ty.starts_with("t_contract") {
+            "address".into()
+        } else if ty.starts_with("t_enum") || ty.starts_with("t_userDefinedValueType") {
+            format!("{}{}", &ty[2..ty.find('(').unwrap_or(ty.len())], json.number_of_bytes)
+        } else {
+            json.label.clone()
+        }
+    }
+}
+
+fn lookup<'a>(layout: &'a StorageLayoutJson, ty: &str) -> Option<&'a TypeJson> {
+    layout.types.as_ref()?.get(ty)
+}
+
+fn size(layout: &StorageLayoutJson, ty: &str) -> U256 {
+    lookup(layout, ty).and_then(|ty| ty.number_of_bytes.parse().ok()).unwrap_or_default()
+}
+
+fn location(var: &StorageJson) -> String {
+    format!("slot {}, offset {}", var.slot, var.offset)
+}
@@ -1,7 +1,11 @@
 use super::{Gcx, Ty, TyKind};
-use crate::hir;
+use crate::hir::{self, Visit};
 use alloy_json_abi as json;
 use solar_ast::ast::ElementaryType;
+use solar_data_structures::{
+    map::{FxHashSet, FxIndexSet},
+    BumpExt, Never,
+};
 use std::{fmt, ops::ControlFlow};
 
 impl<'gcx> Gcx<'gcx> {
@@ -41,9 +45,7 @@ impl<'gcx> Gcx<'gcx> {
         for f in self.interface_functions(id) {
             items.push(self.function_abi(f.id).into());
         }
-        // TODO: Does not include referenced items.
-        // See solc `interfaceEvents` and `interfaceErrors`.
-        for item in self.hir.contract_item_ids(id) {
+        for &item in self.interface_events_and_errors(id) {
             match item {
                 hir::ItemId::Event(id) => items.push(self.event_abi(id).into()),
                 hir::ItemId::Error(id) => items.push(self.error_abi(id).into()),
@@ -136,6 +138,138 @@ fn json_state_mutability(s: hir::StateMutability) -> json::StateMutability {
     }
 }
 
+/// Returns the events and errors that are part of the interface of the given contract.
+///
+/// These are the events and errors defined in the contract and its bases, followed by the ones
+/// that are emitted or reverted with in the functions of the contract and its bases, including
+/// through calls to library and free functions. Events and errors with the same signature are only
+/// included once, keeping the first one, like in solc.
+///
+/// Reference: solc `ContractDefinition::interfaceEvents` and `interfaceErrors`.
+pub(super) fn interface_events_and_errors(gcx: Gcx<'_>, id: hir::ContractId) -> &[hir::ItemId] {
+    let mut collector =
+        EventErrorCollector { gcx, items: FxIndexSet::default(), visited: FxHashSet::default() };
+    collector.items.extend(
+        gcx.hir
+            .contract_item_ids(id)
+            .filter(|item| matches!(item, hir::ItemId::Event(_) | hir::ItemId::Error(_))),
+    );
+    for &base in gcx.hir.contract(id).linearized_bases {
+        let _ = collector.visit_nested_contract(base);
+    }
+    let mut signatures = FxHashSet::default();
+    gcx.bump().alloc_from_iter(collector.items.into_iter().filter(|&item| {
+        signatures.insert((matches!(item, hir::ItemId::Event(_)), gcx.item_signature(item)))
+    }))
+}
+
+/// Collects the events and errors referenced by `emit`, `revert`, and error constructor calls.
+///
+/// Overloads are resolved by the number of arguments, and by their names if named arguments are
+/// used, since resolving them by type requires type checking of the arguments. If several
+/// overloads remain, all of them are collected, unlike in solc.
+struct EventErrorCollector<'gcx> {
+    gcx: Gcx<'gcx>,
+    items: FxIndexSet<hir::ItemId>,
+    visited: FxHashSet<hir::FunctionId>,
+}
+
+impl EventErrorCollector<'_> {
+    fn collect(&mut self, candidates: &[hir::Res], args: &hir::CallArgs<'_>) {
+        for &res in candidates {
+            if let hir::Res::Item(item @ (hir::ItemId::Event(_) | hir::ItemId::Error(_))) = res {
+                if candidates.len() == 1 || self.accepts(item, args) {
+                    self.items.insert(item);
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the given event or error can be called with the given arguments.
+    fn accepts(&self, item: hir::ItemId, args: &hir::CallArgs<'_>) -> bool {
+        let params = self.gcx.item_parameters(item);
+        match *args {
+            hir::CallArgs::Unnamed(args) => params.len() == args.len(),
+            hir::CallArgs::Named(args) => {
+                params.len() == args.len()
+                    && args.iter().all(|arg| {
+                        params.iter().any(|&p| {
+                            self.gcx.hir.variable(p).name.is_some_and(|n| n.name == arg.name.name)
+                        })
+                    })
+            }
+        }
+    }
+}
+
+impl<'gcx> Visit<'gcx> for EventErrorCollector<'gcx> {
+    type BreakValue = Never;
+
+    fn hir(&self) -> &'gcx hir::Hir<'gcx> {
+        &self.gcx.hir
+    }
+
+    fn visit_nested_function(&mut self, id: hir::FunctionId) -> ControlFlow<Self::BreakValue> {
+        if !self.visited.insert(id) {
+            return ControlFlow::Continue(());
+        }
+        self.visit_function(self.gcx.hir.function(id))
+    }
+
+    fn visit_stmt(&mut self, stmt: &'gcx hir::Stmt<'gcx>) -> ControlFlow<Self::BreakValue> {
+        if let hir::StmtKind::Emit(res, ref args) | hir::StmtKind::Revert(res, ref args) = stmt.kind
+        {
+            self.collect(res, args);
+        }
+        self.walk_stmt(stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'gcx hir::Expr<'gcx>) -> ControlFlow<Self::BreakValue> {
+        match expr.kind {
+            // Custom errors passed to `require`.
+            hir::ExprKind::Call(callee, ref args) => {
+                if let hir::ExprKind::Ident(res) = callee.peel_parens().kind {
+                    self.collect(res, args);
+                }
+            }
+            // Calls to library functions.
+            hir::ExprKind::Member(base, member) => {
+                if let hir::ExprKind::Ident(res) = base.peel_parens().kind {
+                    for &res in res {
+                        let Ok(lib) = hir::ContractId::try_from(res) else { continue };
+                        let lib = self.gcx.hir.contract(lib);
+                        if !lib.kind.is_library() {
+                            continue;
+                        }
+                        for f in lib.functions() {
+                            if self.gcx.hir.function(f).name.is_some_and(|n| n.name == member.name)
+                            {
+                                self.visit_nested_function(f)?;
+                            }
+                        }
+                    }
+                }
+            }
+            // Internal calls to free and library functions.
+            hir::ExprKind::Ident(res) => {
+                for &res in res {
+                    if let hir::Res::Item(hir::ItemId::Function(f)) = res {
+                        let func = self.gcx.hir.function(f);
+                        let in_library = func
+                            .contract
+                            .is_some_and(|c| self.gcx.hir.contract(c).kind.is_library());
+                        if func.is_free() || in_library {
+                            self.visit_nested_function(f)?;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.walk_expr(expr)
+    }
+}
+
 struct TyPrinter<'gcx, W: fmt::Write> {
     gcx: Gcx<'gcx>,
     buf: W,
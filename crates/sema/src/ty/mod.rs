@@ -3,7 +3,7 @@ use crate::{
     builtins::{members, Builtin},
     hir::{self, Hir},
 };
-use alloy_primitives::{keccak256, Selector, B256};
+use alloy_primitives::{keccak256, Selector, B256, U256};
 use solar_ast::ast::{DataLocation, StateMutability, TypeSize, Visibility};
 use solar_data_structures::{
     fmt_from_fn,
@@ -28,6 +28,13 @@ pub use common::{CommonTypes, EachDataLoc};
 mod interner;
 use interner::Interner;
 
+mod natspec;
+
+mod storage;
+pub use storage::{
+    erc7201_slot, StorageItem, StorageItems, StorageLayout, StorageNamespace, StorageSize,
+};
+
 #[allow(clippy::module_inception)]
 mod ty;
 pub use ty::{Ty, TyData, TyFlags, TyFnPtr, TyKind};
@@ -455,12 +462,13 @@ cached! {
 ///
 /// The solc implementation excludes inheritance: <https://github.com/ethereum/solidity/blob/ad2644c52b3afbe80801322c5fe44edb59383500/libsolidity/ast/AST.cpp#L310-L316>
 ///
+/// For contracts other than interfaces, which don't have a `type(C).interfaceId`, this is
+/// computed the same way over the external functions defined in the contract itself.
+///
 /// See [ERC-165] for more details.
 ///
 /// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
 pub fn interface_id(gcx: _, id: hir::ContractId) -> Selector {
-    let kind = gcx.hir.contract(id).kind;
-    assert!(kind.is_interface(), "{kind} {id:?} is not an interface");
     let selectors = gcx.interface_functions(id).own().iter().map(|f| f.selector);
     let iid = selectors.fold(Selector::ZERO, std::ops::BitXor::bitxor);
     debug!("{}.interfaceId = {iid}", gcx.contract_fully_qualified_name(id));
@@ -598,6 +606,28 @@ pub fn struct_field_types(gcx: _, id: hir::StructId) -> &'gcx [Ty<'gcx>] {
     gcx.mk_ty_iter(gcx.hir.strukt(id).fields.iter().map(|&f| gcx.type_of_item(f.into())))
 }
 
+/// Returns the storage layout of the given contract.
+///
+/// State variables are laid out in the order of the contract's linearized bases, starting from the
+/// most base contract. Constants and immutables are not stored, and transient variables are laid
+/// out separately.
+pub fn storage_layout(gcx: _, id: hir::ContractId) -> StorageLayout<'gcx> {
+    storage::contract_storage_layout(gcx, id)
+}
+
+/// Returns the storage layout of the fields of the given struct, relative to its first slot.
+///
+/// Returns `None` if the struct does not fit in storage.
+pub fn struct_storage_layout(gcx: _, id: hir::StructId) -> Option<StorageItems<'gcx>> {
+    gcx.layout_storage_items(U256::ZERO, gcx.hir.strukt(id).fields.iter().copied())
+}
+
+/// Returns the events and errors that are part of the ABI of the given contract, including the
+/// ones that are only referenced by `emit` and `revert`.
+pub fn interface_events_and_errors(gcx: _, id: hir::ContractId) -> &'gcx [hir::ItemId] {
+    abi::interface_events_and_errors(gcx, id)
+}
+
 /// Returns the members of the given type.
 pub fn members_of(gcx: _, ty: Ty<'gcx>) -> members::MemberList<'gcx> {
     members::members_of(gcx, ty)
@@ -0,0 +1,134 @@
+//! NatSpec documentation resolution.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/natspec-format.html#inheritance-notes>
+
+use super::Gcx;
+use crate::hir::{self, NatSpecItem, NatSpecKind};
+use solar_interface::Symbol;
+
+impl<'gcx> Gcx<'gcx> {
+    /// Returns the NatSpec documentation of the given item, including inherited tags.
+    ///
+    /// Tags are inherited from the function named by `@inheritdoc`, or, if the item has no
+    /// documentation, from the first documented virtual function it overrides. Tags of the item
+    /// itself take precedence over inherited ones.
+    pub fn item_natspec(self, id: hir::ItemId) -> Vec<NatSpecItem<'gcx>> {
+        let own = self.hir.natspec(id);
+        let base_item = self.natspec_base_item(id);
+
+        let mut items = own
+            .iter()
+            .filter(|item| !matches!(item.kind, NatSpecKind::Inheritdoc { .. }))
+            .copied()
+            .collect::<Vec<_>>();
+        let Some(base_item) = base_item else { return items };
+        let params = self.item_parameters_opt(id).unwrap_or_default();
+        let base_params = self.item_parameters_opt(base_item).unwrap_or_default();
+        let returns = self.natspec_returns(id);
+        let base_returns = self.natspec_returns(base_item);
+        let has_returns = items.iter().any(|item| item.kind == NatSpecKind::Return);
+        let mut return_index = 0;
+        for mut inherited in self.item_natspec(base_item) {
+            if let NatSpecKind::Param { name } = &mut inherited.kind {
+                // Parameters may be renamed in the overriding function.
+                let index = base_params
+                    .iter()
+                    .position(|&p| self.item_name_opt(p).is_some_and(|n| n.name == *name));
+                if let Some(p) =
+                    index.and_then(|i| params.get(i)).and_then(|&p| self.item_name_opt(p))
+                {
+                    *name = p.name;
+                }
+            } else if inherited.kind == NatSpecKind::Return {
+                // So may named return parameters, whose documentation starts with the name.
+                let name = |vars: &[hir::VariableId]| {
+                    vars.get(return_index).and_then(|&v| self.item_name_opt(v))
+                };
+                let mut content = inherited.content;
+                if let Some(base_name) = name(base_returns) {
+                    content =
+                        content.strip_prefix(base_name.as_str()).map_or(content, str::trim_start);
+                }
+                if let Some(name) = name(returns) {
+                    content = self.bump().alloc_str(&format!("{name} {content}"));
+                }
+                inherited.content = content;
+                return_index += 1;
+            }
+            let overridden = match inherited.kind {
+                NatSpecKind::Return => has_returns,
+                kind => items.iter().any(|item| item.kind == kind),
+            };
+            if !overridden {
+                items.push(inherited);
+            }
+        }
+        items
+    }
+
+    /// Returns the item that the given item inherits its NatSpec documentation from, if any.
+    ///
+    /// See [`item_natspec`](Self::item_natspec).
+    pub fn natspec_base_item(self, id: hir::ItemId) -> Option<hir::ItemId> {
+        let own = self.hir.natspec(id);
+        match own.iter().find_map(|item| match item.kind {
+            NatSpecKind::Inheritdoc { contract } => Some(contract),
+            _ => None,
+        }) {
+            Some(contract) => {
+                self.inheritdoc_base(id, contract).and_then(|base| self.overridden_item(id, base))
+            }
+            None if own.is_empty() => {
+                let bases =
+                    self.hir.item(id).contract().map(|c| self.hir.contract(c).linearized_bases);
+                bases.unwrap_or_default().iter().skip(1).find_map(|&base| {
+                    let base_item = self.overridden_item(id, base)?;
+                    let virtual_ =
+                        base_item.as_function().is_some_and(|f| self.hir.function(f).virtual_);
+                    (virtual_ && !self.item_natspec(base_item).is_empty()).then_some(base_item)
+                })
+            }
+            None => None,
+        }
+    }
+
+    /// Returns the base contract named by `@inheritdoc <contract>` on the given item.
+    ///
+    /// This is looked up in the linearized bases of the contract the item is defined in,
+    /// excluding the contract itself.
+    pub fn inheritdoc_base(self, id: hir::ItemId, contract: Symbol) -> Option<hir::ContractId> {
+        let c = self.hir.contract(self.hir.item(id).contract()?);
+        c.linearized_bases
+            .iter()
+            .skip(1)
+            .copied()
+            .find(|&base| self.hir.contract(base).name.name == contract)
+    }
+
+    /// Returns the function or public state variable defined in `base` that has the same
+    /// signature as the given function or public state variable.
+    pub fn overridden_item(self, id: hir::ItemId, base: hir::ContractId) -> Option<hir::ItemId> {
+        let signature = self.natspec_signature(id)?;
+        self.hir.contract(base).items.iter().copied().find(|&item| {
+            matches!(item, hir::ItemId::Function(_) | hir::ItemId::Variable(_))
+                && self.natspec_signature(item) == Some(signature)
+        })
+    }
+
+    fn natspec_returns(self, id: hir::ItemId) -> &'gcx [hir::VariableId] {
+        match id {
+            hir::ItemId::Function(f) => self.hir.function(f).returns,
+            _ => &[],
+        }
+    }
+
+    fn natspec_signature(self, id: hir::ItemId) -> Option<&'gcx str> {
+        let f = match id {
+            hir::ItemId::Function(f) => f,
+            hir::ItemId::Variable(v) => self.hir.variable(v).getter?,
+            _ => return None,
+        };
+        self.hir.function(f).name?;
+        Some(self.item_signature(f.into()))
+    }
+}
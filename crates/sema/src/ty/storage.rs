@@ -0,0 +1,271 @@
+//! Storage layout.
+//!
+//! Reference: <https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html>
+
+use super::{Gcx, Ty, TyKind};
+use crate::{eval::ConstantEvaluator, hir};
+use alloy_primitives::{keccak256, U256};
+use solar_ast::ast::{DataLocation, ElementaryType, VarMut, Visibility};
+
+/// The storage layout of a contract.
+///
+/// Return type of [`Gcx::storage_layout`].
+#[derive(Clone, Copy, Debug)]
+pub struct StorageLayout<'gcx> {
+    /// The persistent storage variables, including inherited ones.
+    pub storage: StorageItems<'gcx>,
+    /// The transient storage variables, including inherited ones.
+    pub transient: StorageItems<'gcx>,
+    /// The ERC-7201 namespaced storage structs defined in the contract and its bases.
+    pub namespaces: &'gcx [StorageNamespace<'gcx>],
+}
+
+/// An ERC-7201 namespaced storage struct, annotated with
+/// `@custom:storage-location erc7201:<namespace>`.
+///
+/// Reference: <https://eips.ethereum.org/EIPS/eip-7201>
+#[derive(Clone, Copy, Debug)]
+pub struct StorageNamespace<'gcx> {
+    /// The struct ID.
+    pub id: hir::StructId,
+    /// The namespace ID, without the `erc7201:` prefix.
+    pub namespace: &'gcx str,
+    /// The fields of the struct, laid out starting at the namespace root slot.
+    pub items: StorageItems<'gcx>,
+}
+
+/// A list of variables laid out in consecutive storage slots.
+#[derive(Clone, Copy, Debug)]
+pub struct StorageItems<'gcx> {
+    /// The variables, in layout order.
+    pub items: &'gcx [StorageItem<'gcx>],
+    /// The total number of slots occupied by the variables.
+    pub slots: U256,
+}
+
+impl<'gcx> StorageItems<'gcx> {
+    /// Returns an iterator over the variables.
+    pub fn iter(&self) -> std::slice::Iter<'gcx, StorageItem<'gcx>> {
+        self.items.iter()
+    }
+
+    /// Returns `true` if there are no variables.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// A variable in a storage layout.
+#[derive(Clone, Copy, Debug)]
+pub struct StorageItem<'gcx> {
+    /// The variable ID.
+    pub id: hir::VariableId,
+    /// The type of the variable, without the data location.
+    pub ty: Ty<'gcx>,
+    /// The slot of the variable.
+    pub slot: U256,
+    /// The byte offset of the variable in the slot.
+    pub offset: u8,
+}
+
+/// The size of a type in storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageSize {
+    /// The number of bytes occupied in a slot. This is 32 for all non-value types.
+    pub bytes: u8,
+    /// The number of slots occupied.
+    pub slots: U256,
+}
+
+impl StorageSize {
+    const WORD: Self = Self { bytes: 32, slots: U256::from_limbs([1, 0, 0, 0]) };
+
+    const fn bytes(bytes: u8) -> Self {
+        Self { bytes, slots: U256::from_limbs([1, 0, 0, 0]) }
+    }
+
+    /// Returns the number of bytes occupied by the type, as reported by `numberOfBytes`.
+    pub fn number_of_bytes(self) -> U256 {
+        if self.slots == U256::from(1) {
+            U256::from(self.bytes)
+        } else {
+            self.slots.saturating_mul(U256::from(32))
+        }
+    }
+}
+
+impl<'gcx> Gcx<'gcx> {
+    /// Returns the base slot of the storage layout of the given contract, as specified with
+    /// `layout at <slot>`, or zero if not specified.
+    ///
+    /// Returns zero if the slot cannot be evaluated, which is reported during type checking.
+    pub fn storage_base_slot(self, id: hir::ContractId) -> U256 {
+        let Some(slot) = self.hir.contract(id).layout else { return U256::ZERO };
+        ConstantEvaluator::new(self).try_eval(slot).map_or(U256::ZERO, |slot| slot.data)
+    }
+
+    /// Returns the size of the given type in storage.
+    ///
+    /// Returns `None` if the type cannot be stored or if it is too large.
+    pub fn type_storage_size(self, ty: Ty<'gcx>) -> Option<StorageSize> {
+        Some(match ty.kind {
+            TyKind::Elementary(ty) => match ty {
+                ElementaryType::Address(_) => StorageSize::bytes(20),
+                ElementaryType::Bool => StorageSize::bytes(1),
+                ElementaryType::String | ElementaryType::Bytes => StorageSize::WORD,
+                ElementaryType::Fixed(size, _)
+                | ElementaryType::UFixed(size, _)
+                | ElementaryType::Int(size)
+                | ElementaryType::UInt(size)
+                | ElementaryType::FixedBytes(size) => StorageSize::bytes(size.bytes()),
+            },
+            TyKind::Contract(_) => StorageSize::bytes(20),
+            TyKind::Enum(_) => StorageSize::bytes(1),
+            TyKind::Udvt(ty, _) => return self.type_storage_size(ty),
+            TyKind::FnPtr(f) => {
+                StorageSize::bytes(if f.visibility == Visibility::External { 24 } else { 8 })
+            }
+            TyKind::Ref(ty, _) => return self.type_storage_size(ty),
+            TyKind::DynArray(_) | TyKind::Mapping(..) => StorageSize::WORD,
+            TyKind::Array(elem, len) => {
+                let elem = self.type_storage_size(elem)?;
+                let slots = if elem.slots == U256::from(1) && elem.bytes < 32 {
+                    let per_slot = U256::from(32 / elem.bytes);
+                    len.div_ceil(per_slot)
+                } else {
+                    len.checked_mul(elem.slots)?
+                };
+                StorageSize { bytes: 32, slots: slots.max(U256::from(1)) }
+            }
+            TyKind::Struct(id) => {
+                let slots = self.struct_storage_layout(id)?.slots;
+                StorageSize { bytes: 32, slots: slots.max(U256::from(1)) }
+            }
+            _ => return None,
+        })
+    }
+
+    /// Lays out the given variables in consecutive slots starting at `base_slot`, packing value
+    /// types into the same slot when they fit.
+    ///
+    /// Returns `None` if the layout does not fit in storage.
+    pub(super) fn layout_storage_items(
+        self,
+        base_slot: U256,
+        vars: impl IntoIterator<Item = hir::VariableId>,
+    ) -> Option<StorageItems<'gcx>> {
+        // Lay out relative to the base slot so that the last slot of storage can be used.
+        let mut items = Vec::new();
+        let mut slot = U256::ZERO;
+        let mut offset = 0u8;
+        for id in vars {
+            let ty = self.type_of_item(id.into()).peel_refs();
+            if ty.has_error() {
+                continue;
+            }
+            let size = self.type_storage_size(ty)?;
+            if offset as usize + size.bytes as usize > 32 {
+                slot = slot.checked_add(U256::from(1))?;
+                offset = 0;
+            }
+            items.push(StorageItem { id, ty, slot, offset });
+            if size.slots == U256::from(1) && offset as usize + size.bytes as usize <= 32 {
+                offset += size.bytes;
+            } else {
+                slot = slot.checked_add(size.slots)?;
+                offset = 0;
+            }
+        }
+        if offset > 0 {
+            slot = slot.checked_add(U256::from(1))?;
+        }
+        let slots = slot;
+        if !slots.is_zero() {
+            base_slot.checked_add(slots - U256::from(1))?;
+        }
+        for item in &mut items {
+            item.slot += base_slot;
+        }
+        Some(StorageItems { items: self.bump().alloc_slice_copy(&items), slots })
+    }
+}
+
+/// The formula prefix of ERC-7201 `@custom:storage-location` annotations.
+pub(crate) const ERC7201_PREFIX: &str = "erc7201:";
+
+/// Returns the ERC-7201 root slot of the given namespace ID.
+///
+/// This is `keccak256(abi.encode(uint256(keccak256(namespace)) - 1)) & ~bytes32(uint256(0xff))`.
+pub fn erc7201_slot(namespace: &str) -> U256 {
+    let id = U256::from_be_bytes(keccak256(namespace).0).wrapping_sub(U256::from(1));
+    let slot = U256::from_be_bytes(keccak256(id.to_be_bytes::<32>()).0);
+    slot & !U256::from(0xff)
+}
+
+impl<'gcx> Gcx<'gcx> {
+    /// Returns the ERC-7201 namespace ID of the given struct, if it is annotated with
+    /// `@custom:storage-location erc7201:<namespace>`.
+    pub fn struct_storage_namespace(self, id: hir::StructId) -> Option<&'gcx str> {
+        self.hir.natspec(id).iter().find_map(|item| match item.kind {
+            hir::NatSpecKind::Custom { name } if name.as_str() == "storage-location" => {
+                item.content.strip_prefix(ERC7201_PREFIX).filter(|ns| !ns.is_empty())
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Computes the storage layout of the given contract.
+pub(super) fn contract_storage_layout<'gcx>(
+    gcx: Gcx<'gcx>,
+    id: hir::ContractId,
+) -> StorageLayout<'gcx> {
+    let c = gcx.hir.contract(id);
+    let state_vars = c.linearized_bases.iter().rev().flat_map(|&base| {
+        gcx.hir.contract(base).variables().filter(|&v| {
+            let v = gcx.hir.variable(v);
+            !matches!(v.mutability, Some(VarMut::Constant | VarMut::Immutable))
+        })
+    });
+    let (transient, persistent): (Vec<_>, Vec<_>) = state_vars
+        .partition(|&v| gcx.hir.variable(v).data_location == Some(DataLocation::Transient));
+
+    let layout = |base_slot, vars: Vec<hir::VariableId>| {
+        gcx.layout_storage_items(base_slot, vars).unwrap_or_else(|| {
+            let msg = "contract requires too much storage";
+            gcx.dcx().err(msg).span(c.name.span).emit();
+            StorageItems { items: &[], slots: U256::ZERO }
+        })
+    };
+    let namespaces = c
+        .linearized_bases
+        .iter()
+        .rev()
+        .flat_map(|&base| gcx.hir.contract(base).items.iter().filter_map(hir::ItemId::as_struct))
+        .filter_map(|id| {
+            let namespace = gcx.struct_storage_namespace(id)?;
+            let fields = gcx.hir.strukt(id).fields.iter().copied();
+            let items = gcx.layout_storage_items(erc7201_slot(namespace), fields)?;
+            Some(StorageNamespace { id, namespace, items })
+        })
+        .collect::<Vec<_>>();
+
+    StorageLayout {
+        // The base slot only applies to persistent storage.
+        storage: layout(gcx.storage_base_slot(id), persistent),
+        transient: layout(U256::ZERO, transient),
+        namespaces: gcx.bump().alloc_slice_copy(&namespaces),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn erc7201() {
+        // Example from the EIP.
+        let expected = "0x183a6125c38840424c4a85fa12bab2ab606c4b6d0e7cc73c0c06ba5300eab500";
+        assert_eq!(erc7201_slot("example.main"), expected.parse::<U256>().unwrap());
+    }
+}
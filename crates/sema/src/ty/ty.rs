@@ -15,6 +15,14 @@ impl fmt::Debug for Ty<'_> {
     }
 }
 
+/// Serializes the type's kind. Types are interned, so nested types are serialized in full.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Ty<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.kind.serialize(serializer)
+    }
+}
+
 impl<'gcx> std::ops::Deref for Ty<'gcx> {
     type Target = &'gcx TyData<'gcx>;
 
@@ -246,6 +254,7 @@ impl fmt::Debug for TyData<'_> {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TyKind<'gcx> {
     /// An elementary/primitive type.
     Elementary(ElementaryType),
@@ -266,7 +275,7 @@ pub enum TyKind<'gcx> {
     DynArray(Ty<'gcx>),
 
     /// Fixed-size array: `T[N]`.
-    Array(Ty<'gcx>, U256),
+    Array(Ty<'gcx>, #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_u256"))] U256),
 
     /// Tuple: `(T1, T2, ...)`.
     Tuple(&'gcx [Ty<'gcx>]),
@@ -314,7 +323,13 @@ pub enum TyKind<'gcx> {
     Err(ErrorGuaranteed),
 }
 
+#[cfg(feature = "serde")]
+fn serialize_u256<S: serde::Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TyFnPtr<'gcx> {
     pub parameters: &'gcx [Ty<'gcx>],
     pub returns: &'gcx [Ty<'gcx>],
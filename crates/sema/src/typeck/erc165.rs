@@ -0,0 +1,276 @@
+//! ERC-165 `supportsInterface` checks.
+//!
+//! Reference: <https://eips.ethereum.org/EIPS/eip-165>
+
+use crate::{
+    builtins::Builtin,
+    eval::ConstantEvaluator,
+    hir::{self, Visit},
+    ty::Gcx,
+};
+use alloy_primitives::Selector;
+use solar_ast::ast::{LitKind, VarMut};
+use solar_data_structures::{map::FxHashSet, Never};
+use std::ops::ControlFlow;
+
+/// Checks that the `supportsInterface(bytes4)` function defined in the given contract handles the
+/// interface IDs of all the interfaces that the contract inherits.
+///
+/// An interface ID is considered handled if it is referenced with `type(I).interfaceId`, as a
+/// literal, as `bytes4(keccak256(...))`, or as the XOR of any of these, either directly or through
+/// constants, internal calls, and calls to the overridden implementations with `super`.
+///
+/// Implementations that read state variables, such as a mapping of registered interface IDs, are
+/// not checked, since the IDs they handle are only known at runtime.
+pub(super) fn check_supports_interface(gcx: Gcx<'_>, id: hir::ContractId) {
+    let c = gcx.hir.contract(id);
+    if c.kind.is_interface() || c.kind.is_library() {
+        return;
+    }
+    let Some(f) = supports_interface(gcx, id) else { return };
+
+    let mut collector = InterfaceIdCollector {
+        gcx,
+        contract: id,
+        current: id,
+        ids: FxHashSet::default(),
+        visited: FxHashSet::default(),
+        reads_state: false,
+    };
+    let _ = collector.visit_nested_function(f);
+    if collector.reads_state {
+        return;
+    }
+
+    let span = gcx.hir.function(f).name.unwrap().span;
+    for &base in c.linearized_bases.iter().skip(1) {
+        let b = gcx.hir.contract(base);
+        if !b.kind.is_interface() {
+            continue;
+        }
+        let interface_id = gcx.interface_id(base);
+        // Interfaces that don't define any functions cannot be detected.
+        if interface_id == Selector::ZERO || collector.ids.contains(&interface_id) {
+            continue;
+        }
+        let name = b.name;
+        let msg = format!(
+            "`supportsInterface` does not handle the ID of the inherited interface `{name}`"
+        );
+        gcx.dcx()
+            .warn(msg)
+            .span(span)
+            .note(format!("the interface ID of `{name}` is `{interface_id}`"))
+            .help(format!("compare the argument against `type({name}).interfaceId`"))
+            .emit();
+    }
+}
+
+/// Returns the implemented `supportsInterface(bytes4)` function defined in the given contract.
+fn supports_interface(gcx: Gcx<'_>, id: hir::ContractId) -> Option<hir::FunctionId> {
+    gcx.hir.contract(id).functions().find(|&f| {
+        let func = gcx.hir.function(f);
+        func.body.is_some()
+            && func.name.is_some_and(|name| name.as_str() == "supportsInterface")
+            && gcx.item_signature(f.into()) == "supportsInterface(bytes4)"
+    })
+}
+
+/// Collects the interface IDs referenced in a function.
+struct InterfaceIdCollector<'gcx> {
+    gcx: Gcx<'gcx>,
+    /// The contract being checked, whose linearization `super` refers to.
+    contract: hir::ContractId,
+    /// The contract of the function being visited.
+    current: hir::ContractId,
+    ids: FxHashSet<Selector>,
+    visited: FxHashSet<hir::ItemId>,
+    /// Whether a non-constant state variable is read.
+    reads_state: bool,
+}
+
+impl InterfaceIdCollector<'_> {
+    /// Returns the interface ID that the given expression evaluates to, if it is a composition of
+    /// literals, `type(I).interfaceId`, `bytes4(keccak256(...))` and constants with `^`.
+    fn interface_id(&self, expr: &hir::Expr<'_>, depth: usize) -> Option<Selector> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        match expr.peel_parens().kind {
+            hir::ExprKind::Lit(lit) => literal_id(lit),
+            hir::ExprKind::Member(base, member) => match base.peel_parens().kind {
+                hir::ExprKind::TypeCall(hir::Type {
+                    kind: hir::TypeKind::Custom(hir::ItemId::Contract(i)),
+                    ..
+                }) if member.as_str() == "interfaceId"
+                    && self.gcx.hir.contract(i).kind.is_interface() =>
+                {
+                    Some(self.gcx.interface_id(i))
+                }
+                _ => None,
+            },
+            hir::ExprKind::Binary(l, op, r) if op.kind == hir::BinOpKind::BitXor => {
+                Some(self.interface_id(l, depth + 1)? ^ self.interface_id(r, depth + 1)?)
+            }
+            hir::ExprKind::Call(callee, hir::CallArgs::Unnamed([arg])) => {
+                let hir::ExprKind::Type(hir::Type {
+                    kind: hir::TypeKind::Elementary(hir::ElementaryType::FixedBytes(size)),
+                    ..
+                }) = callee.peel_parens().kind
+                else {
+                    return None;
+                };
+                if size.bytes() != 4 {
+                    return None;
+                }
+                // `bytes4(keccak256(...))` keeps the first 4 bytes of the hash.
+                let is_keccak256 = |expr: &hir::Expr<'_>| {
+                    matches!(
+                        expr.peel_parens().kind,
+                        hir::ExprKind::Ident(&[hir::Res::Builtin(Builtin::Keccak256)])
+                    )
+                };
+                match arg.peel_parens().kind {
+                    hir::ExprKind::Call(callee, _) if is_keccak256(callee) => {
+                        let hash = ConstantEvaluator::new(self.gcx).try_eval(arg).ok()?;
+                        Some(Selector::from_slice(&hash.data.to_be_bytes::<32>()[..4]))
+                    }
+                    _ => self.interface_id(arg, depth + 1),
+                }
+            }
+            hir::ExprKind::Ident(&[hir::Res::Item(hir::ItemId::Variable(v))]) => {
+                let var = self.gcx.hir.variable(v);
+                if var.mutability != Some(VarMut::Constant) {
+                    return None;
+                }
+                self.interface_id(var.initializer?, depth + 1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Visits the `supportsInterface` implementation of the first contract in `bases` that defines
+    /// one.
+    fn visit_base_implementation(
+        &mut self,
+        mut bases: impl Iterator<Item = hir::ContractId>,
+    ) -> ControlFlow<Never> {
+        match bases.find_map(|base| supports_interface(self.gcx, base)) {
+            Some(f) => self.visit_nested_function(f),
+            None => ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl<'gcx> Visit<'gcx> for InterfaceIdCollector<'gcx> {
+    type BreakValue = Never;
+
+    fn hir(&self) -> &'gcx hir::Hir<'gcx> {
+        &self.gcx.hir
+    }
+
+    fn visit_nested_function(&mut self, id: hir::FunctionId) -> ControlFlow<Self::BreakValue> {
+        if !self.visited.insert(id.into()) {
+            return ControlFlow::Continue(());
+        }
+        let func = self.gcx.hir.function(id);
+        let current = func.contract.unwrap_or(self.current);
+        let prev = std::mem::replace(&mut self.current, current);
+        let r = self.visit_function(func);
+        self.current = prev;
+        r
+    }
+
+    fn visit_expr(&mut self, expr: &'gcx hir::Expr<'gcx>) -> ControlFlow<Self::BreakValue> {
+        match expr.kind {
+            hir::ExprKind::Member(base, member) => match base.peel_parens().kind {
+                // `type(I).interfaceId`
+                hir::ExprKind::TypeCall(hir::Type {
+                    kind: hir::TypeKind::Custom(hir::ItemId::Contract(i)),
+                    ..
+                }) if member.as_str() == "interfaceId"
+                    && self.gcx.hir.contract(i).kind.is_interface() =>
+                {
+                    self.ids.insert(self.gcx.interface_id(i));
+                }
+                // `super.supportsInterface(...)`
+                hir::ExprKind::Ident(res)
+                    if member.as_str() == "supportsInterface"
+                        && res.contains(&hir::Res::Builtin(Builtin::Super)) =>
+                {
+                    let bases = self.gcx.hir.contract(self.contract).linearized_bases;
+                    let current = bases.iter().position(|&base| base == self.current);
+                    let bases = bases.iter().skip(current.map_or(1, |i| i + 1)).copied();
+                    self.visit_base_implementation(bases)?;
+                }
+                // `Base.supportsInterface(...)`
+                hir::ExprKind::Ident(res) if member.as_str() == "supportsInterface" => {
+                    let bases = res.iter().filter_map(|&res| hir::ContractId::try_from(res).ok());
+                    self.visit_base_implementation(bases)?;
+                }
+                _ => {}
+            },
+            hir::ExprKind::Lit(lit) => {
+                if let Some(id) = literal_id(lit) {
+                    self.ids.insert(id);
+                }
+            }
+            hir::ExprKind::Binary(_, op, _) if op.kind == hir::BinOpKind::BitXor => {
+                if let Some(id) = self.interface_id(expr, 0) {
+                    self.ids.insert(id);
+                }
+            }
+            hir::ExprKind::Call(..) => {
+                if let Some(id) = self.interface_id(expr, 0) {
+                    self.ids.insert(id);
+                }
+            }
+            hir::ExprKind::Ident(res) => {
+                for &res in res {
+                    match res {
+                        // Internal calls.
+                        hir::Res::Item(hir::ItemId::Function(f)) => {
+                            self.visit_nested_function(f)?
+                        }
+                        // Constants.
+                        hir::Res::Item(hir::ItemId::Variable(v)) => {
+                            let var = self.gcx.hir.variable(v);
+                            if var.mutability == Some(VarMut::Constant) {
+                                if self.visited.insert(v.into()) {
+                                    if let Some(init) = var.initializer {
+                                        self.visit_expr(init)?;
+                                    }
+                                }
+                            } else if var.is_state_variable() {
+                                self.reads_state = true;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.walk_expr(expr)
+    }
+}
+
+/// The maximum depth of nested expressions and constants to evaluate interface IDs through.
+const MAX_DEPTH: usize = 32;
+
+/// Returns the interface ID of a number literal of at most 4 bytes or a 4-byte string literal.
+fn literal_id(lit: &hir::Lit) -> Option<Selector> {
+    match &lit.kind {
+        LitKind::Number(n) => {
+            let (_, bytes) = n.to_bytes_be();
+            if bytes.len() > 4 {
+                return None;
+            }
+            let mut id = Selector::ZERO;
+            id[4 - bytes.len()..].copy_from_slice(&bytes);
+            Some(id)
+        }
+        LitKind::Str(_, bytes) => Selector::try_from(&bytes[..]).ok(),
+        _ => None,
+    }
+}
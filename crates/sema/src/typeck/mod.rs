@@ -1,20 +1,32 @@
 use crate::{
     ast_lowering::resolve::{Declaration, Declarations},
+    eval::ConstantEvaluator,
     hir::{self, Res},
-    ty::{Gcx, Ty},
+    ty::{erc7201_slot, Gcx, Ty},
 };
+use alloy_primitives::hex;
 use rayon::prelude::*;
 use solar_data_structures::{map::FxHashSet, parallel};
 
+mod erc165;
+
 pub(crate) fn check(gcx: Gcx<'_>) {
     parallel!(
         gcx.sess,
         gcx.hir.par_contract_ids().for_each(|id| {
             check_duplicate_definitions(gcx, &gcx.symbol_resolver.contract_scopes[id]);
+            check_storage_layout_specifier(gcx, id);
+            erc165::check_supports_interface(gcx, id);
         }),
         gcx.hir.par_source_ids().for_each(|id| {
             check_duplicate_definitions(gcx, &gcx.symbol_resolver.source_scopes[id]);
         }),
+        gcx.hir.par_strukt_ids().for_each(|id| {
+            check_storage_location(gcx, id);
+        }),
+        gcx.hir.par_item_ids().for_each(|id| {
+            check_natspec(gcx, id);
+        }),
     );
 }
 
@@ -80,3 +92,167 @@ fn same_external_params<'gcx>(gcx: Gcx<'gcx>, a: Ty<'gcx>, b: Ty<'gcx>) -> bool
     let key = |ty: Ty<'gcx>| ty.as_externally_callable_function(gcx).parameters().unwrap();
     key(a) == key(b)
 }
+
+/// Checks that the NatSpec tags of the given item match its definition.
+///
+/// The tags themselves are validated during lowering.
+fn check_natspec(gcx: Gcx<'_>, id: hir::ItemId) {
+    let natspec = gcx.hir.natspec(id);
+    if natspec.is_empty() {
+        return;
+    }
+    let item = gcx.hir.item(id);
+    let description = item.description();
+
+    if let Some(params) = item.parameters() {
+        for tag in natspec {
+            let hir::NatSpecKind::Param { name } = tag.kind else { continue };
+            if !params.iter().any(|&p| gcx.item_name_opt(p).is_some_and(|n| n.name == name)) {
+                let msg = format!(
+                    "documented parameter `{name}` not found in the parameter list of the \
+                     {description}"
+                );
+                gcx.dcx().err(msg).span(tag.span).emit();
+            }
+        }
+    }
+
+    // The names of the return parameters.
+    let returns = match id {
+        hir::ItemId::Function(f) => {
+            gcx.hir.function(f).returns.iter().map(|&r| gcx.item_name_opt(r)).collect()
+        }
+        hir::ItemId::Variable(v) => match gcx.hir.variable(v).getter {
+            Some(getter) => {
+                let returns = gcx.type_of_item(getter.into()).returns().unwrap_or_default();
+                vec![None; returns.len()]
+            }
+            None => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    let return_tags = natspec.iter().filter(|tag| tag.kind == hir::NatSpecKind::Return);
+    for (i, tag) in return_tags.enumerate() {
+        let Some(&name) = returns.get(i) else {
+            let msg = "`@return` tag exceeds the number of return parameters";
+            let s = if returns.len() == 1 { "" } else { "s" };
+            let owner = if id.as_variable().is_some() { "getter" } else { description };
+            let note = format!("the {owner} has {} return parameter{s}", returns.len());
+            gcx.dcx().err(msg).span(tag.span).note(note).emit();
+            break;
+        };
+        if let Some(name) = name {
+            if split_first_word(tag.content) != name.as_str() {
+                let msg = "`@return` tag does not start with the name of its return parameter";
+                let help = format!("document the return parameter as `@return {name} ...`");
+                gcx.dcx().err(msg).span(tag.span).help(help).emit();
+            }
+        }
+    }
+
+    let mut inheritdocs = natspec.iter().filter_map(|tag| match tag.kind {
+        hir::NatSpecKind::Inheritdoc { contract } => Some((contract, tag.span)),
+        _ => None,
+    });
+    if let Some((contract, span)) = inheritdocs.next() {
+        if contract.as_str().is_empty() {
+            gcx.dcx().err("`@inheritdoc` requires a contract name").span(span).emit();
+        } else if let Some(base) = gcx.inheritdoc_base(id, contract) {
+            if gcx.overridden_item(id, base).is_none() {
+                let msg = format!(
+                    "`@inheritdoc` references contract `{contract}`, which does not define a \
+                     function that is overridden by this {description}"
+                );
+                gcx.dcx().err(msg).span(span).emit();
+            }
+        } else {
+            let msg =
+                format!("`@inheritdoc` references `{contract}`, which is not a base contract");
+            gcx.dcx().err(msg).span(span).emit();
+        }
+    }
+    for (_, span) in inheritdocs {
+        gcx.dcx().err("`@inheritdoc` can only be given once").span(span).emit();
+    }
+}
+
+fn split_first_word(s: &str) -> &str {
+    s.split_whitespace().next().unwrap_or_default()
+}
+
+/// Checks the `layout at <slot>` specifier of the given contract.
+fn check_storage_layout_specifier(gcx: Gcx<'_>, id: hir::ContractId) {
+    let c = gcx.hir.contract(id);
+    if let Some(slot) = c.layout {
+        let kind = match c.kind {
+            hir::ContractKind::Contract => None,
+            hir::ContractKind::AbstractContract => Some("abstract contracts"),
+            hir::ContractKind::Interface => Some("interfaces"),
+            hir::ContractKind::Library => Some("libraries"),
+        };
+        if let Some(kind) = kind {
+            let msg = format!("storage layout cannot be specified for {kind}");
+            gcx.dcx().err(msg).span(slot.span).emit();
+        } else if ConstantEvaluator::new(gcx).eval(slot).is_ok() {
+            // Check that the variables fit in storage after the base slot.
+            let _ = gcx.storage_layout(id);
+        }
+    }
+
+    for &base in c.linearized_bases.iter().skip(1) {
+        if let Some(base_slot) = gcx.hir.contract(base).layout {
+            let msg = "storage layout can only be specified in the most derived contract";
+            gcx.dcx()
+                .err(msg)
+                .span(c.name.span)
+                .span_note(base_slot.span, "storage layout specified here")
+                .emit();
+        }
+    }
+}
+
+/// Checks the ERC-7201 `@custom:storage-location` annotation of the given struct, and that it
+/// matches the value of the struct's location constant, named `<Struct>Location` by convention.
+fn check_storage_location(gcx: Gcx<'_>, id: hir::StructId) {
+    let Some(annotation) = gcx.hir.natspec(id).iter().find(|item| {
+        matches!(item.kind, hir::NatSpecKind::Custom { name } if name.as_str() == "storage-location")
+    }) else {
+        return;
+    };
+    let Some(namespace) = gcx.struct_storage_namespace(id) else {
+        let msg = format!("unsupported storage location `{}`", annotation.content);
+        let help = "only ERC-7201 namespaces are supported: `erc7201:<namespace>`";
+        gcx.dcx().warn(msg).span(annotation.span).help(help).emit();
+        return;
+    };
+
+    let strukt = gcx.hir.strukt(id);
+    let scope = match strukt.contract {
+        Some(contract) => gcx.hir.contract(contract).items,
+        None => gcx.hir.source(strukt.source).items,
+    };
+    let name = format!("{}Location", strukt.name);
+    let Some(constant) =
+        scope.iter().filter_map(hir::ItemId::as_variable).map(|v| gcx.hir.variable(v)).find(|v| {
+            v.mutability == Some(hir::VarMut::Constant)
+                && v.name.is_some_and(|n| n.as_str() == name)
+        })
+    else {
+        return;
+    };
+    let Some(init) = constant.initializer else { return };
+    let Ok(value) = ConstantEvaluator::new(gcx).try_eval(init) else { return };
+    let expected = erc7201_slot(namespace);
+    if value.data != expected {
+        let msg = format!(
+            "storage location constant `{name}` does not match the namespace `erc7201:{namespace}`"
+        );
+        let note = format!("expected `{}`", hex::encode_prefixed(expected.to_be_bytes::<32>()));
+        gcx.dcx()
+            .warn(msg)
+            .span(constant.span)
+            .note(note)
+            .span_note(annotation.span, "namespace declared here")
+            .emit();
+    }
+}
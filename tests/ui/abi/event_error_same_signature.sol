@@ -0,0 +1,18 @@
+//@compile-flags: --emit=abi,error-hashes,event-hashes --pretty-json
+
+library L {
+    error Foo(uint256 value);
+
+    function check(uint256 value) internal pure {
+        if (value == 0) revert Foo(value);
+    }
+}
+
+contract C {
+    event Foo(uint256 value);
+
+    function f(uint256 value) public {
+        L.check(value);
+        emit Foo(value);
+    }
+}
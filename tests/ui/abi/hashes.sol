@@ -0,0 +1,14 @@
+//@compile-flags: --emit=hashes,error-hashes,event-hashes --pretty-json
+
+contract Base {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Anonymous(uint256 value) anonymous;
+
+    error Unauthorized(address caller);
+}
+
+contract C is Base {
+    error InsufficientBalance(uint256 available, uint256 required);
+
+    function transfer(address to, uint256 value) external {}
+}
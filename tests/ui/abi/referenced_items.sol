@@ -0,0 +1,44 @@
+//@compile-flags: --emit=abi,hashes,error-hashes,event-hashes --pretty-json
+
+event Log(string message);
+error FreeError(uint256 code);
+
+function fail(uint256 code) pure {
+    revert FreeError(code);
+}
+
+library Events {
+    event Deposit(address indexed account, uint256 amount);
+    event Unused();
+
+    function deposit(address account, uint256 amount) internal {
+        emit Deposit(account, amount);
+    }
+}
+
+library Errors {
+    error Empty();
+    error Unused();
+
+    function check(uint256 amount) public pure {
+        require(amount != 0, Empty());
+    }
+}
+
+contract Base {
+    function log() internal {
+        emit Log("base");
+    }
+}
+
+contract C is Base {
+    function deposit() external payable {
+        Events.deposit(msg.sender, msg.value);
+        Errors.check(msg.value);
+        log();
+    }
+
+    function withdraw(uint256 code) external pure {
+        fail(code);
+    }
+}
@@ -0,0 +1,33 @@
+//@compile-flags: --emit=abi --pretty-json
+
+interface IEvents {
+    event Transfer(address from, address to);
+    event Transfer(address from, address to, uint256 amount);
+    event Approval(address owner, uint256 amount);
+    event Approval(uint256 id, address spender);
+
+    error Failed(uint256 code);
+}
+
+contract Base {
+    event Transfer(address from, address to);
+
+    error Failed(uint256 code);
+}
+
+contract C is Base {
+    // Resolved by the number of arguments.
+    function transfer() external {
+        emit IEvents.Transfer(msg.sender, address(this), 1);
+    }
+
+    // Resolved by the names of the arguments.
+    function approve() external {
+        emit IEvents.Approval({amount: 1, owner: msg.sender});
+    }
+
+    // Same signature as `Base.Failed`, so it is only included once.
+    function fail() external pure {
+        revert IEvents.Failed(1);
+    }
+}
@@ -0,0 +1,7 @@
+function double(uint256 x) pure returns (uint256) {
+    return x * 2;
+}
+
+interface IMath {
+    function run(uint256 x) external returns (uint256);
+}
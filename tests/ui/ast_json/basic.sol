@@ -0,0 +1,50 @@
+//@compile-flags: --emit ast-json --pretty-json
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+/// @title A counter.
+contract Counter {
+    struct Entry {
+        address owner;
+        uint256 value;
+    }
+
+    enum Mode { Off, On }
+
+    event Incremented(address indexed by, uint256 value);
+    error TooLarge(uint256 value);
+
+    mapping(address => Entry) public entries;
+    Mode mode = Mode.On;
+
+    constructor() payable {}
+
+    receive() external payable {}
+
+    fallback() external {}
+
+    modifier whenOn() {
+        require(mode == Mode.On);
+        _;
+    }
+
+    function increment(uint256 by) external whenOn returns (uint256 total) {
+        Entry storage entry = entries[msg.sender];
+        if (entry.value + by > 100) revert TooLarge(entry.value + by);
+        entry.value += by;
+        emit Incremented(msg.sender, by);
+        return entry.value;
+    }
+
+    function sum(uint256[] memory values) public pure returns (uint256 s) {
+        for (uint256 i = 0; i < values.length; i++) {
+            unchecked {
+                s += values[i];
+            }
+        }
+        assembly {
+            let x := add(s, 1)
+            if gt(x, 2) { s := x }
+        }
+    }
+}
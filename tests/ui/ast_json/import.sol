@@ -0,0 +1,8 @@
+//@compile-flags: --combined-json ast --pretty-json
+import { double, IMath as Math } from "./auxiliary/math.sol";
+
+contract Doubler is Math {
+    function run(uint256 x) external pure override returns (uint256) {
+        return double(x);
+    }
+}
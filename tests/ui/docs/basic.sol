@@ -0,0 +1,51 @@
+//@compile-flags: --emit docs
+
+/// @title Counter interface
+interface ICounter {
+    /// @notice Emitted when the number changes.
+    /// @param number The new number.
+    event NumberChanged(uint256 indexed number);
+
+    /// @notice Thrown when the number is too large.
+    error TooLarge(uint256 number, uint256 max);
+
+    /// @notice Sets the number.
+    /// @param newNumber The new number.
+    function setNumber(uint256 newNumber) external;
+}
+
+/// @notice A simple counter.
+/// @dev Not safe for production.
+/// @author Solar
+contract Counter is ICounter {
+    /// @notice The current number.
+    uint256 public number;
+
+    uint256 internal constant MAX = 100;
+
+    constructor(uint256 initial) payable {
+        number = initial;
+    }
+
+    /// @inheritdoc ICounter
+    function setNumber(uint256 newNumber) external {
+        if (newNumber > MAX) revert TooLarge(newNumber, MAX);
+        number = newNumber;
+        emit NumberChanged(newNumber);
+    }
+
+    /// @notice Returns the number and whether it is even.
+    /// @return value The number.
+    /// @return even Whether the number is even.
+    function get() public view virtual returns (uint256 value, bool even) {
+        return (number, number % 2 == 0);
+    }
+
+    function _double(uint256 x) internal pure returns (uint256) {
+        return x * 2;
+    }
+
+    event Anonymous(address) anonymous;
+
+    receive() external payable {}
+}
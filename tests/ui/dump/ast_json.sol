@@ -0,0 +1,6 @@
+//@compile-flags: -Zdump=ast-json --pretty-json --stop-after parsing
+
+/// Doc.
+function f(uint8 x) pure returns (int) {
+    return -x;
+}
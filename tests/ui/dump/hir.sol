@@ -0,0 +1,29 @@
+//@compile-flags: -Zdump=hir
+
+type Amount is uint128;
+
+function twice(uint256 x) pure returns (uint256) {
+    return x * 2;
+}
+
+contract C {
+    struct S {
+        uint256 a;
+        bytes b;
+    }
+
+    mapping(address => S[]) public items;
+    uint256 public constant MAX = 10;
+
+    event Moved(address indexed from, uint256 amount);
+
+    function f(uint256 a) public returns (uint256 r) {
+        uint256[] memory xs = new uint256[](3);
+        for (uint256 i = 0; i < xs.length; i++) {
+            xs[i] = twice(i) + a;
+        }
+        require(a > 0, "zero");
+        emit Moved(msg.sender, a);
+        r = Amount.unwrap(Amount.wrap(uint128(a))) > 0 ? xs[0] : MAX;
+    }
+}
@@ -0,0 +1,5 @@
+//@compile-flags: -Zdump=hir-json --pretty-json
+
+contract C {
+    uint256[2] x;
+}
@@ -0,0 +1,19 @@
+//@compile-flags: -Zdump=hir=hir_select:C.f,hir_select.sol:D
+
+contract C {
+    uint256 x;
+
+    function f(uint256 a) public view returns (uint256) {
+        return a + x;
+    }
+
+    function f(bool b) public pure returns (bool) {
+        return !b;
+    }
+
+    function g() public {}
+}
+
+contract D {}
+
+contract E {}
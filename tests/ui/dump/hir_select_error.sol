@@ -0,0 +1,5 @@
+//@compile-flags: -Zdump=hir=hir_select_error:C.missing
+
+contract C {}
+
+contract D {}
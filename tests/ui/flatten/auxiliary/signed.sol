@@ -0,0 +1,16 @@
+/* SPDX-License-Identifier: Apache-2.0 */
+pragma solidity >=0.8.4 <0.9.0;
+pragma abicoder v2;
+
+import {Math} from "./math.sol";
+
+struct Point {
+    int256 x;
+    int256 y;
+}
+
+function origin() pure returns (Point memory) {}
+
+function max(int256 a, int256 b) pure returns (int256) {
+    return a > b ? a : b;
+}
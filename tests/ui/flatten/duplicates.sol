@@ -0,0 +1,17 @@
+//@compile-flags: flatten
+import {Point} from "./auxiliary/math.sol";
+import {Point as SignedPoint, origin} from "./auxiliary/signed.sol";
+
+function max(uint256 a, uint256 b) pure returns (uint256) {
+    return a > b ? a : b;
+}
+
+contract C {
+    Point point;
+    SignedPoint signedPoint;
+
+    function f(uint256 a) public {
+        point = Point(max(a, 1), 2);
+        signedPoint = origin();
+    }
+}
@@ -0,0 +1,10 @@
+//@compile-flags: flatten
+import {Math as M, Point as P} from "./auxiliary/math.sol";
+
+contract C {
+    P point;
+
+    function f(uint256 a) public {
+        point = P(M.max(a, point.x), 0);
+    }
+}
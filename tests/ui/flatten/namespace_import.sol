@@ -0,0 +1,13 @@
+//@compile-flags: flatten
+import "./auxiliary/math.sol" as MathLib;
+import * as Signed from "./auxiliary/signed.sol";
+
+contract C {
+    MathLib.Point point;
+    Signed.Point signedPoint;
+
+    function f(uint256 a) public {
+        point = MathLib.Point(MathLib.Math.max(a, 1), 2);
+        signedPoint = Signed.origin();
+    }
+}
@@ -0,0 +1,13 @@
+//@compile-flags: flatten
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+pragma abicoder v2;
+pragma experimental SMTChecker;
+
+import "./auxiliary/signed.sol";
+
+contract C {
+    function f() public pure returns (int256) {
+        return max(1, 2);
+    }
+}
@@ -0,0 +1,47 @@
+//@compile-flags: --emit interface
+// SPDX-License-Identifier: MIT
+
+type Price is uint128;
+
+interface IOracle {
+    function price() external view returns (Price);
+}
+
+/// @title Vault
+/// @notice Holds deposits.
+contract Vault {
+    enum Status {
+        Active,
+        Paused
+    }
+
+    struct Deposit {
+        address owner;
+        Price amount;
+        Status status;
+    }
+
+    /// @notice Emitted on deposit.
+    /// @param owner The depositor.
+    event Deposited(address indexed owner, Deposit deposit);
+
+    error Unauthorized(address caller);
+
+    /// @notice The oracle.
+    IOracle public oracle;
+
+    mapping(address => Deposit) public deposits;
+
+    /// @notice Deposits funds.
+    /// @param d The deposit.
+    /// @return id The deposit ID.
+    function deposit(Deposit memory d, string calldata note) public payable returns (uint256 id) {}
+
+    function names() external pure returns (string[] memory) {}
+
+    function pause() external {
+        revert Unauthorized(msg.sender);
+    }
+
+    receive() external payable {}
+}
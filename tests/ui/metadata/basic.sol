@@ -0,0 +1,13 @@
+//@ignore-host: windows
+//@compile-flags: --emit=metadata --pretty-json
+// SPDX-License-Identifier: MIT
+
+contract A {
+    event E(uint256 x);
+
+    function f(uint256 x) public returns (bool) {}
+}
+
+contract B is A {
+    uint256 public x;
+}
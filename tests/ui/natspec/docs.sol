@@ -0,0 +1,57 @@
+//@compile-flags: --emit userdoc,devdoc --pretty-json
+
+/// @title A token
+/// @author Solar
+/// @notice Keeps track of balances.
+/// @dev Not audited.
+/// @custom:security-contact security@example.com
+interface IToken {
+    /// @notice Emitted on transfers.
+    /// @param from The sender.
+    /// @param to The recipient.
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    /// @notice Thrown when the balance is too low.
+    /// @param needed The missing amount.
+    error Insufficient(uint256 needed);
+
+    /// @notice Returns the balance of `owner`.
+    /// @dev Reads from storage.
+    /// @param owner The account.
+    /// @return The balance.
+    function balanceOf(address owner) external view returns (uint256);
+
+    /**
+     * @notice Transfers tokens.
+     * @param to The recipient,
+     *           which must not be zero.
+     * @param amount The amount.
+     * @return ok Whether the transfer succeeded.
+     */
+    function transfer(address to, uint256 amount) external returns (bool ok);
+}
+
+/// The token implementation.
+contract Token is IToken {
+    /// @notice The total supply.
+    /// @dev Never decreases.
+    /// @return The supply.
+    uint256 public totalSupply;
+
+    /// @notice Creates the token.
+    /// @param supply The initial supply.
+    constructor(uint256 supply) {
+        totalSupply = supply;
+    }
+
+    /// @inheritdoc IToken
+    /// @dev Overrides the base details.
+    function balanceOf(address account) external pure returns (uint256) {
+        return account == address(0) ? 0 : 1;
+    }
+
+    function transfer(address, uint256) external returns (bool) {
+        emit Transfer(msg.sender, address(0), 0);
+        return true;
+    }
+}
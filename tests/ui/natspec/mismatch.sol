@@ -0,0 +1,45 @@
+contract Base {
+    /// @param a The value.
+    /// @param b Does not exist.
+    //~^ ERROR: documented parameter `b` not found in the parameter list of the function
+    /// @return The result.
+    /// @return Too many.
+    //~^ ERROR: `@return` tag exceeds the number of return parameters
+    function f(uint256 a) public virtual returns (uint256) {
+        return a;
+    }
+
+    /// @return y The result.
+    //~^ ERROR: `@return` tag does not start with the name of its return parameter
+    function g() public virtual returns (uint256 x) {}
+
+    /// @return The value.
+    /// @return Too many.
+    //~^ ERROR: `@return` tag exceeds the number of return parameters
+    uint256 public value;
+
+    /// @param missing Missing.
+    //~^ ERROR: documented parameter `missing` not found in the parameter list of the event
+    event E(uint256 value);
+}
+
+contract Other {
+    function f(uint256) public virtual returns (uint256) {}
+}
+
+contract Derived is Base {
+    /// @inheritdoc Other
+    //~^ ERROR: `@inheritdoc` references `Other`, which is not a base contract
+    function f(uint256 a) public override returns (uint256) {
+        return a;
+    }
+
+    /// @inheritdoc Base
+    //~^ ERROR: `@inheritdoc` references contract `Base`, which does not define a function that is overridden by this function
+    /// @inheritdoc Base
+    //~^^ ERROR: `@inheritdoc` can only be given once
+    function h() public {}
+
+    /// @inheritdoc Base
+    function g() public override returns (uint256) {}
+}
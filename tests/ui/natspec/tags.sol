@@ -0,0 +1,35 @@
+/// @title Base
+/// @notce Typo.
+//~^ ERROR: unknown NatSpec tag `@notce`
+/// @custom:Invalid Bad name.
+//~^ ERROR: invalid custom NatSpec tag `@custom:Invalid`
+/// @param x Not a function.
+//~^ ERROR: NatSpec tag `@param` is not valid for contract definitions
+/// @custom:security-contact security@example.com
+contract C {
+    /// @notice Private.
+    //~^ ERROR: NatSpec tag `@notice` is only valid for public state variables
+    /// @dev Fine.
+    uint256 internal hidden;
+
+    /// @return Not a function.
+    //~^ ERROR: NatSpec tag `@return` is not valid for error definitions
+    error Err();
+
+    /// @return Not a function.
+    //~^ ERROR: NatSpec tag `@return` is not valid for modifier definitions
+    modifier m() {
+        _;
+    }
+
+    /// @title Not a contract.
+    //~^ ERROR: NatSpec tag `@title` is not valid for function definitions
+    function f() public {
+        /// Not documentation.
+        //~^ WARN: doc comments on statements are ignored
+        uint256 x;
+
+        /// @solidity memory-safe-assembly
+        assembly {}
+    }
+}
@@ -0,0 +1,17 @@
+uint256 constant BASE = 0x1000;
+
+contract A layout at 42 {}
+
+contract B is A layout at BASE + 1 {}
+
+contract C layout at 2 ** 255 is A {}
+
+contract D layout at 1 layout at 2 {} //~ ERROR: storage layout was already specified
+
+contract E is A is B {} //~ ERROR: base contracts were already specified
+
+contract layout {}
+
+contract F is layout layout at 0 {}
+
+contract G is A, {} //~ ERROR: trailing `,` separator is not allowed
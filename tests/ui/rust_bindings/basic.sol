@@ -0,0 +1,27 @@
+//@compile-flags: --emit rust-bindings
+// SPDX-License-Identifier: MIT
+
+type Price is uint128;
+
+contract Vault {
+    enum Status {
+        Active,
+        Paused
+    }
+
+    struct Deposit {
+        address owner;
+        Price amount;
+        Status status;
+    }
+
+    event Deposited(address indexed owner, Deposit deposit);
+
+    error Unauthorized(address caller);
+
+    function deposit(Deposit memory d) public payable returns (uint256 id) {}
+
+    function pause() external {
+        revert Unauthorized(msg.sender);
+    }
+}
@@ -0,0 +1,15 @@
+//@compile-flags: --abi --hashes
+
+contract A {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    error Unauthorized(address caller);
+
+    function transfer(address to, uint256 value) external returns (bool) {}
+    function balanceOf(address owner) external view returns (uint256) {}
+}
+
+contract B is A {}
+
+interface I {
+    function f() external;
+}
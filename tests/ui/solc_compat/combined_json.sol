@@ -0,0 +1,7 @@
+//@compile-flags: --combined-json abi,hashes
+
+contract A {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    function transfer(address to, uint256 value) external returns (bool) {}
+}
@@ -0,0 +1,3 @@
+//@compile-flags: --combined-json abi,docs
+
+contract A {}
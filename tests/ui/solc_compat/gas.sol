@@ -0,0 +1,3 @@
+//@compile-flags: --gas
+
+contract C {}
@@ -0,0 +1,8 @@
+//@compile-flags: --standard-json=../../tests/ui/standard_json/basic.json --pretty-json
+
+/// @notice A base contract loaded from `urls`.
+abstract contract Base {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    function totalSupply() public view virtual returns (uint256);
+}
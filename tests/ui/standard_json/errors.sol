@@ -0,0 +1,3 @@
+//@compile-flags: --standard-json=../../tests/ui/standard_json/errors.json
+
+contract C {}
@@ -0,0 +1,4 @@
+contract Base {
+    uint256 internal base;
+    uint256[10] private __gap;
+}
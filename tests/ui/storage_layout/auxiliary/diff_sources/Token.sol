@@ -0,0 +1,12 @@
+import "./Base.sol";
+
+contract Token is Base {
+    uint256 public supply;
+    mapping(address => uint256) public balances;
+    uint256 public cap;
+}
+
+contract Vault {
+    address owner;
+    uint256 transient locked;
+}
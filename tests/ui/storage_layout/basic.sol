@@ -0,0 +1,34 @@
+//@ignore-host: windows
+//@compile-flags: --emit=storage-layout,transient-storage-layout --pretty-json
+
+contract A {
+    uint128 a;
+    uint128 b;
+    bool c;
+    uint256 constant C = 1;
+    address immutable I = address(0);
+}
+
+contract B is A {
+    enum E { X, Y }
+    struct S {
+        uint8 x;
+        uint256 y;
+        address z;
+    }
+    type U is uint64;
+
+    E e;
+    S s;
+    uint16[5] small;
+    uint256[2] big;
+    mapping(address => mapping(string => S)) m;
+    bytes bs;
+    string str;
+    S[] list;
+    U u;
+    function(uint256) external returns (bool) f;
+    A other;
+    bool transient locked;
+    uint256 transient counter;
+}
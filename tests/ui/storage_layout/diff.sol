@@ -0,0 +1,47 @@
+//@compile-flags: --storage-layout-baseline ../../tests/ui/storage_layout/diff.json
+
+struct S {
+    uint256 a;
+    uint256 b;
+}
+
+struct T {
+    uint128 a;
+    uint256 b;
+}
+
+contract Base {
+    uint256 a;
+    uint256 b;
+    uint256[9] __gap;
+}
+
+contract C is Base {
+    uint128 x;
+    int128 y; //~ ERROR: storage variable `y` changed type from `uint128` to `int128`
+    address admin; //~ WARN: storage variable `owner` was renamed to `admin`
+    uint128 total; //~ ERROR: storage variable `total` was shrunk from `uint256` to `uint128`
+    bytes32 hash; //~ ERROR: storage variable `removed` was replaced by `hash` with a different type
+    mapping(address => uint256) balances;
+    S s; //~ ERROR: the layout of storage variable `s` of type `struct S` changed
+}
+
+contract D {
+    uint256 a;
+    uint256 c;
+    uint256[10] __gap; //~ ERROR: storage gap `__gap` was resized incorrectly
+    uint256 b; //~ ERROR: storage variable `b` was moved
+}
+
+contract E { //~ ERROR: storage variable `b` was removed
+    uint256 a;
+}
+
+contract F {
+    uint256 transient t; //~ ERROR: transient storage variable `t` changed type from `uint128` to `uint256`
+    uint128 transient u; //~ ERROR: transient storage variable `u` was moved
+}
+
+contract G {
+    mapping(uint256 => T) ts; //~ ERROR: the layout of storage variable `ts` of type `mapping(uint256 => struct T)` changed
+}
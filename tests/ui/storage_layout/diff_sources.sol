@@ -0,0 +1,22 @@
+//@compile-flags: --storage-layout-baseline ../../tests/ui/storage_layout/auxiliary/diff_sources
+
+contract Base {
+    uint256 internal base;
+    uint256 internal fee;
+    uint256[9] private __gap;
+}
+
+contract Token is Base {
+    mapping(address => uint256) public balances; //~ ERROR: storage variable `balances` was moved
+    uint256 public supply; //~ ERROR: storage variable `supply` was moved
+    uint128 public cap; //~ ERROR: storage variable `cap` was shrunk from `uint256` to `uint128`
+}
+
+contract Vault {
+    address owner;
+    bool transient locked; //~ ERROR: transient storage variable `locked` was shrunk from `uint256` to `bool`
+}
+
+contract New {
+    uint256 x;
+}
@@ -0,0 +1,42 @@
+//@compile-flags: --emit storage-layout
+
+contract Ownable {
+    /// @custom:storage-location erc7201:example.main
+    struct MainStorage {
+        uint256 x;
+        address owner;
+        bool flag;
+    }
+
+    // keccak256(abi.encode(uint256(keccak256("example.main")) - 1)) & ~bytes32(uint256(0xff))
+    bytes32 private constant MainStorageLocation =
+        0x183a6125c38840424c4a85fa12bab2ab606c4b6d0e7cc73c0c06ba5300eab500;
+
+    uint256 a;
+}
+
+contract C is Ownable {
+    /**
+     * @dev Computed from the formula.
+     * @custom:storage-location erc7201:example.other
+     */
+    struct OtherStorage {
+        mapping(address => uint256) balances;
+    }
+
+    bytes32 private constant OtherStorageLocation =
+        keccak256(abi.encode(uint256(keccak256("example.other")) - 1)) & ~bytes32(uint256(0xff));
+
+    /// @custom:storage-location erc7201:example.wrong
+    struct WrongStorage {
+        uint256 y;
+    }
+
+    bytes32 private constant WrongStorageLocation = //~ WARN: storage location constant `WrongStorageLocation` does not match the namespace `erc7201:example.wrong`
+        keccak256(abi.encode(uint256(keccak256("example.main")) - 1)) & ~bytes32(uint256(0xff));
+
+    /// @custom:storage-location erc1234:unsupported
+    struct UnsupportedStorage { //~^ WARN: unsupported storage location `erc1234:unsupported`
+        uint256 z;
+    }
+}
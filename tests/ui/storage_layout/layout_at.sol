@@ -0,0 +1,14 @@
+//@compile-flags: --emit storage-layout,transient-storage-layout
+
+uint256 constant BASE = 0x1000;
+
+contract A layout at BASE + 1 {
+    uint256 a;
+    uint128 b;
+    uint256 transient t;
+}
+
+contract B layout at 0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe {
+    uint256 a;
+    uint256 b;
+}
@@ -0,0 +1,14 @@
+uint constant big = 300;
+
+contract C {
+    uint[uint8(255)] public a;
+    uint[uint16(big)] public b;
+    uint[uint8(int8(int16(100)))] public c;
+    uint[uint256(bytes32(uint256(1)))] public d;
+    uint[uint160(address(uint160(2)))] public e;
+
+    uint[uint8(big)] public f; //~ ERROR: evaluation of constant value failed
+    uint[uint8(big - 44)] public g; //~ ERROR: evaluation of constant value failed
+    uint[int8(big)] public h; //~ ERROR: evaluation of constant value failed
+    uint[bytes4(uint32(1))] public i; //~ ERROR: evaluation of constant value failed
+}
@@ -0,0 +1,16 @@
+contract A layout at 1 {}
+
+contract B is A {} //~ ERROR: storage layout can only be specified in the most derived contract
+
+abstract contract C layout at 1 {} //~ ERROR: storage layout cannot be specified for abstract contracts
+
+interface I layout at 1 {} //~ ERROR: storage layout cannot be specified for interfaces
+
+library L layout at 1 {} //~ ERROR: storage layout cannot be specified for libraries
+
+contract D layout at 1 / 0 {} //~ ERROR: evaluation of constant value failed
+
+contract E layout at 0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff { //~ ERROR: contract requires too much storage
+    uint256 a;
+    uint256 b;
+}
@@ -0,0 +1,11 @@
+//@compile-flags: --libraries typeck/libraries.sol:L=0x1111111111111111111111111111111111111111,typeck/libraries.sol:C=0x1111111111111111111111111111111111111111,typeck/libraries.sol:Missing=0x1111111111111111111111111111111111111111,other.sol:L=0x1111111111111111111111111111111111111111
+
+library L {
+    function f() public {}
+}
+
+contract C {
+    function g() public {
+        L.f();
+    }
+}
@@ -0,0 +1,83 @@
+interface IERC165 {
+    function supportsInterface(bytes4 interfaceId) external view returns (bool);
+}
+
+interface IERC721 is IERC165 {
+    function balanceOf(address owner) external view returns (uint256);
+    function ownerOf(uint256 tokenId) external view returns (address);
+}
+
+interface IERC721Metadata is IERC721 {
+    function name() external view returns (string memory);
+    function symbol() external view returns (string memory);
+    function tokenURI(uint256 tokenId) external view returns (string memory);
+}
+
+interface IEmpty {}
+
+abstract contract ERC165 is IERC165 {
+    function supportsInterface(bytes4 interfaceId) public view virtual returns (bool) {
+        return interfaceId == type(IERC165).interfaceId;
+    }
+}
+
+abstract contract ERC721 is ERC165, IERC721Metadata {
+    function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {
+        return interfaceId == type(IERC721).interfaceId
+            || interfaceId == type(IERC721Metadata).interfaceId
+            || super.supportsInterface(interfaceId);
+    }
+}
+
+interface IERC2981 is IERC165 {
+    function royaltyInfo(uint256 tokenId, uint256 salePrice) external view returns (address, uint256);
+}
+
+abstract contract Royalties is ERC721, IERC2981 {
+    bytes4 private constant INTERFACE_ID_ERC2981 = 0x2a55205a;
+
+    function supportsInterface(bytes4 interfaceId) public view virtual override(ERC721, IERC165) returns (bool) {
+        return interfaceId == INTERFACE_ID_ERC2981 || ERC721.supportsInterface(interfaceId);
+    }
+}
+
+abstract contract Missing is ERC165, IERC721Metadata, IEmpty {
+    function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {
+        //~^ WARN: `supportsInterface` does not handle the ID of the inherited interface `IERC721Metadata`
+        //~| WARN: `supportsInterface` does not handle the ID of the inherited interface `IERC721`
+        return interfaceId == 0x01ffc9a7;
+    }
+}
+
+abstract contract NoOverride is ERC721, IERC2981 {}
+
+abstract contract Registry is ERC165, IERC721Metadata {
+    mapping(bytes4 => bool) private _supportedInterfaces;
+
+    constructor() {
+        _supportedInterfaces[type(IERC721).interfaceId] = true;
+        _supportedInterfaces[type(IERC721Metadata).interfaceId] = true;
+    }
+
+    function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {
+        return _supportedInterfaces[interfaceId];
+    }
+}
+
+abstract contract HashedIds is ERC721, IERC2981 {
+    bytes4 private constant INTERFACE_ID_ERC2981 = bytes4(keccak256("royaltyInfo(uint256,uint256)"));
+
+    function supportsInterface(bytes4 interfaceId) public view virtual override(ERC721, IERC165) returns (bool) {
+        return interfaceId == INTERFACE_ID_ERC2981 || super.supportsInterface(interfaceId);
+    }
+}
+
+abstract contract XorIds is ERC165, IERC721Metadata {
+    bytes4 private constant INTERFACE_ID_ERC721 =
+        bytes4(keccak256("balanceOf(address)")) ^ bytes4(keccak256("ownerOf(uint256)"));
+
+    function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {
+        //~^ WARN: `supportsInterface` does not handle the ID of the inherited interface `IERC721Metadata`
+        return interfaceId == INTERFACE_ID_ERC721 || super.supportsInterface(interfaceId);
+    }
+}